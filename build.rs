@@ -1,16 +1,208 @@
-use std::fmt::Display;
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+/// 探索するライブラリ名（優先順）
+///
+/// Note: 最近のリリースでは`slang-compiler`にリネームされている。古いリリースは`slang`だが、
+/// Gentooなどでは別パッケージ（S-Lang）のlibslangと名前が被っているため、pkg-configでは`slang`を探さない
+/// （S-Langのslang.pcを拾ってしまう）。ディレクトリ探索ではSlangのインストール先と分かっている場所だけを見るので両方探す
+const LIB_NAMES: &[&str] = &["slang-compiler", "slang"];
+#[cfg(not(feature = "vendored"))]
+const PKG_CONFIG_NAMES: &[&str] = &["slang-compiler", "shader-slang"];
 
 fn main() {
+    if std::env::var_os("CARGO_FEATURE_DYNAMIC_LOADING").is_some() {
+        // ライブラリは実行時にロードするのでリンクしない
+        return;
+    }
+
+    rerun_if_env_changed("DOCS_RS");
+    if std::env::var_os("DOCS_RS").is_some() {
+        // docs.rsにはライブラリがないのでリンクしない
+        return;
+    }
+
+    // Note: Slangが無い環境でも`cargo check`/`clippy`できるようにするための明示的なオプトアウト
+    // （実行ファイルのリンクは未解決シンボルで失敗する）
+    rerun_if_env_changed("SLANG_SKIP_LINK");
+    if std::env::var_os("SLANG_SKIP_LINK").is_some_and(|v| !v.is_empty() && v != "0") {
+        return;
+    }
+
+    #[cfg(feature = "vendored")]
+    build_vendored();
+    #[cfg(not(feature = "vendored"))]
+    find_installed();
+}
+
+/// システムにインストールされたSlangを探してリンクする
+#[cfg(not(feature = "vendored"))]
+fn find_installed() {
     // Note: プラットフォームによって異なる（Gentooとかは別のslangパッケージとライブラリ名が被ってたりする）ので
     // SLANG_LIB_***を環境変数で指定することでリンク情報を上書きできるようにしている
     rerun_if_env_changed("SLANG_LIB_NAME");
     rerun_if_env_changed("SLANG_LIB_PATH");
-    if let Some(libname) = std::env::var_os("SLANG_LIB_NAME") {
-        link_lib(&libname.display());
+    let lib_name = std::env::var_os("SLANG_LIB_NAME");
+    let lib_path = std::env::var_os("SLANG_LIB_PATH");
+    if lib_name.is_some() || lib_path.is_some() {
+        if let Some(ref libpath) = lib_path {
+            link_search(&libpath.display());
+            rpath(Path::new(libpath));
+        }
+        match lib_name {
+            Some(libname) => link_lib(&libname.display()),
+            None => {
+                let dir = PathBuf::from(lib_path.unwrap());
+                let Some(name) = find_lib_in(&dir) else {
+                    panic!(
+                        "no Slang library ({}) found in SLANG_LIB_PATH={}; set SLANG_LIB_NAME to the library name",
+                        LIB_NAMES.join(", "),
+                        dir.display()
+                    );
+                };
+                link_lib(name);
+            }
+        }
+
+        return;
+    }
+
+    let mut tried = Vec::new();
+
+    for name in PKG_CONFIG_NAMES {
+        match pkg_config::Config::new().probe(name) {
+            Ok(lib) => {
+                for path in &lib.link_paths {
+                    rpath(path);
+                }
+
+                return;
+            }
+            Err(e) => tried.push(format!("pkg-config `{name}`: {}", pkg_config_failure(&e))),
+        }
+    }
+
+    for (env, subdirs) in [
+        ("SLANG_DIR", &["lib"][..]),
+        ("VULKAN_SDK", &["lib", "Lib"][..]),
+    ] {
+        rerun_if_env_changed(env);
+        let Some(prefix) = std::env::var_os(env) else {
+            tried.push(format!("{env}: not set"));
+            continue;
+        };
+
+        for subdir in subdirs {
+            let dir = Path::new(&prefix).join(subdir);
+            if let Some(name) = find_lib_in(&dir) {
+                link_search(&dir.display());
+                link_lib(name);
+                rpath(&dir);
+
+                return;
+            }
+
+            tried.push(format!("{env}: no Slang library in {}", dir.display()));
+        }
     }
-    if let Some(libpath) = std::env::var_os("SLANG_LIB_PATH") {
-        link_search(&libpath.display());
+
+    panic!(
+        "could not find the Slang library. Tried:\n{}\n\n\
+        Install Slang (e.g. with the Vulkan SDK), point SLANG_DIR at its install prefix, \
+        set SLANG_LIB_NAME/SLANG_LIB_PATH explicitly, or enable the `dynamic-loading` feature. \
+        Set SLANG_SKIP_LINK=1 to build without linking (e.g. for `cargo check`).",
+        tried
+            .iter()
+            .map(|t| format!("  - {t}"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    );
+}
+
+/// ソースツリーからCMakeでSlangをビルドしてリンクする
+///
+/// ソースは`SLANG_SOURCE_DIR`、なければ`vendor/slang`（git submodule）を使う。
+/// LLVMのダウンロードなどネットワークを必要とするものは無効にしている
+#[cfg(feature = "vendored")]
+fn build_vendored() {
+    rerun_if_env_changed("SLANG_SOURCE_DIR");
+    let source_dir = match std::env::var_os("SLANG_SOURCE_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => Path::new(env!("CARGO_MANIFEST_DIR")).join("vendor/slang"),
+    };
+    if !source_dir.join("CMakeLists.txt").is_file() {
+        panic!(
+            "the `vendored` feature needs the Slang source tree, but {} does not contain CMakeLists.txt. \
+            Set SLANG_SOURCE_DIR to a Slang checkout (with its submodules), or run \
+            `git submodule update --init --recursive vendor/slang`.",
+            source_dir.display()
+        );
     }
+    println!("cargo::rerun-if-changed={}", source_dir.display());
+
+    let install_dir = cmake::Config::new(&source_dir)
+        .define("SLANG_LIB_TYPE", "SHARED")
+        .define("SLANG_SLANG_LLVM_FLAVOR", "DISABLE")
+        .define("SLANG_ENABLE_TESTS", "OFF")
+        .define("SLANG_ENABLE_EXAMPLES", "OFF")
+        .define("SLANG_ENABLE_GFX", "OFF")
+        .define("SLANG_ENABLE_SLANGD", "OFF")
+        .define("SLANG_ENABLE_SLANGC", "OFF")
+        .define("SLANG_ENABLE_SLANGRT", "OFF")
+        .define("SLANG_ENABLE_SLANG_RHI", "OFF")
+        .define("SLANG_ENABLE_REPLAYER", "OFF")
+        .build();
+
+    let lib_dir = install_dir.join("lib");
+    let Some(name) = find_lib_in(&lib_dir) else {
+        panic!(
+            "built Slang from {}, but no library ({}) was installed to {}",
+            source_dir.display(),
+            LIB_NAMES.join(", "),
+            lib_dir.display()
+        );
+    };
+    link_search(&lib_dir.display());
+    link_lib(name);
+    rpath(&lib_dir);
+}
+
+/// `dir`に存在するSlangライブラリのリンク名を返す
+fn find_lib_in(dir: &Path) -> Option<&'static str> {
+    let windows = std::env::var("CARGO_CFG_TARGET_OS").is_ok_and(|os| os == "windows");
+    let macos = std::env::var("CARGO_CFG_TARGET_OS").is_ok_and(|os| os == "macos");
+
+    LIB_NAMES.iter().copied().find(|name| {
+        let files = if windows {
+            vec![format!("{name}.lib")]
+        } else if macos {
+            vec![format!("lib{name}.dylib"), format!("lib{name}.a")]
+        } else {
+            vec![format!("lib{name}.so"), format!("lib{name}.a")]
+        };
+
+        files.iter().any(|f| dir.join(f).is_file())
+    })
+}
+
+/// pkg-configの失敗理由を1行で返す
+#[cfg(not(feature = "vendored"))]
+fn pkg_config_failure(e: &pkg_config::Error) -> String {
+    let message = match e {
+        pkg_config::Error::ProbeFailure { output, .. } => {
+            String::from_utf8_lossy(&output.stderr).into_owned()
+        }
+        e => e.to_string(),
+    };
+
+    message
+        .lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty())
+        .unwrap_or("failed")
+        .to_owned()
 }
 
 fn rerun_if_env_changed(env: &(impl Display + ?Sized)) {
@@ -24,3 +216,11 @@ fn link_search(path: &(impl Display + ?Sized)) {
 fn link_lib(name: &(impl Display + ?Sized)) {
     println!("cargo::rustc-link-lib={name}");
 }
+
+/// 実行ファイル（example/test等）がライブラリを見つけられるようにrpathを埋め込む
+fn rpath(dir: &Path) {
+    let os = std::env::var("CARGO_CFG_TARGET_OS").unwrap_or_default();
+    if os != "windows" {
+        println!("cargo::rustc-link-arg=-Wl,-rpath,{}", dir.display());
+    }
+}
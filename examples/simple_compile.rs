@@ -1,36 +1,29 @@
-use core::{ffi::CStr, mem::MaybeUninit};
+use core::mem::MaybeUninit;
 
-use slang::{IBlob, IComponentType, IGlobalSession, IModule, ISession, IUnknown};
+use slang::{Diagnosed, IBlob, IComponentType, IGlobalSession, IModule, ISession, IUnknown};
+
+fn print_diagnostics<T>(r: slang::DiagnosedResult<T>) -> Result<T, slang::CompileError> {
+    let Diagnosed { value, diagnostics } = r?;
+    if let Some(d) = diagnostics {
+        for x in d.lines() {
+            eprintln!("diag: {x}");
+        }
+    }
+
+    Ok(value)
+}
 
 fn main() {
     let g_session = slang::create_global_session(&slang::GlobalSessionDesc::default())
         .expect("create_global_session failed");
-    let targets = [slang::TargetDesc {
-        format: slang::ffi::SLANG_SPIRV,
-        profile: g_session.find_profile(c"glsl_450"),
-        ..Default::default()
-    }];
     let session = g_session
-        .create_session(&slang::SessionDesc {
-            targets: targets.as_ptr(),
-            target_count: targets.len() as _,
-            ..Default::default()
-        })
+        .create_session(
+            &slang::SessionDescBuilder::new()
+                .target(slang::ffi::SLANG_SPIRV, g_session.find_profile(c"glsl_450")),
+        )
         .unwrap();
-    let mut diag = MaybeUninit::zeroed();
-    let module = session.load_module(c"test", Some(&mut diag));
-    let diag = unsafe { diag.assume_init() };
-    if let Some(d) = diag {
-        let diag_str = unsafe {
-            CStr::from_ptr(d.get_buffer_pointer() as _)
-                .to_str()
-                .unwrap_unchecked()
-        };
-        for x in diag_str.lines() {
-            eprintln!("diag: {x}");
-        }
-    }
-    let module = module.expect("Failed to load module");
+    let module = print_diagnostics(session.load_module_with_diagnostics(c"test"))
+        .unwrap_or_else(|e| panic!("Failed to load module: {e}"));
 
     let mut program_components = Vec::<slang::IComponentTypePtr>::with_capacity(
         1 + module.get_defined_entry_point_count() as usize,
@@ -41,57 +34,49 @@ fn main() {
             .clone_cast()
             .expect("failed to cast")
     }));
-    let mut diag = MaybeUninit::new(None);
-    let program = session.create_composite_component_type(&program_components, Some(&mut diag));
-    if let Some(d) = unsafe { diag.assume_init() } {
-        println!("diag: {:?}", unsafe {
-            CStr::from_ptr(d.get_buffer_pointer() as _)
-        });
-    }
-    let program = program.expect("failed to create program");
+    let program = print_diagnostics(
+        session.create_composite_component_type_with_diagnostics(&program_components),
+    )
+    .unwrap_or_else(|e| panic!("failed to create program: {e}"));
 
     let mut diag = MaybeUninit::new(None);
     let layout = program.get_layout(0, Some(&mut diag));
     if let Some(d) = unsafe { diag.assume_init() } {
-        println!("diag: {:?}", unsafe {
-            CStr::from_ptr(d.get_buffer_pointer() as _)
-        });
+        println!("diag: {:?}", d.as_str());
     }
+    let layout = layout.expect("failed to get layout");
     for (n, ep) in layout.iter_entry_point().enumerate() {
-        let fr = ep.function();
+        let fr = ep.function().expect("entry point without function");
         let name = ep.name();
         let stage = ep.stage();
 
         println!("ep {n} {name:?} {stage}");
 
         for (np, param) in ep.iter_parameter().enumerate() {
-            let pv = param.variable();
+            let pv = param.variable().expect("parameter without variable");
             let param_semantic_name = param.semantic_name();
-            let param_type = pv.r#type();
+            let param_type = pv.r#type().expect("parameter without type");
             let param_type_name_blob = param_type
                 .full_name()
                 .expect("spReflectionType_GetFullName failed");
-            let param_type_name =
-                unsafe { CStr::from_ptr(param_type_name_blob.get_buffer_pointer() as _) };
+            let param_type_name = param_type_name_blob.as_str().unwrap_or_default();
 
             println!("  param {np} {param_type_name:?} {param_semantic_name:?}");
         }
 
-        let rt = fr.result_type();
+        let rt = fr.result_type().expect("function without result type");
         let rt_name_blob = rt.full_name().expect("spReflectionType_GetFullName failed");
-        let rt_name = unsafe { CStr::from_ptr(rt_name_blob.get_buffer_pointer() as _) };
-        let rtl = ep.result_var_layout();
-        let rt_semantic_name = rtl.semantic_name();
+        let rt_name = rt_name_blob.as_str().unwrap_or_default();
+        let rt_semantic_name = ep.result_var_layout().and_then(|l| l.semantic_name());
 
         println!("  rt {rt_name:?} {rt_semantic_name:?}");
     }
     for (n, param) in layout.iter_parameter().enumerate() {
-        let pv = param.variable();
+        let pv = param.variable().expect("parameter without variable");
         let param_semantic_name = param.semantic_name();
-        let param_type = pv.r#type();
+        let param_type = pv.r#type().expect("parameter without type");
         let param_type_name_blob = param_type.full_name().expect("param_type.full_name failed");
-        let param_type_name =
-            unsafe { CStr::from_ptr(param_type_name_blob.get_buffer_pointer() as _) };
+        let param_type_name = param_type_name_blob.as_str().unwrap_or_default();
         let binding_index = param.binding_index();
         let binding_space = param.binding_space();
 
@@ -103,29 +88,11 @@ fn main() {
     let gcb_size = layout.global_constant_buffer_size();
     println!("global constant buffer: {gcb_binding} {gcb_size}");
 
-    let mut diag = MaybeUninit::new(None);
-    let linked = program.link(Some(&mut diag));
-    if let Some(d) = unsafe { diag.assume_init() } {
-        println!("diag: {:?}", unsafe {
-            CStr::from_ptr(d.get_buffer_pointer() as _)
-        });
-    }
-    let linked = linked.expect("Failed to link");
+    let linked = print_diagnostics(program.link_with_diagnostics())
+        .unwrap_or_else(|e| panic!("Failed to link: {e}"));
 
-    let mut diag = MaybeUninit::new(None);
-    let spv_code = linked.get_target_code(0, Some(&mut diag));
-    if let Some(d) = unsafe { diag.assume_init() } {
-        println!("diag: {:?}", unsafe {
-            CStr::from_ptr(d.get_buffer_pointer() as _)
-        });
-    }
-    let spv_code = spv_code.expect("Failed to generate spirv");
+    let spv_code = print_diagnostics(linked.get_target_code_with_diagnostics(0))
+        .unwrap_or_else(|e| panic!("Failed to generate spirv: {e}"));
     println!("code length: {}", spv_code.get_buffer_size());
-    std::fs::write("test.spv", unsafe {
-        core::slice::from_raw_parts(
-            spv_code.get_buffer_pointer() as *const u8,
-            spv_code.get_buffer_size(),
-        )
-    })
-    .expect("Failed to write spv");
+    std::fs::write("test.spv", spv_code.as_bytes()).expect("Failed to write spv");
 }
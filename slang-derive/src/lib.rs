@@ -0,0 +1,99 @@
+//! Derive macros for the `slang` crate (enabled with its `derive` feature).
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, LitStr, ext::IdentExt, parse_macro_input, spanned::Spanned};
+
+/// Implements `slang::layout::ShaderLayout` for a `#[repr(C)]` struct with named fields.
+///
+/// Fields are matched with shader fields by name; use `#[shader(rename = "name")]` if the names differ.
+/// Fields starting with `_` are treated as padding.
+#[proc_macro_derive(ShaderLayout, attributes(shader))]
+pub fn derive_shader_layout(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    shader_layout(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn shader_layout(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let Data::Struct(ref data) = input.data else {
+        return Err(syn::Error::new(
+            input.ident.span(),
+            "ShaderLayout can only be derived for structs",
+        ));
+    };
+    let Fields::Named(ref fields) = data.fields else {
+        return Err(syn::Error::new(
+            data.fields.span(),
+            "ShaderLayout requires named fields",
+        ));
+    };
+    if !is_repr_c(&input)? {
+        return Err(syn::Error::new(
+            input.ident.span(),
+            "ShaderLayout requires #[repr(C)] (the Rust layout is unspecified otherwise)",
+        ));
+    }
+
+    let mut host_fields = Vec::new();
+    for f in &fields.named {
+        let ident = f.ident.as_ref().expect("named field");
+        let mut name = ident.unraw().to_string();
+        for attr in f.attrs.iter().filter(|a| a.path().is_ident("shader")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    name = meta.value()?.parse::<LitStr>()?.value();
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown shader attribute (expected `rename`)"))
+                }
+            })?;
+        }
+        let ty = &f.ty;
+
+        host_fields.push(quote! {
+            ::slang::layout::HostField {
+                name: #name,
+                offset: ::core::mem::offset_of!(Self, #ident),
+                ty: <#ty as ::slang::layout::ShaderLayout>::host_type(),
+            }
+        });
+    }
+
+    let ident = &input.ident;
+    let name = ident.unraw().to_string();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::slang::layout::ShaderLayout for #ident #ty_generics #where_clause {
+            fn host_type() -> ::slang::layout::HostType {
+                ::slang::layout::HostType::Struct(::slang::layout::HostStruct {
+                    name: #name,
+                    size: ::core::mem::size_of::<Self>(),
+                    fields: ::std::vec![#(#host_fields),*],
+                })
+            }
+        }
+    })
+}
+
+fn is_repr_c(input: &DeriveInput) -> syn::Result<bool> {
+    let mut repr_c = false;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("repr")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("C") {
+                repr_c = true;
+            } else if meta.input.peek(syn::token::Paren) {
+                // align(N) etc.
+                let content;
+                syn::parenthesized!(content in meta.input);
+                content.parse::<proc_macro2::TokenStream>()?;
+            }
+            Ok(())
+        })?;
+    }
+
+    Ok(repr_c)
+}
@@ -0,0 +1,335 @@
+//! Program-wide traversal of resource bindings, shared by the [`crate::vulkan`] and [`crate::webgpu`] mappers.
+//!
+//! Binding and set numbers are the ones Slang assigned, so explicit `[[vk::binding]]` / `register` locations are
+//! honoured. They are computed the way Slang's reflection documentation describes:
+//!
+//! * every binding range of a scope takes the binding of its first descriptor range
+//!   (`descriptor_set_descriptor_range_index_offset`), offset by the `DescriptorTableSlot` offsets of the variables on
+//!   the path to the scope; its set is the space of that path plus the space offset of the descriptor set
+//! * a scope with ordinary (uniform) data is wrapped in a constant buffer, whose container is the automatically
+//!   introduced uniform buffer
+//! * `ConstantBuffer<T>` takes one binding, and the resources inside `T` follow at the offset of its sub-object range
+//! * every `ParameterBlock<T>` is a sub-object range with a set of its own (`sub_object_range_space_offset`), holding
+//!   an automatically introduced uniform buffer for the ordinary data of `T` and the resources of `T`
+//! * every `[vk::push_constant]` buffer becomes a push-constant block
+
+use crate::{
+    ffi::SlangInt,
+    reflection::{
+        BindingType, ParameterCategory, ScalarType, Shader, TypeKind, TypeLayout, VariableLayout,
+        owned_name,
+    },
+};
+
+pub(crate) struct ResourceBinding {
+    pub set: u32,
+    pub binding: u32,
+    pub binding_type: BindingType,
+    /// `None` for an unbounded array.
+    pub count: Option<u32>,
+    /// `None` for bindings of the global scope, which are visible to every stage.
+    pub stage: Option<crate::Stage>,
+    /// Name of the leaf type of the binding, e.g. `SamplerComparisonState`.
+    pub type_name: Option<String>,
+    /// Shape of a texture or buffer resource (`SLANG_RESOURCE_NONE` for other bindings).
+    pub resource_shape: crate::ResourceShape,
+    pub resource_access: crate::ResourceAccess,
+    /// Scalar type and component count of the texel type of a texture (`ScalarType::None` and 0 for other bindings).
+    pub texel: (ScalarType, u32),
+    /// Size in bytes of the uniform data held by a uniform buffer or an inline uniform block (0 for other bindings).
+    pub uniform_size: usize,
+    pub image_format: crate::ImageFormat,
+}
+
+pub(crate) struct PushConstantBlock {
+    pub stage: Option<crate::Stage>,
+    pub size: usize,
+}
+
+pub(crate) struct ProgramBindings {
+    /// Ordered by set, then binding.
+    pub bindings: Vec<ResourceBinding>,
+    pub push_constants: Vec<PushConstantBlock>,
+}
+impl ProgramBindings {
+    pub fn new(shader: Shader<'_>) -> Self {
+        let mut walker = Walker {
+            out: Self {
+                bindings: Vec::new(),
+                push_constants: Vec::new(),
+            },
+            stage: None,
+        };
+
+        if let Some(globals) = shader.global_params_var_layout() {
+            walker.add_root(globals);
+        }
+        for entry_point in shader.iter_entry_point() {
+            walker.stage = Some(entry_point.stage());
+            if let Some(scope) = entry_point.var_layout() {
+                walker.add_root(scope);
+            }
+        }
+
+        walker.out.sorted()
+    }
+
+    /// Orders the bindings by set, then binding.
+    pub fn sorted(mut self) -> Self {
+        self.bindings.sort_by_key(|b| (b.set, b.binding));
+        self
+    }
+}
+
+/// Position of a scope: the variables leading to it since the last change of set, and the space they start at.
+#[derive(Clone, Default)]
+struct Path<'a> {
+    variables: Vec<VariableLayout<'a>>,
+    space: usize,
+}
+impl<'a> Path<'a> {
+    fn join(&self, v: VariableLayout<'a>) -> Self {
+        let mut variables = self.variables.clone();
+        variables.push(v);
+
+        Self {
+            variables,
+            space: self.space,
+        }
+    }
+
+    fn offset(&self, category: ParameterCategory) -> usize {
+        self.variables.iter().map(|v| v.offset(category)).sum()
+    }
+
+    fn space(&self, category: ParameterCategory) -> usize {
+        self.space
+            + self
+                .variables
+                .iter()
+                .map(|v| v.space(category))
+                .sum::<usize>()
+    }
+}
+
+struct Walker {
+    out: ProgramBindings,
+    stage: Option<crate::Stage>,
+}
+impl Walker {
+    /// Adds the global scope or the parameter list of an entry point.
+    fn add_root(&mut self, scope: VariableLayout<'_>) {
+        let path = Path::default().join(scope);
+        let type_layout = scope.type_layout();
+
+        match type_layout.kind() {
+            TypeKind::ConstantBuffer | TypeKind::ParameterBlock => {
+                self.add_container(&path, type_layout)
+            }
+            _ => self.add_scope(&path, type_layout),
+        }
+    }
+
+    /// Adds the automatically introduced uniform buffer of a `ConstantBuffer`/`ParameterBlock` layout (if its element
+    /// has ordinary data) and the contents of the element.
+    fn add_container<'a>(&mut self, path: &Path<'a>, container: TypeLayout<'a>) {
+        let Some(element) = container.element_var_layout() else {
+            return;
+        };
+
+        if element.type_layout().size(ParameterCategory::Uniform) > 0
+            && let Some(buffer) = container.container_var_layout()
+        {
+            let path = path.join(buffer);
+            let category = ParameterCategory::DescriptorTableSlot;
+            self.push(
+                (path.space(category) as _, path.offset(category) as _),
+                BindingType::ConstantBuffer,
+                Some(1),
+                element.type_layout(),
+                0,
+            );
+        }
+
+        self.add_scope(&path.join(element), element.type_layout());
+    }
+
+    /// Adds the binding ranges of the scope at `path` with layout `scope`.
+    fn add_scope<'a>(&mut self, path: &Path<'a>, scope: TypeLayout<'a>) {
+        for range in 0..scope.binding_range_count() {
+            let Some(leaf) = scope.binding_range_leaf_type_layout(range) else {
+                continue;
+            };
+            let count = u32::try_from(scope.binding_range_binding_count(range)).ok();
+            let sub_object = (0..scope.sub_object_range_count())
+                .find(|&i| scope.sub_object_range_binding_range_index(i) == range);
+
+            match scope.binding_range_type(range) {
+                BindingType::ParameterBlock => {
+                    let Some(sub_object) = sub_object else {
+                        continue;
+                    };
+                    // the block starts a set of its own, with bindings counted from zero
+                    let block = Path {
+                        variables: Vec::new(),
+                        space: path.space(ParameterCategory::DescriptorTableSlot)
+                            + scope.sub_object_range_space_offset(sub_object) as usize,
+                    };
+                    self.add_container(&block, leaf);
+                }
+                BindingType::PushConstant => {
+                    let size = leaf
+                        .element_type_layout()
+                        .map_or(0, |e| e.size(ParameterCategory::Uniform));
+                    if size > 0 {
+                        self.out.push_constants.push(PushConstantBlock {
+                            stage: self.stage,
+                            size,
+                        });
+                    }
+                }
+                BindingType::Unknown
+                | BindingType::VaryingInput
+                | BindingType::VaryingOutput
+                | BindingType::ExistentialValue => (),
+                binding_type => {
+                    let Some(location) = location(path, scope, range) else {
+                        continue;
+                    };
+                    let element = leaf.element_var_layout();
+                    let type_layout = match (binding_type, element) {
+                        (BindingType::ConstantBuffer | BindingType::InlineUniformData, Some(e)) => {
+                            e.type_layout()
+                        }
+                        _ => leaf,
+                    };
+                    let image_format = scope.binding_range_image_format(range);
+                    self.push(location, binding_type, count, type_layout, image_format);
+
+                    // the resources inside a `ConstantBuffer<T>` share the set of the buffer
+                    if binding_type == BindingType::ConstantBuffer
+                        && count == Some(1)
+                        && let (Some(sub_object), Some(element)) = (sub_object, element)
+                    {
+                        let mut inner = path.clone();
+                        if let Some(offset) = scope.sub_object_range_offset(sub_object) {
+                            inner = inner.join(offset);
+                        }
+                        self.add_scope(&inner.join(element), element.type_layout());
+                    }
+                }
+            }
+        }
+    }
+
+    fn push(
+        &mut self,
+        (set, binding): (u32, u32),
+        binding_type: BindingType,
+        count: Option<u32>,
+        type_layout: TypeLayout<'_>,
+        image_format: crate::ImageFormat,
+    ) {
+        let uniform_size = match binding_type {
+            BindingType::ConstantBuffer | BindingType::InlineUniformData => {
+                type_layout.size(ParameterCategory::Uniform)
+            }
+            _ => 0,
+        };
+        self.out.bindings.push(ResourceBinding {
+            set,
+            binding,
+            binding_type,
+            count,
+            stage: self.stage,
+            type_name: owned_name(type_layout.name()),
+            resource_shape: type_layout.resource_shape(),
+            resource_access: type_layout.resource_access(),
+            texel: texel(type_layout),
+            uniform_size,
+            image_format,
+        });
+    }
+}
+
+/// Set and binding of the first descriptor of binding range `range` of the scope at `path`, or `None` if the range
+/// takes no descriptors.
+fn location(path: &Path<'_>, scope: TypeLayout<'_>, range: SlangInt) -> Option<(u32, u32)> {
+    if scope.binding_range_descriptor_range_count(range) == 0 {
+        return None;
+    }
+    let set = scope.binding_range_descriptor_set_index(range);
+    let first = scope.binding_range_first_descriptor_range_index(range);
+    let category = scope.descriptor_set_descriptor_range_category(set, first);
+    let index = scope.descriptor_set_descriptor_range_index_offset(set, first);
+
+    Some((
+        (path.space(category) as SlangInt + scope.descriptor_set_space_offset(set)) as _,
+        (path.offset(category) as SlangInt + index) as _,
+    ))
+}
+
+/// Scalar type and component count of the texel type of a texture.
+fn texel(texture: TypeLayout<'_>) -> (ScalarType, u32) {
+    match texture.resource_result_type() {
+        Some(t) if t.kind() == TypeKind::Vector => (
+            t.element_type()
+                .map_or(ScalarType::None, |e| e.scalar_type()),
+            t.element_count(None) as _,
+        ),
+        Some(t) => (t.scalar_type(), 1),
+        None => (ScalarType::None, 0),
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::ffi;
+
+    /// A binding of a hand-built program.
+    pub(crate) fn binding(
+        set: u32,
+        binding: u32,
+        binding_type: BindingType,
+        count: Option<u32>,
+        stage: Option<crate::Stage>,
+    ) -> ResourceBinding {
+        ResourceBinding {
+            set,
+            binding,
+            binding_type,
+            count,
+            stage,
+            type_name: None,
+            resource_shape: ffi::SLANG_RESOURCE_NONE,
+            resource_access: ffi::SLANG_RESOURCE_ACCESS_NONE,
+            texel: (ScalarType::None, 0),
+            uniform_size: 0,
+            image_format: ffi::SLANG_IMAGE_FORMAT_UNKNOWN,
+        }
+    }
+
+    #[test]
+    fn sorted_by_set_then_binding() {
+        let bindings = ProgramBindings {
+            bindings: vec![
+                binding(1, 0, BindingType::Texture, Some(1), None),
+                binding(0, 2, BindingType::Sampler, Some(1), None),
+                binding(0, 0, BindingType::ConstantBuffer, Some(1), None),
+                binding(1, 1, BindingType::Sampler, Some(1), None),
+            ],
+            push_constants: Vec::new(),
+        }
+        .sorted();
+
+        assert_eq!(
+            bindings
+                .bindings
+                .iter()
+                .map(|b| (b.set, b.binding))
+                .collect::<Vec<_>>(),
+            [(0, 0), (0, 2), (1, 0), (1, 1)]
+        );
+    }
+}
@@ -0,0 +1,129 @@
+//! `ISlangBlob` implemented over Rust-owned bytes.
+
+use core::ffi::c_void;
+use std::borrow::Cow;
+
+use crate::{
+    BLOB_IID, IBlobPtr, IBlobVTable, IUnknownVTable, SlangInterfacePtr, SlangUUID, UNKNOWN_IID,
+    com::ComObject,
+};
+
+struct BlobData {
+    bytes: Cow<'static, [u8]>,
+    /// Reported size; shorter than `bytes` when a NUL terminator is kept past the end
+    size: usize,
+}
+
+type BlobObject = ComObject<BlobData>;
+
+const INTERFACES: &[SlangUUID] = &[UNKNOWN_IID, BLOB_IID];
+const VTABLE: IBlobVTable = IBlobVTable {
+    base: IUnknownVTable {
+        query_interface: BlobObject::query_interface,
+        add_ref: BlobObject::add_ref,
+        release: BlobObject::release,
+    },
+    get_buffer_pointer,
+    get_buffer_size,
+};
+
+unsafe extern "system" fn get_buffer_pointer(this: *mut c_void) -> *const c_void {
+    unsafe { BlobObject::inner(this) }.bytes.as_ptr().cast()
+}
+
+unsafe extern "system" fn get_buffer_size(this: *mut c_void) -> usize {
+    unsafe { BlobObject::inner(this) }.size
+}
+
+/// Creates a blob owning (or statically borrowing) `data`.
+pub(crate) fn new_blob(data: Cow<'static, [u8]>) -> IBlobPtr {
+    let size = data.len();
+
+    unsafe {
+        IBlobPtr::from_ptr(BlobObject::allocate(
+            &VTABLE,
+            INTERFACES,
+            BlobData { bytes: data, size },
+        ))
+    }
+}
+
+/// Creates a blob holding `s` followed by a NUL terminator that is not included in the reported size
+/// (the layout Slang uses for its own string blobs).
+pub(crate) fn new_string_blob(s: String) -> IBlobPtr {
+    let size = s.len();
+    let mut bytes = s.into_bytes();
+    bytes.push(0);
+
+    unsafe {
+        IBlobPtr::from_ptr(BlobObject::allocate(
+            &VTABLE,
+            INTERFACES,
+            BlobData {
+                bytes: Cow::Owned(bytes),
+                size,
+            },
+        ))
+    }
+}
+
+impl IBlobPtr {
+    /// Creates a blob owning `data`.
+    #[inline]
+    pub fn from_vec(data: Vec<u8>) -> Self {
+        new_blob(Cow::Owned(data))
+    }
+
+    /// Creates a blob referencing static `data` without copying it.
+    #[inline]
+    pub fn from_static(data: &'static [u8]) -> Self {
+        new_blob(Cow::Borrowed(data))
+    }
+
+    /// Creates a blob holding a copy of `data`.
+    #[inline]
+    pub fn from_bytes(data: &[u8]) -> Self {
+        Self::from_vec(data.to_vec())
+    }
+
+    /// Creates a blob holding text (e.g. source code).
+    ///
+    /// The buffer is NUL-terminated, but the terminator is not counted in the buffer size.
+    #[inline]
+    pub fn from_string(s: impl Into<String>) -> Self {
+        new_string_blob(s.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IBlob, SendBlob};
+
+    #[test]
+    fn send_blob_requires_the_sole_reference() {
+        let blob = IBlobPtr::from_static(b"code");
+        let clone = blob.clone();
+        let blob = SendBlob::new(blob).unwrap_err();
+
+        drop(clone);
+        let blob = SendBlob::new(blob).unwrap();
+        let blob = std::thread::spawn(move || blob.into_inner().as_bytes().to_vec())
+            .join()
+            .unwrap();
+        assert_eq!(blob, b"code");
+    }
+
+    #[test]
+    fn send_blob_copies_shared_blobs() {
+        let blob = IBlobPtr::from_static(b"code");
+        let clone = blob.clone();
+
+        let copy = SendBlob::copy_from(&blob);
+        drop((blob, clone));
+        let copy = std::thread::spawn(move || copy.into_inner().as_bytes().to_vec())
+            .join()
+            .unwrap();
+        assert_eq!(copy, b"code");
+    }
+}
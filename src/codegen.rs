@@ -0,0 +1,757 @@
+//! Generation of Rust struct definitions matching the uniform layout of Slang types.
+//!
+//! [`RustStructGenerator`] walks a [`TypeLayout`] (typically the element of a `cbuffer`/`ConstantBuffer<T>`) and emits
+//! `#[repr(C)]` structs with explicit padding fields, followed by `const` assertions on their sizes and field offsets
+//! so that layout drift is a compile error. The output is plain source text, meant to be written to `OUT_DIR` from a
+//! `build.rs` (and `include!`d) or parsed by a proc macro.
+//!
+//! Type mapping:
+//!
+//! * scalars map to the Rust type of the same width; `bool` becomes `u32` and `half` becomes `u16` (raw bits)
+//! * vectors become `[T; N]`
+//! * matrices become `[[T; N]; M]` along the storage order given by the matrix layout mode, with the padding
+//!   Slang adds between rows/columns kept as extra elements; if the last row/column is not padded (D3D constant
+//!   buffers), they become a flat `[T; N]` covering the padding instead
+//! * arrays become `[T; N]`; elements padded to a larger array stride are wrapped in a generated `<Type>Stride<N>`
+//!   struct
+//! * pointers become `u64` (device addresses)
+//! * fields without uniform data (resources, samplers) are skipped
+
+use std::collections::HashMap;
+
+use crate::{
+    layout::{ShaderTypeLayout, matrix_stride},
+    reflection::{ParameterCategory, ScalarType, TypeKind, TypeLayout, VariableLayout, owned_name},
+};
+
+/// Failure of [`RustStructGenerator`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CodegenError {
+    /// The type given to [`RustStructGenerator::add_type_layout`] is not a struct.
+    NotAStruct(TypeKind),
+    /// A field (at `path`, e.g. `Globals.lights[].color`) has a type that cannot be represented.
+    Unsupported { path: String, reason: &'static str },
+    /// The size Slang computed for a field does not match the generated Rust type.
+    SizeMismatch {
+        path: String,
+        slang: usize,
+        rust: usize,
+    },
+    /// A field starts before the end of the previous one.
+    Overlap { path: String, offset: usize },
+}
+impl core::fmt::Display for CodegenError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::NotAStruct(kind) => write!(f, "expected a struct type, got {kind:?}"),
+            Self::Unsupported { path, reason } => write!(f, "{path}: unsupported type ({reason})"),
+            Self::SizeMismatch { path, slang, rust } => write!(
+                f,
+                "{path}: uniform size is {slang} bytes, but the generated Rust type has {rust} bytes"
+            ),
+            Self::Overlap { path, offset } => {
+                write!(
+                    f,
+                    "{path}: field at offset {offset} overlaps the previous field"
+                )
+            }
+        }
+    }
+}
+impl std::error::Error for CodegenError {}
+
+/// Rust representation of a field type.
+struct FieldType {
+    /// Rust type.
+    ty: String,
+    /// Name fragment for generated wrapper structs (e.g. `F32x3`).
+    label: String,
+    /// Uniform size in bytes.
+    size: usize,
+}
+
+/// Generates `#[repr(C)]` Rust structs for the uniform layout of Slang struct types.
+///
+/// ```ignore
+/// let layout = program.get_layout(0, None).unwrap();
+/// let mut g = slang::codegen::RustStructGenerator::new().derive("bytemuck::Pod").derive("bytemuck::Zeroable");
+/// for p in layout.iter_parameter() {
+///     g.add_parameter(p)?;
+/// }
+/// std::fs::write(out_dir.join("shader_types.rs"), g.finish())?;
+/// ```
+pub struct RustStructGenerator {
+    derives: Vec<String>,
+    assertions: bool,
+    items: Vec<String>,
+    /// Emitted structs by Slang name: (definition body, Rust name).
+    structs: HashMap<String, Vec<(String, String)>>,
+}
+impl Default for RustStructGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl RustStructGenerator {
+    /// A generator deriving `Clone`, `Copy` and `Debug` and emitting layout assertions.
+    pub fn new() -> Self {
+        Self {
+            derives: vec!["Clone".into(), "Copy".into(), "Debug".into()],
+            assertions: true,
+            items: Vec::new(),
+            structs: HashMap::new(),
+        }
+    }
+
+    /// Adds a derive to every generated struct (e.g. `bytemuck::Pod`).
+    pub fn derive(mut self, path: impl Into<String>) -> Self {
+        self.derives.push(path.into());
+        self
+    }
+
+    /// Whether to emit `const` assertions on struct sizes and field offsets (default: `true`).
+    pub const fn assertions(mut self, enabled: bool) -> Self {
+        self.assertions = enabled;
+        self
+    }
+
+    /// Generates the struct for `layout` (and the structs it contains), returning its Rust name.
+    ///
+    /// `fallback_name` is used if the type has no name.
+    pub fn add_type_layout(
+        &mut self,
+        layout: TypeLayout<'_>,
+        fallback_name: &str,
+    ) -> Result<String, CodegenError> {
+        if layout.kind() != TypeKind::Struct {
+            return Err(CodegenError::NotAStruct(layout.kind()));
+        }
+
+        let name = owned_name(layout.name()).unwrap_or_else(|| fallback_name.to_owned());
+        let name = self.struct_type(layout, &name, &name)?.ty;
+
+        Ok(name)
+    }
+
+    /// Generates the struct for the uniform data of a shader parameter (`cbuffer`, `ConstantBuffer<T>`,
+    /// `ParameterBlock<T>` or a plain struct parameter), returning its Rust name.
+    ///
+    /// Returns `None` for parameters without uniform data of struct type (e.g. textures).
+    pub fn add_parameter(
+        &mut self,
+        parameter: VariableLayout<'_>,
+    ) -> Result<Option<String>, CodegenError> {
+        let mut layout = parameter.type_layout();
+        if matches!(
+            layout.kind(),
+            TypeKind::ConstantBuffer
+                | TypeKind::ParameterBlock
+                | TypeKind::TextureBuffer
+                | TypeKind::ShaderStorageBuffer
+        ) && let Some(element) = layout.element_type_layout()
+        {
+            layout = element;
+        }
+        if layout.kind() != TypeKind::Struct || layout.size(ParameterCategory::Uniform) == 0 {
+            return Ok(None);
+        }
+
+        let fallback =
+            owned_name(parameter.name()).map_or_else(|| "Uniforms".to_owned(), |n| pascal_case(&n));
+
+        self.add_type_layout(layout, &fallback).map(Some)
+    }
+
+    /// Returns the generated source.
+    pub fn finish(self) -> String {
+        let mut out = String::from("// Generated by slang::codegen. Do not edit.\n");
+        for item in &self.items {
+            out.push('\n');
+            out.push_str(item);
+        }
+
+        out
+    }
+
+    fn field_type<L: ShaderTypeLayout>(
+        &mut self,
+        layout: L,
+        path: &str,
+    ) -> Result<Option<FieldType>, CodegenError> {
+        let size = layout.size();
+
+        let ft = match layout.kind() {
+            TypeKind::Scalar => {
+                let (rust, label, bytes) = scalar(layout.scalar_type(), path)?;
+                FieldType {
+                    ty: rust.into(),
+                    label: label.into(),
+                    size: bytes,
+                }
+            }
+            TypeKind::Vector => {
+                let (rust, label, bytes) = scalar(layout.scalar_type(), path)?;
+                let n = layout.element_count();
+                FieldType {
+                    ty: format!("[{rust}; {n}]"),
+                    label: format!("{label}x{n}"),
+                    size: bytes * n,
+                }
+            }
+            TypeKind::Matrix => matrix(
+                layout.scalar_type(),
+                layout.row_count(),
+                layout.column_count(),
+                layout.row_major(),
+                size,
+                path,
+            )?,
+            TypeKind::Array => {
+                let count = layout.element_count();
+                if count == 0 || count == usize::MAX {
+                    return Err(CodegenError::Unsupported {
+                        path: path.to_owned(),
+                        reason: "unsized array",
+                    });
+                }
+                let Some(element) = layout.element() else {
+                    return Err(CodegenError::Unsupported {
+                        path: path.to_owned(),
+                        reason: "array without element layout",
+                    });
+                };
+                let Some(element_type) = self.field_type(element, &format!("{path}[]"))? else {
+                    return Ok(None);
+                };
+
+                let stride = layout.element_stride();
+                let (ty, label) = if stride == element_type.size {
+                    (element_type.ty, element_type.label)
+                } else if stride > element_type.size {
+                    let name = self.stride_wrapper(&element_type, stride);
+                    (name.clone(), name)
+                } else {
+                    return Err(CodegenError::Unsupported {
+                        path: path.to_owned(),
+                        reason: "array stride is smaller than its element",
+                    });
+                };
+                if size != stride * count {
+                    return Err(CodegenError::Unsupported {
+                        path: path.to_owned(),
+                        reason: "last array element is not padded to the array stride (D3D constant buffer packing)",
+                    });
+                }
+
+                FieldType {
+                    ty: format!("[{ty}; {count}]"),
+                    label: format!("{label}x{count}"),
+                    size,
+                }
+            }
+            TypeKind::Struct => {
+                if size == 0 {
+                    return Ok(None);
+                }
+
+                let name = layout.name().unwrap_or_else(|| pascal_case(path));
+                self.struct_type(layout, &name, path)?
+            }
+            TypeKind::Pointer if size == 8 => FieldType {
+                ty: "u64".into(),
+                label: "Ptr".into(),
+                size,
+            },
+            _ if size == 0 => return Ok(None),
+            _ => {
+                return Err(CodegenError::Unsupported {
+                    path: path.to_owned(),
+                    reason: "type kind has no Rust representation",
+                });
+            }
+        };
+
+        if ft.size != size {
+            return Err(CodegenError::SizeMismatch {
+                path: path.to_owned(),
+                slang: size,
+                rust: ft.size,
+            });
+        }
+
+        Ok(Some(ft))
+    }
+
+    fn struct_type<L: ShaderTypeLayout>(
+        &mut self,
+        layout: L,
+        name: &str,
+        path: &str,
+    ) -> Result<FieldType, CodegenError> {
+        let size = layout.size();
+
+        let mut fields = Vec::new();
+        for (n, (field_name, offset, field)) in layout.fields().enumerate() {
+            let field_name = if field_name.is_empty() {
+                format!("field{n}")
+            } else {
+                field_name
+            };
+            let field_path = format!("{path}.{field_name}");
+            if let Some(ft) = self.field_type(field, &field_path)? {
+                fields.push((offset, field_ident(&field_name), ft, field_path));
+            }
+        }
+        fields.sort_by_key(|f| f.0);
+
+        let mut body = String::new();
+        let mut offsets = Vec::new();
+        let mut cursor = 0;
+        let mut pad = 0;
+        for (offset, ident, ft, field_path) in fields {
+            if offset < cursor {
+                return Err(CodegenError::Overlap {
+                    path: field_path,
+                    offset,
+                });
+            }
+            if offset > cursor {
+                body.push_str(&format!("    pub _pad{pad}: [u8; {}],\n", offset - cursor));
+                pad += 1;
+            }
+            body.push_str(&format!("    pub {ident}: {},\n", ft.ty));
+            offsets.push((ident, offset));
+            cursor = offset + ft.size;
+        }
+        if cursor > size {
+            return Err(CodegenError::SizeMismatch {
+                path: path.to_owned(),
+                slang: size,
+                rust: cursor,
+            });
+        }
+        if size > cursor {
+            body.push_str(&format!("    pub _pad{pad}: [u8; {}],\n", size - cursor));
+        }
+
+        let rust_name = self.emit_struct(name, &body, size, &offsets);
+
+        Ok(FieldType {
+            label: rust_name.clone(),
+            ty: rust_name,
+            size,
+        })
+    }
+
+    /// A struct wrapping an array element to pad it to the array stride.
+    fn stride_wrapper(&mut self, element: &FieldType, stride: usize) -> String {
+        let name = format!("{}Stride{stride}", element.label);
+        let body = format!(
+            "    pub value: {},\n    pub _pad0: [u8; {}],\n",
+            element.ty,
+            stride - element.size
+        );
+
+        self.emit_struct(&name, &body, stride, &[("value".into(), 0)])
+    }
+
+    /// Emits a struct (unless an identical one exists), returning its Rust name.
+    fn emit_struct(
+        &mut self,
+        name: &str,
+        body: &str,
+        size: usize,
+        offsets: &[(String, usize)],
+    ) -> String {
+        let name = sanitize(name);
+        let variants = self.structs.entry(name.clone()).or_default();
+        if let Some((_, rust_name)) = variants.iter().find(|(b, _)| b == body) {
+            return rust_name.clone();
+        }
+        // same name, different layout (e.g. another layout rule or specialization)
+        let rust_name = if variants.is_empty() {
+            name
+        } else {
+            format!("{name}{}", variants.len())
+        };
+        variants.push((body.to_owned(), rust_name.clone()));
+
+        let mut item = String::from("#[repr(C)]\n");
+        if !self.derives.is_empty() {
+            item.push_str(&format!("#[derive({})]\n", self.derives.join(", ")));
+        }
+        item.push_str(&format!("pub struct {rust_name} {{\n{body}}}\n"));
+        if self.assertions {
+            item.push_str(&format!(
+                "const _: () = assert!(core::mem::size_of::<{rust_name}>() == {size});\n"
+            ));
+            for (field, offset) in offsets {
+                item.push_str(&format!(
+                    "const _: () = assert!(core::mem::offset_of!({rust_name}, {field}) == {offset});\n"
+                ));
+            }
+        }
+        self.items.push(item);
+
+        rust_name
+    }
+}
+
+/// Rust type, name fragment and size of a scalar type.
+fn scalar(
+    scalar_type: ScalarType,
+    path: &str,
+) -> Result<(&'static str, &'static str, usize), CodegenError> {
+    Ok(match scalar_type {
+        ScalarType::Bool => ("u32", "Bool", 4),
+        ScalarType::Int8 => ("i8", "I8", 1),
+        ScalarType::UInt8 => ("u8", "U8", 1),
+        ScalarType::Int16 => ("i16", "I16", 2),
+        ScalarType::UInt16 => ("u16", "U16", 2),
+        ScalarType::Int32 => ("i32", "I32", 4),
+        ScalarType::UInt32 => ("u32", "U32", 4),
+        ScalarType::Int64 => ("i64", "I64", 8),
+        ScalarType::UInt64 => ("u64", "U64", 8),
+        ScalarType::Float16 => ("u16", "F16", 2),
+        ScalarType::Float32 => ("f32", "F32", 4),
+        ScalarType::Float64 => ("f64", "F64", 8),
+        ScalarType::None | ScalarType::Void => {
+            return Err(CodegenError::Unsupported {
+                path: path.to_owned(),
+                reason: "scalar without a type",
+            });
+        }
+    })
+}
+
+/// Rust representation of a matrix of `size` bytes.
+///
+/// Rows/columns become arrays padded to the stride between them, unless the last one is not padded (D3D constant
+/// buffer packing): the storage is kept flat then, as nested arrays would put the vectors at the wrong offsets.
+fn matrix(
+    scalar_type: ScalarType,
+    rows: usize,
+    columns: usize,
+    row_major: bool,
+    size: usize,
+    path: &str,
+) -> Result<FieldType, CodegenError> {
+    let (rust, label, bytes) = scalar(scalar_type, path)?;
+    let (outer, inner) = if row_major {
+        (rows, columns)
+    } else {
+        (columns, rows)
+    };
+    let label = format!("{label}x{rows}x{columns}");
+
+    match matrix_stride(size, outer, inner, bytes) {
+        Some(stride) if stride * outer == size => Ok(FieldType {
+            ty: format!("[[{rust}; {}]; {outer}]", stride / bytes),
+            label,
+            size,
+        }),
+        Some(_) => Ok(FieldType {
+            ty: format!("[{rust}; {}]", size / bytes),
+            label,
+            size,
+        }),
+        None => Err(CodegenError::Unsupported {
+            path: path.to_owned(),
+            reason: "matrix size matches no known layout of its rows/columns",
+        }),
+    }
+}
+
+/// Replaces characters that cannot appear in a Rust identifier (e.g. in `Foo<int>`).
+fn sanitize(name: &str) -> String {
+    let mut s: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if s.starts_with(|c: char| c.is_ascii_digit()) || s.is_empty() {
+        s.insert(0, '_');
+    }
+
+    s
+}
+
+fn pascal_case(name: &str) -> String {
+    sanitize(name)
+        .split('_')
+        .filter(|p| !p.is_empty())
+        .map(|p| {
+            let mut c = p.chars();
+            c.next()
+                .map(|f| f.to_ascii_uppercase().to_string() + c.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+fn field_ident(name: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "as", "break", "const", "continue", "else", "enum", "extern", "false", "fn", "for", "if",
+        "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return",
+        "static", "struct", "trait", "true", "type", "unsafe", "use", "where", "while", "async",
+        "await", "dyn", "abstract", "become", "box", "do", "final", "macro", "override", "priv",
+        "typeof", "unsized", "virtual", "yield", "try", "gen",
+    ];
+
+    let name = sanitize(name);
+    match name.as_str() {
+        // cannot be raw identifiers
+        "crate" | "self" | "super" | "Self" => format!("{name}_"),
+        n if KEYWORDS.contains(&n) => format!("r#{name}"),
+        _ => name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::tests::{
+        Shader, shader, shader_array, shader_scalar, shader_struct, shader_vector,
+    };
+
+    /// Generates the struct for a hand-built layout, returning the emitted items.
+    fn generate(layout: &Shader) -> Result<String, CodegenError> {
+        let mut g = RustStructGenerator::new().derive("bytemuck::Pod");
+        g.struct_type(layout, "Params", "Params")?;
+        Ok(g.finish())
+    }
+
+    #[test]
+    fn identifiers() {
+        assert_eq!(sanitize("Foo<int, 4>"), "Foo_int__4_");
+        assert_eq!(sanitize("2d"), "_2d");
+        assert_eq!(sanitize(""), "_");
+
+        assert_eq!(pascal_case("light_params"), "LightParams");
+        assert_eq!(pascal_case("Globals.lights[]"), "GlobalsLights");
+        assert_eq!(pascal_case("gColor"), "GColor");
+
+        assert_eq!(field_ident("color"), "color");
+        assert_eq!(field_ident("type"), "r#type");
+        assert_eq!(field_ident("gen"), "r#gen");
+        assert_eq!(field_ident("self"), "self_");
+        assert_eq!(field_ident("crate"), "crate_");
+        assert_eq!(field_ident("Self"), "Self_");
+        assert_eq!(field_ident("a.b"), "a_b");
+    }
+
+    #[test]
+    fn emitted_struct() {
+        let mut g = RustStructGenerator::new().derive("bytemuck::Pod");
+        let name = g.emit_struct(
+            "Params",
+            "    pub color: [f32; 3],\n    pub scale: f32,\n",
+            16,
+            &[("color".into(), 0), ("scale".into(), 12)],
+        );
+        assert_eq!(name, "Params");
+
+        assert_eq!(
+            g.finish(),
+            "// Generated by slang::codegen. Do not edit.\n\
+             \n\
+             #[repr(C)]\n\
+             #[derive(Clone, Copy, Debug, bytemuck::Pod)]\n\
+             pub struct Params {\n    pub color: [f32; 3],\n    pub scale: f32,\n}\n\
+             const _: () = assert!(core::mem::size_of::<Params>() == 16);\n\
+             const _: () = assert!(core::mem::offset_of!(Params, color) == 0);\n\
+             const _: () = assert!(core::mem::offset_of!(Params, scale) == 12);\n"
+        );
+    }
+
+    #[test]
+    fn emitted_struct_without_assertions() {
+        let mut g = RustStructGenerator::new().assertions(false);
+        g.emit_struct("P", "    pub x: f32,\n", 4, &[("x".into(), 0)]);
+
+        assert!(!g.finish().contains("assert!"));
+    }
+
+    #[test]
+    fn struct_deduplication() {
+        let mut g = RustStructGenerator::new();
+        let a = "    pub x: f32,\n";
+        let b = "    pub x: f32,\n    pub _pad0: [u8; 12],\n";
+
+        assert_eq!(g.emit_struct("Light<float>", a, 4, &[]), "Light_float_");
+        // identical definitions are emitted once
+        assert_eq!(g.emit_struct("Light<float>", a, 4, &[]), "Light_float_");
+        // same name, different layout
+        assert_eq!(g.emit_struct("Light<float>", b, 16, &[]), "Light_float_1");
+        assert_eq!(g.emit_struct("Light<float>", b, 16, &[]), "Light_float_1");
+        assert_eq!(g.items.len(), 2);
+    }
+
+    #[test]
+    fn stride_wrappers() {
+        let mut g = RustStructGenerator::new().assertions(false);
+        let element = FieldType {
+            ty: "[f32; 3]".into(),
+            label: "F32x3".into(),
+            size: 12,
+        };
+
+        assert_eq!(g.stride_wrapper(&element, 16), "F32x3Stride16");
+        assert_eq!(g.stride_wrapper(&element, 16), "F32x3Stride16");
+        assert_eq!(
+            g.finish(),
+            "// Generated by slang::codegen. Do not edit.\n\
+             \n\
+             #[repr(C)]\n\
+             #[derive(Clone, Copy, Debug)]\n\
+             pub struct F32x3Stride16 {\n    pub value: [f32; 3],\n    pub _pad0: [u8; 4],\n}\n"
+        );
+    }
+
+    #[test]
+    fn matrices() {
+        let ty = |rows, columns, row_major, size| {
+            matrix(ScalarType::Float32, rows, columns, row_major, size, "m").map(|t| t.ty)
+        };
+
+        // std140 float3x3: padded columns
+        assert_eq!(ty(3, 3, false, 48), Ok("[[f32; 4]; 3]".into()));
+        // scalar layout float3x2, row-major: 3 rows of 2
+        assert_eq!(ty(3, 2, true, 24), Ok("[[f32; 2]; 3]".into()));
+        // D3D constant buffer float2x2: the second column starts at 16, so the storage stays flat
+        assert_eq!(ty(2, 2, false, 24), Ok("[f32; 6]".into()));
+        assert_eq!(ty(3, 3, false, 44), Ok("[f32; 11]".into()));
+        assert!(matches!(
+            ty(2, 2, false, 20),
+            Err(CodegenError::Unsupported { .. })
+        ));
+    }
+
+    #[test]
+    fn padded_fields() {
+        let layout = shader_struct(
+            32,
+            vec![
+                ("scale", 0, shader_scalar(ScalarType::Float32)),
+                // no uniform data
+                ("texture", 0, shader(TypeKind::Resource, 0)),
+                ("color", 16, shader_vector(ScalarType::Float32, 3)),
+            ],
+        );
+
+        assert_eq!(
+            generate(&layout).unwrap(),
+            "// Generated by slang::codegen. Do not edit.\n\
+             \n\
+             #[repr(C)]\n\
+             #[derive(Clone, Copy, Debug, bytemuck::Pod)]\n\
+             pub struct Params {\n    \
+             pub scale: f32,\n    \
+             pub _pad0: [u8; 12],\n    \
+             pub color: [f32; 3],\n    \
+             pub _pad1: [u8; 4],\n\
+             }\n\
+             const _: () = assert!(core::mem::size_of::<Params>() == 32);\n\
+             const _: () = assert!(core::mem::offset_of!(Params, scale) == 0);\n\
+             const _: () = assert!(core::mem::offset_of!(Params, color) == 16);\n"
+        );
+
+        let overlapping = shader_struct(
+            8,
+            vec![
+                ("a", 0, shader_vector(ScalarType::Float32, 2)),
+                ("b", 4, shader_scalar(ScalarType::Float32)),
+            ],
+        );
+        assert_eq!(
+            generate(&overlapping),
+            Err(CodegenError::Overlap {
+                path: "Params.b".to_owned(),
+                offset: 4,
+            })
+        );
+    }
+
+    #[test]
+    fn padded_array_elements() {
+        // `float weights[3]` in a constant buffer: every element padded to 16 bytes
+        let layout = shader_struct(
+            48,
+            vec![(
+                "weights",
+                0,
+                shader_array(shader_scalar(ScalarType::Float32), 3, 16),
+            )],
+        );
+
+        let mut g = RustStructGenerator::new().assertions(false);
+        g.struct_type(&layout, "Params", "Params").unwrap();
+        assert_eq!(
+            g.finish(),
+            "// Generated by slang::codegen. Do not edit.\n\
+             \n\
+             #[repr(C)]\n\
+             #[derive(Clone, Copy, Debug)]\n\
+             pub struct F32Stride16 {\n    pub value: f32,\n    pub _pad0: [u8; 12],\n}\n\
+             \n\
+             #[repr(C)]\n\
+             #[derive(Clone, Copy, Debug)]\n\
+             pub struct Params {\n    pub weights: [F32Stride16; 3],\n}\n"
+        );
+
+        // the last element of a D3D constant buffer array is not padded
+        let mut unpadded = shader_array(shader_scalar(ScalarType::Float32), 3, 16);
+        unpadded.size = 36;
+        assert!(matches!(
+            generate(&shader_struct(36, vec![("weights", 0, unpadded)])),
+            Err(CodegenError::Unsupported { path, .. }) if path == "Params.weights"
+        ));
+
+        let overlapping = shader_array(shader_vector(ScalarType::Float32, 2), 2, 4);
+        assert!(matches!(
+            generate(&shader_struct(8, vec![("weights", 0, overlapping)])),
+            Err(CodegenError::Unsupported { path, .. }) if path == "Params.weights"
+        ));
+    }
+
+    #[test]
+    fn matrix_fields() {
+        let float2x3 = |row_major, size| Shader {
+            scalar_type: ScalarType::Float32,
+            rows: 2,
+            columns: 3,
+            row_major,
+            ..shader(TypeKind::Matrix, size)
+        };
+        let field = |layout: &Shader| {
+            RustStructGenerator::new()
+                .field_type(layout, "Params.m")
+                .map(|ft| ft.map(|ft| (ft.ty, ft.size)))
+        };
+
+        // row-major: 2 rows padded to 16 bytes
+        assert_eq!(
+            field(&float2x3(true, 32)),
+            Ok(Some(("[[f32; 4]; 2]".to_owned(), 32)))
+        );
+        // column-major: 3 columns of 2 floats
+        assert_eq!(
+            field(&float2x3(false, 24)),
+            Ok(Some(("[[f32; 2]; 3]".to_owned(), 24)))
+        );
+        // row-major in a D3D constant buffer: the last row is not padded
+        assert_eq!(
+            field(&float2x3(true, 28)),
+            Ok(Some(("[f32; 7]".to_owned(), 28)))
+        );
+        assert!(matches!(
+            field(&float2x3(true, 20)),
+            Err(CodegenError::Unsupported { path, .. }) if path == "Params.m"
+        ));
+    }
+}
@@ -0,0 +1,109 @@
+//! Reference-counted COM objects implemented in Rust.
+
+use core::{
+    ffi::c_void,
+    ptr::NonNull,
+    sync::atomic::{AtomicU32, Ordering, fence},
+};
+
+use crate::{
+    SlangUUID,
+    ffi::{SLANG_E_NO_INTERFACE, SLANG_OK, SlangResult},
+};
+
+/// Heap layout of an object handed out to Slang: a pointer to its vtable followed by the refcount, the list of
+/// interfaces it answers to in `query_interface` and the Rust value itself.
+///
+/// The functions below match the `IUnknown`/`ICastable` slots of any vtable laid out for this object.
+#[repr(C)]
+pub(crate) struct ComObject<T> {
+    vtable: *const c_void,
+    ref_count: AtomicU32,
+    interfaces: &'static [SlangUUID],
+    inner: T,
+}
+impl<T> ComObject<T> {
+    /// Allocates a new object with a reference count of 1.
+    pub fn allocate<V>(
+        vtable: &'static V,
+        interfaces: &'static [SlangUUID],
+        inner: T,
+    ) -> NonNull<c_void> {
+        let p = Box::into_raw(Box::new(Self {
+            vtable: (vtable as *const V).cast(),
+            ref_count: AtomicU32::new(1),
+            interfaces,
+            inner,
+        }));
+
+        unsafe { NonNull::new_unchecked(p.cast()) }
+    }
+
+    /// # Safety
+    /// `this` must be a pointer returned from [`ComObject::allocate`] with the same `T`.
+    #[inline(always)]
+    pub unsafe fn inner<'a>(this: *mut c_void) -> &'a T {
+        unsafe { &(*this.cast::<Self>()).inner }
+    }
+
+    #[inline(always)]
+    fn implements(&self, guid: &SlangUUID) -> bool {
+        self.interfaces.contains(guid)
+    }
+
+    pub unsafe extern "system" fn query_interface(
+        this: *mut c_void,
+        guid: *const SlangUUID,
+        out: *mut *mut c_void,
+    ) -> SlangResult {
+        let s = unsafe { &*this.cast::<Self>() };
+        if unsafe { s.implements(&*guid) } {
+            s.ref_count.fetch_add(1, Ordering::Relaxed);
+            unsafe {
+                out.write(this);
+            }
+
+            SLANG_OK
+        } else {
+            unsafe {
+                out.write(core::ptr::null_mut());
+            }
+
+            SLANG_E_NO_INTERFACE
+        }
+    }
+
+    pub unsafe extern "system" fn add_ref(this: *mut c_void) -> u32 {
+        unsafe { &*this.cast::<Self>() }
+            .ref_count
+            .fetch_add(1, Ordering::Relaxed)
+            + 1
+    }
+
+    pub unsafe extern "system" fn release(this: *mut c_void) -> u32 {
+        let r = unsafe { &*this.cast::<Self>() }
+            .ref_count
+            .fetch_sub(1, Ordering::Release)
+            - 1;
+        if r == 0 {
+            fence(Ordering::Acquire);
+            let object = unsafe { Box::from_raw(this.cast::<Self>()) };
+            // the Rust value must not unwind into Slang
+            let _ = std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| drop(object)));
+        }
+
+        r
+    }
+
+    /// `ICastable::castAs`: same lookup as `query_interface`, without adding a reference
+    pub unsafe extern "system" fn cast_as(
+        this: *mut c_void,
+        guid: *const SlangUUID,
+    ) -> *mut c_void {
+        if unsafe { (*this.cast::<Self>()).implements(&*guid) } {
+            this
+        } else {
+            core::ptr::null_mut()
+        }
+    }
+}
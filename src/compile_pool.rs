@@ -0,0 +1,506 @@
+//! Parallel compilation over worker threads, each with its own session.
+
+use std::{
+    ffi::CString,
+    sync::{Arc, Mutex, PoisonError, mpsc},
+    thread::JoinHandle,
+};
+
+use crate::{
+    CompileError, Diagnosed, DiagnosedResult, IBlob, IComponentType, IComponentTypePtr,
+    IGlobalSession, IGlobalSessionPtr, IModule, IModulePtr, ISession, ISessionPtr, IUnknown,
+    SessionDescBuilder, SlangError, SpecializationArg, SpecializationArgKind,
+    SpecializationArgValue, ffi::SlangInt, is_sole_reference,
+};
+
+/// Where the module of a [`CompileJob`] comes from.
+#[derive(Debug, Clone)]
+pub enum ModuleSource {
+    /// A module found through the search paths / file system of the session (as `import`ed).
+    Name(CString),
+    /// A module given as source text.
+    Source {
+        name: CString,
+        path: CString,
+        source: CString,
+    },
+}
+
+/// A single compilation: a module, the entry points to include and the target to generate code for.
+#[derive(Debug, Clone)]
+pub struct CompileJob {
+    pub module: ModuleSource,
+    /// Entry points to compile (all entry points defined in the module if empty).
+    pub entry_points: Vec<CString>,
+    /// Arguments for the specialization parameters of the program, as expressions (e.g. type names).
+    pub specialization_args: Vec<CString>,
+    /// Index of the target in the session description.
+    pub target_index: SlangInt,
+}
+impl CompileJob {
+    /// Compiles the module `name`, loaded through the session search paths.
+    #[inline]
+    pub fn module(name: impl Into<CString>) -> Self {
+        Self::new(ModuleSource::Name(name.into()))
+    }
+
+    /// Compiles the module `name` from `source` (`path` is used in diagnostics).
+    #[inline]
+    pub fn source(
+        name: impl Into<CString>,
+        path: impl Into<CString>,
+        source: impl Into<CString>,
+    ) -> Self {
+        Self::new(ModuleSource::Source {
+            name: name.into(),
+            path: path.into(),
+            source: source.into(),
+        })
+    }
+
+    #[inline]
+    pub const fn new(module: ModuleSource) -> Self {
+        Self {
+            module,
+            entry_points: Vec::new(),
+            specialization_args: Vec::new(),
+            target_index: 0,
+        }
+    }
+
+    #[inline]
+    pub fn entry_point(mut self, name: impl Into<CString>) -> Self {
+        self.entry_points.push(name.into());
+        self
+    }
+
+    #[inline]
+    pub fn specialization_arg(mut self, expr: impl Into<CString>) -> Self {
+        self.specialization_args.push(expr.into());
+        self
+    }
+
+    #[inline]
+    pub const fn target_index(mut self, index: SlangInt) -> Self {
+        self.target_index = index;
+        self
+    }
+}
+
+/// Receiver of the result (the target code) of a job submitted to a [`CompilePool`].
+#[derive(Debug)]
+pub struct CompileHandle(mpsc::Receiver<DiagnosedResult<Vec<u8>>>);
+impl CompileHandle {
+    /// Blocks until the job has finished.
+    pub fn wait(self) -> DiagnosedResult<Vec<u8>> {
+        self.0
+            .recv()
+            .unwrap_or(Err(CompileError::from(SlangError::INTERNAL_FAIL)))
+    }
+
+    /// Returns the result if the job has finished.
+    pub fn try_wait(&self) -> Option<DiagnosedResult<Vec<u8>>> {
+        match self.0.try_recv() {
+            Ok(r) => Some(r),
+            Err(mpsc::TryRecvError::Empty) => None,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                Some(Err(CompileError::from(SlangError::INTERNAL_FAIL)))
+            }
+        }
+    }
+
+    #[inline(always)]
+    pub fn into_receiver(self) -> mpsc::Receiver<DiagnosedResult<Vec<u8>>> {
+        self.0
+    }
+}
+
+/// Creates the global session of a worker.
+type GlobalSessionFactory = dyn Fn() -> crate::Result<IGlobalSessionPtr> + Send + Sync;
+
+/// The description the workers create their sessions from.
+struct SharedDesc(SessionDescBuilder);
+// The file system is the only reference to itself (checked by `CompilePool::new`) and the description is only
+// accessed under the mutex of the pool. The other pointers held by the description (option strings) are owned by it.
+unsafe impl Send for SharedDesc {}
+
+struct Task {
+    job: CompileJob,
+    reply: Box<dyn FnOnce(DiagnosedResult<Vec<u8>>) + Send>,
+}
+
+/// A fixed set of worker threads compiling [`CompileJob`]s in parallel.
+///
+/// Each worker creates its own global session and, from it, a session with the same description on its first job:
+/// Slang does not synchronize the state and reference counts a global session shares with its sessions, so workers
+/// cannot share one. Modules loaded by a worker stay loaded in its session, so jobs on the same module get cheaper over
+/// time.
+///
+/// The file system set on the description (if any) is used by all sessions concurrently, so it must be thread-safe
+/// (as the ones created with [`IFileSystemPtr::from_provider`](crate::IFileSystemPtr::from_provider) are).
+///
+/// The pool takes over the file system: it refuses one with other references, as Slang's reference counts are not
+/// synchronized with threads outside the pool.
+pub struct CompilePool {
+    queue: Option<mpsc::Sender<Task>>,
+    workers: Vec<JoinHandle<()>>,
+}
+impl CompilePool {
+    /// Starts `threads` workers (at least one), each creating its global session with `create_global_session`
+    /// (e.g. `|| slang::create_global_session(&GlobalSessionDesc::default())`).
+    ///
+    /// Fails, giving the description back, if other references to its file system exist.
+    pub fn new(
+        create_global_session: impl Fn() -> crate::Result<IGlobalSessionPtr> + Send + Sync + 'static,
+        desc: SessionDescBuilder,
+        threads: usize,
+    ) -> Result<Self, Box<SessionDescBuilder>> {
+        if desc
+            .file_system
+            .as_ref()
+            .is_some_and(|fs| !is_sole_reference(fs))
+        {
+            return Err(Box::new(desc));
+        }
+
+        let create_global_session: Arc<GlobalSessionFactory> = Arc::new(create_global_session);
+        let desc = Arc::new(Mutex::new(SharedDesc(desc)));
+        let (queue, tasks) = mpsc::channel::<Task>();
+        let tasks = Arc::new(Mutex::new(tasks));
+
+        let workers = (0..threads.max(1))
+            .map(|n| {
+                let create_global_session = create_global_session.clone();
+                let desc = desc.clone();
+                let tasks = tasks.clone();
+
+                std::thread::Builder::new()
+                    .name(format!("slang-compile-{n}"))
+                    .spawn(move || worker(&*create_global_session, &desc, &tasks))
+                    .expect("failed to spawn compile worker")
+            })
+            .collect();
+
+        Ok(Self {
+            queue: Some(queue),
+            workers,
+        })
+    }
+
+    /// Starts one worker per available CPU.
+    ///
+    /// Fails like [`new`](Self::new).
+    pub fn with_available_parallelism(
+        create_global_session: impl Fn() -> crate::Result<IGlobalSessionPtr> + Send + Sync + 'static,
+        desc: SessionDescBuilder,
+    ) -> Result<Self, Box<SessionDescBuilder>> {
+        let threads = std::thread::available_parallelism().map_or(1, usize::from);
+
+        Self::new(create_global_session, desc, threads)
+    }
+
+    #[inline]
+    pub fn thread_count(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Queues `job` and returns a handle to its result.
+    pub fn submit(&self, job: CompileJob) -> CompileHandle {
+        let (tx, rx) = mpsc::channel();
+        self.enqueue(job, move |r| {
+            let _ = tx.send(r);
+        });
+
+        CompileHandle(rx)
+    }
+
+    /// Queues `job`, sending its result along with `tag` to `results`
+    /// (e.g. to collect the results of many permutations on one channel).
+    pub fn submit_tagged<K: Send + 'static>(
+        &self,
+        job: CompileJob,
+        tag: K,
+        results: mpsc::Sender<(K, DiagnosedResult<Vec<u8>>)>,
+    ) {
+        self.enqueue(job, move |r| {
+            let _ = results.send((tag, r));
+        });
+    }
+
+    fn enqueue(
+        &self,
+        job: CompileJob,
+        reply: impl FnOnce(DiagnosedResult<Vec<u8>>) + Send + 'static,
+    ) {
+        let queue = self.queue.as_ref().expect("pool is shut down");
+        let task = Task {
+            job,
+            reply: Box::new(reply),
+        };
+        if let Err(mpsc::SendError(task)) = queue.send(task) {
+            // all workers are gone (they only exit on shutdown)
+            (task.reply)(Err(CompileError::from(SlangError::INTERNAL_FAIL)));
+        }
+    }
+}
+impl Drop for CompilePool {
+    /// Finishes the queued jobs and joins the workers.
+    fn drop(&mut self) {
+        drop(self.queue.take());
+        for w in self.workers.drain(..) {
+            let _ = w.join();
+        }
+    }
+}
+
+fn worker(
+    create_global_session: &GlobalSessionFactory,
+    desc: &Mutex<SharedDesc>,
+    tasks: &Mutex<mpsc::Receiver<Task>>,
+) {
+    // declared before the global session so that it is released first
+    let mut global_session = None::<IGlobalSessionPtr>;
+    let mut session = None::<ISessionPtr>;
+
+    loop {
+        let task = {
+            let tasks = tasks.lock().unwrap_or_else(PoisonError::into_inner);
+            match tasks.recv() {
+                Ok(t) => t,
+                // the pool has been dropped
+                Err(_) => break,
+            }
+        };
+
+        let result = std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| {
+            let session = match session {
+                Some(ref s) => s,
+                None => {
+                    let global_session = match global_session {
+                        Some(ref g) => g,
+                        None => global_session.insert(create_global_session()?),
+                    };
+                    // the description (and its file system) is shared by the workers
+                    let desc = desc.lock().unwrap_or_else(PoisonError::into_inner);
+                    session.insert(global_session.create_session(&desc.0)?)
+                }
+            };
+
+            // only this worker uses the session and its global session
+            compile(session, &task.job)
+        }))
+        .unwrap_or(Err(CompileError::from(SlangError::INTERNAL_FAIL)));
+
+        (task.reply)(result);
+    }
+
+    // releasing the session touches the shared file system
+    let _desc = desc.lock().unwrap_or_else(PoisonError::into_inner);
+    drop(session);
+    drop(global_session);
+}
+
+/// Diagnostic output collected over the steps of a job.
+#[derive(Default)]
+struct Diagnostics(Option<String>);
+impl Diagnostics {
+    fn append(&mut self, d: Option<String>) {
+        match (&mut self.0, d) {
+            (_, None) => (),
+            (None, d) => self.0 = d,
+            (Some(s), Some(d)) => s.push_str(&d),
+        }
+    }
+
+    fn step<T>(&mut self, r: DiagnosedResult<T>) -> Result<T, CompileError> {
+        match r {
+            Ok(Diagnosed { value, diagnostics }) => {
+                self.append(diagnostics);
+
+                Ok(value)
+            }
+            Err(CompileError { error, diagnostics }) => {
+                self.append(diagnostics);
+
+                Err(CompileError {
+                    error,
+                    diagnostics: self.0.take(),
+                })
+            }
+        }
+    }
+
+    fn fail(&mut self, error: SlangError) -> CompileError {
+        CompileError {
+            error,
+            diagnostics: self.0.take(),
+        }
+    }
+}
+
+fn compile(session: &ISessionPtr, job: &CompileJob) -> DiagnosedResult<Vec<u8>> {
+    let mut diagnostics = Diagnostics::default();
+
+    let module: IModulePtr = diagnostics.step(match job.module {
+        ModuleSource::Name(ref name) => session.load_module_with_diagnostics(name),
+        ModuleSource::Source {
+            ref name,
+            ref path,
+            ref source,
+        } => session.load_module_from_source_string_with_diagnostics(name, path, source),
+    })?;
+
+    let mut components = Vec::<IComponentTypePtr>::with_capacity(1 + job.entry_points.len());
+    components.push(module.clone_cast().map_err(|e| diagnostics.fail(e))?);
+    if job.entry_points.is_empty() {
+        for ep in module.iter_defined_entry_point() {
+            let ep = ep.and_then(|ep| ep.clone_cast());
+            components.push(ep.map_err(|e| diagnostics.fail(e))?);
+        }
+    } else {
+        for name in &job.entry_points {
+            let ep = module
+                .find_entry_point_by_name(name)
+                .and_then(|ep| ep.clone_cast());
+            components.push(ep.map_err(|e| diagnostics.fail(e))?);
+        }
+    }
+
+    let mut program =
+        diagnostics.step(session.create_composite_component_type_with_diagnostics(&components))?;
+    if !job.specialization_args.is_empty() {
+        let args = job
+            .specialization_args
+            .iter()
+            .map(|a| SpecializationArg {
+                kind: SpecializationArgKind::Expr,
+                value: SpecializationArgValue { expr: a.as_ptr() },
+            })
+            .collect::<Vec<_>>();
+        program = diagnostics.step(program.specialize_with_diagnostics(&args))?;
+    }
+    let program = diagnostics.step(program.link_with_diagnostics())?;
+    let code = diagnostics.step(program.get_target_code_with_diagnostics(job.target_index))?;
+
+    // the blob cannot leave the worker: its reference count is not atomic
+    Ok(Diagnosed {
+        value: code.as_bytes().to_vec(),
+        diagnostics: diagnostics.0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ok(value: u32, diagnostics: Option<&str>) -> DiagnosedResult<u32> {
+        Ok(Diagnosed {
+            value,
+            diagnostics: diagnostics.map(ToOwned::to_owned),
+        })
+    }
+
+    #[test]
+    fn diagnostics_accumulate_over_steps() {
+        let mut diagnostics = Diagnostics::default();
+
+        assert_eq!(diagnostics.step(ok(1, None)).unwrap(), 1);
+        assert_eq!(diagnostics.0, None);
+        assert_eq!(
+            diagnostics
+                .step(ok(2, Some("a.slang(1): warning 1: x\n")))
+                .unwrap(),
+            2
+        );
+        assert_eq!(
+            diagnostics
+                .step(ok(3, Some("a.slang(2): warning 2: y\n")))
+                .unwrap(),
+            3
+        );
+        assert_eq!(
+            diagnostics.0.as_deref(),
+            Some("a.slang(1): warning 1: x\na.slang(2): warning 2: y\n")
+        );
+    }
+
+    #[test]
+    fn failed_step_carries_earlier_diagnostics() {
+        let mut diagnostics = Diagnostics::default();
+        diagnostics.step(ok(1, Some("warning 1: x\n"))).unwrap();
+
+        let e = diagnostics
+            .step::<u32>(Err(CompileError {
+                error: SlangError::FAIL,
+                diagnostics: Some("error 2: y\n".to_owned()),
+            }))
+            .unwrap_err();
+        assert_eq!(e.error, SlangError::FAIL);
+        assert_eq!(e.diagnostics.as_deref(), Some("warning 1: x\nerror 2: y\n"));
+        // taken by the error
+        assert_eq!(diagnostics.0, None);
+
+        diagnostics.step(ok(1, Some("warning 3: z\n"))).unwrap();
+        let e = diagnostics.fail(SlangError::INVALID_ARG);
+        assert_eq!(e.error, SlangError::INVALID_ARG);
+        assert_eq!(e.diagnostics.as_deref(), Some("warning 3: z\n"));
+    }
+
+    #[test]
+    fn job_builder() {
+        let job = CompileJob::source(c"m", c"m.slang", c"[shader(\"compute\")] void main() {}")
+            .entry_point(c"main")
+            .specialization_arg(c"Light")
+            .target_index(1);
+
+        assert!(matches!(
+            job.module,
+            ModuleSource::Source { ref name, ref path, .. } if name.as_c_str() == c"m" && path.as_c_str() == c"m.slang"
+        ));
+        assert_eq!(job.entry_points, [c"main".to_owned()]);
+        assert_eq!(job.specialization_args, [c"Light".to_owned()]);
+        assert_eq!(job.target_index, 1);
+
+        let job = CompileJob::module(c"lib");
+        assert!(matches!(job.module, ModuleSource::Name(ref name) if name.as_c_str() == c"lib"));
+        assert!(job.entry_points.is_empty());
+        assert_eq!(job.target_index, 0);
+    }
+
+    #[test]
+    fn workers_run_jobs_concurrently() {
+        // every worker creating its global session waits for the other one: both only get past it if two jobs run at
+        // the same time on separate workers
+        let arrived = Arc::new((Mutex::new(0usize), std::sync::Condvar::new()));
+        let pool = CompilePool::new(
+            {
+                let arrived = arrived.clone();
+                move || {
+                    let (count, cv) = &*arrived;
+                    let mut count = count.lock().unwrap();
+                    *count += 1;
+                    cv.notify_all();
+                    let (count, _) = cv
+                        .wait_timeout_while(count, std::time::Duration::from_secs(10), |c| *c < 2)
+                        .unwrap();
+
+                    Err(if *count >= 2 {
+                        SlangError::NOT_AVAILABLE
+                    } else {
+                        SlangError::TIME_OUT
+                    })
+                }
+            },
+            SessionDescBuilder::new(),
+            2,
+        )
+        .map_err(|_| ())
+        .unwrap();
+        assert_eq!(pool.thread_count(), 2);
+
+        let a = pool.submit(CompileJob::module(c"a"));
+        let b = pool.submit(CompileJob::module(c"b"));
+        assert_eq!(a.wait().unwrap_err().error, SlangError::NOT_AVAILABLE);
+        assert_eq!(b.wait().unwrap_err().error, SlangError::NOT_AVAILABLE);
+    }
+}
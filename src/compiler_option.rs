@@ -0,0 +1,493 @@
+use core::ffi::c_char;
+use std::{borrow::Cow, ffi::CString};
+
+use crate::{
+    CapabilityID, CompileTarget, CompilerOptionEntry, CompilerOptionName, CompilerOptionValue,
+    CompilerOptionValueKind, DebugInfoFormat, DebugInfoLevel, EmitSpirvMethod, FloatingPointMode,
+    LineDirectiveMode, OptimizationLevel, ProfileID, SourceLanguage, Stage,
+};
+
+/// Register class shifted by [`CompilerOption::VulkanBindShift`] (the `b`/`t`/`u`/`s` of `-fvk-*-shift`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(i32)]
+pub enum VulkanShiftKind {
+    /// `b` registers
+    ConstantBuffer = 0,
+    /// `t` registers
+    ShaderResource = 1,
+    /// `u` registers
+    UnorderedAccess = 2,
+    /// `s` registers
+    Sampler = 3,
+}
+
+/// A typed compiler option, lowered to [`CompilerOptionEntry`] through [`CompilerOptionEntries`].
+///
+/// Strings must not contain interior NUL characters.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CompilerOption {
+    MacroDefine {
+        name: String,
+        value: String,
+    },
+    /// Additional include path
+    Include(String),
+    Language(SourceLanguage),
+    MatrixLayoutColumn(bool),
+    MatrixLayoutRow(bool),
+    ZeroInitialize(bool),
+    IgnoreCapabilities(bool),
+    RestrictiveCapabilityCheck(bool),
+    ModuleName(String),
+    Profile(ProfileID),
+    Stage(Stage),
+    Target(CompileTarget),
+    /// Warning codes or names to treat as errors (empty means all warnings)
+    WarningsAsErrors(Vec<String>),
+    /// Warning codes or names to disable
+    DisableWarnings(Vec<String>),
+    EnableWarning(String),
+    DisableWarning(String),
+    EmitIr(bool),
+    ReportDownstreamTime(bool),
+    ReportPerfBenchmark(bool),
+    SkipSpirvValidation(bool),
+    DisableShortCircuit(bool),
+    MinimumSlangOptimization(bool),
+    DisableNonEssentialValidations(bool),
+    DisableSourceMap(bool),
+    UnscopedEnum(bool),
+    /// Preserve all resource parameters in the output code
+    PreserveParameters(bool),
+    Capability(CapabilityID),
+    DefaultImageFormatUnknown(bool),
+    DisableDynamicDispatch(bool),
+    DisableSpecialization(bool),
+    FloatingPointMode(FloatingPointMode),
+    DebugInformation(DebugInfoLevel),
+    DebugInformationFormat(DebugInfoFormat),
+    LineDirectiveMode(LineDirectiveMode),
+    Optimization(OptimizationLevel),
+    Obfuscate(bool),
+    /// Shift the bindings of a register class in a descriptor set (`-fvk-<kind>-shift <shift> <set>`)
+    VulkanBindShift {
+        kind: VulkanShiftKind,
+        set: u32,
+        shift: u32,
+    },
+    /// Shift the bindings of a register class in every descriptor set
+    VulkanBindShiftAll {
+        kind: VulkanShiftKind,
+        shift: u32,
+    },
+    /// Place the global uniform buffer at `binding` in `set`
+    VulkanBindGlobals {
+        binding: u32,
+        set: u32,
+    },
+    VulkanInvertY(bool),
+    VulkanUseDxPositionW(bool),
+    VulkanUseEntryPointName(bool),
+    VulkanUseGlLayout(bool),
+    VulkanEmitReflection(bool),
+    GlslForceScalarLayout(bool),
+    ForceDxLayout(bool),
+    EnableEffectAnnotations(bool),
+    EmitSpirvMethod(EmitSpirvMethod),
+    /// Do not issue an error when the linked program has unresolved extern function symbols
+    IncompleteLibrary(bool),
+    /// Extra arguments passed to a downstream compiler (e.g. `dxc`)
+    DownstreamArgs {
+        compiler: String,
+        args: Vec<String>,
+    },
+    DumpIntermediates(bool),
+    DumpIr(bool),
+    SkipCodeGen(bool),
+    ValidateIr(bool),
+    LoopInversion(bool),
+    LanguageVersion(u32),
+    /// Additional type conformance to link, in the format of `"<TypeName>:<IInterfaceName>[=<sequentialId>]"`
+    TypeConformance(String),
+    EmitReflectionJson(bool),
+    GenerateWholeProgram(bool),
+    /// Only load precompiled modules if they are up-to-date with their source
+    UseUpToDateBinaryModule(bool),
+    EmbedDownstreamIr(bool),
+    /// Any option not covered above, with its raw value
+    Other {
+        name: CompilerOptionName,
+        int_values: [i32; 2],
+        string_values: [Option<String>; 2],
+    },
+}
+impl CompilerOption {
+    pub const fn name(&self) -> CompilerOptionName {
+        match self {
+            Self::MacroDefine { .. } => CompilerOptionName::MacroDefine,
+            Self::Include(_) => CompilerOptionName::Include,
+            Self::Language(_) => CompilerOptionName::Language,
+            Self::MatrixLayoutColumn(_) => CompilerOptionName::MatrixLayoutColumn,
+            Self::MatrixLayoutRow(_) => CompilerOptionName::MatrixLayoutRow,
+            Self::ZeroInitialize(_) => CompilerOptionName::ZeroInitialize,
+            Self::IgnoreCapabilities(_) => CompilerOptionName::IgnoreCapabilities,
+            Self::RestrictiveCapabilityCheck(_) => CompilerOptionName::RestrictiveCapabilityCheck,
+            Self::ModuleName(_) => CompilerOptionName::ModuleName,
+            Self::Profile(_) => CompilerOptionName::Profile,
+            Self::Stage(_) => CompilerOptionName::Stage,
+            Self::Target(_) => CompilerOptionName::Target,
+            Self::WarningsAsErrors(_) => CompilerOptionName::WarningsAsErrors,
+            Self::DisableWarnings(_) => CompilerOptionName::DisableWarnings,
+            Self::EnableWarning(_) => CompilerOptionName::EnableWarning,
+            Self::DisableWarning(_) => CompilerOptionName::DisableWarning,
+            Self::EmitIr(_) => CompilerOptionName::EmitIr,
+            Self::ReportDownstreamTime(_) => CompilerOptionName::ReportDownstreamTime,
+            Self::ReportPerfBenchmark(_) => CompilerOptionName::ReportPerfBenchmark,
+            Self::SkipSpirvValidation(_) => CompilerOptionName::SkipSPIRVValidation,
+            Self::DisableShortCircuit(_) => CompilerOptionName::DisableShortCircuit,
+            Self::MinimumSlangOptimization(_) => CompilerOptionName::MinimumSlangOptimization,
+            Self::DisableNonEssentialValidations(_) => {
+                CompilerOptionName::DisableNonEssentialValidations
+            }
+            Self::DisableSourceMap(_) => CompilerOptionName::DisableSourceMap,
+            Self::UnscopedEnum(_) => CompilerOptionName::UnscopedEnum,
+            Self::PreserveParameters(_) => CompilerOptionName::PreserveParameters,
+            Self::Capability(_) => CompilerOptionName::Capability,
+            Self::DefaultImageFormatUnknown(_) => CompilerOptionName::DefaultImageFormatUnknown,
+            Self::DisableDynamicDispatch(_) => CompilerOptionName::DisableDynamicDispatch,
+            Self::DisableSpecialization(_) => CompilerOptionName::DisableSpecialization,
+            Self::FloatingPointMode(_) => CompilerOptionName::FloatingPointMode,
+            Self::DebugInformation(_) => CompilerOptionName::DebugInformation,
+            Self::DebugInformationFormat(_) => CompilerOptionName::DebugInformationFormat,
+            Self::LineDirectiveMode(_) => CompilerOptionName::LineDirectiveMode,
+            Self::Optimization(_) => CompilerOptionName::Optimization,
+            Self::Obfuscate(_) => CompilerOptionName::Obfuscate,
+            Self::VulkanBindShift { .. } => CompilerOptionName::VulkanBindShift,
+            Self::VulkanBindShiftAll { .. } => CompilerOptionName::VulkanBindShiftAll,
+            Self::VulkanBindGlobals { .. } => CompilerOptionName::VulkanBindGlobals,
+            Self::VulkanInvertY(_) => CompilerOptionName::VulkanInvertY,
+            Self::VulkanUseDxPositionW(_) => CompilerOptionName::VulkanUseDxPositionW,
+            Self::VulkanUseEntryPointName(_) => CompilerOptionName::VulkanUseEntryPointName,
+            Self::VulkanUseGlLayout(_) => CompilerOptionName::VulkanUseGLLayout,
+            Self::VulkanEmitReflection(_) => CompilerOptionName::VulkanEmitReflection,
+            Self::GlslForceScalarLayout(_) => CompilerOptionName::GLSLForceScalarLayout,
+            Self::ForceDxLayout(_) => CompilerOptionName::ForceDXLayout,
+            Self::EnableEffectAnnotations(_) => CompilerOptionName::EnableEffectAnnotations,
+            Self::EmitSpirvMethod(_) => CompilerOptionName::EmitSpirvMethod,
+            Self::IncompleteLibrary(_) => CompilerOptionName::IncompleteLibrary,
+            Self::DownstreamArgs { .. } => CompilerOptionName::DownstreamArgs,
+            Self::DumpIntermediates(_) => CompilerOptionName::DumpIntermediates,
+            Self::DumpIr(_) => CompilerOptionName::DumpIr,
+            Self::SkipCodeGen(_) => CompilerOptionName::SkipCodeGen,
+            Self::ValidateIr(_) => CompilerOptionName::ValidateIr,
+            Self::LoopInversion(_) => CompilerOptionName::LoopInversion,
+            Self::LanguageVersion(_) => CompilerOptionName::LanguageVersion,
+            Self::TypeConformance(_) => CompilerOptionName::TypeConformance,
+            Self::EmitReflectionJson(_) => CompilerOptionName::EmitReflectionJSON,
+            Self::GenerateWholeProgram(_) => CompilerOptionName::GenerateWholeProgram,
+            Self::UseUpToDateBinaryModule(_) => CompilerOptionName::UseUpToDateBinaryModule,
+            Self::EmbedDownstreamIr(_) => CompilerOptionName::EmbedDownstreamIR,
+            Self::Other { name, .. } => *name,
+        }
+    }
+}
+
+/// Raw value of a compiler option before its strings are placed in owned storage
+struct LoweredValue<'a> {
+    kind: CompilerOptionValueKind,
+    int_values: [i32; 2],
+    string_values: [Option<Cow<'a, str>>; 2],
+}
+impl LoweredValue<'_> {
+    const fn int(v0: i32, v1: i32) -> Self {
+        Self {
+            kind: CompilerOptionValueKind::Int,
+            int_values: [v0, v1],
+            string_values: [None, None],
+        }
+    }
+}
+impl<'a> LoweredValue<'a> {
+    const fn string(v0: Cow<'a, str>, v1: Option<Cow<'a, str>>) -> Self {
+        Self {
+            kind: CompilerOptionValueKind::String,
+            int_values: [0, 0],
+            string_values: [Some(v0), v1],
+        }
+    }
+}
+impl CompilerOption {
+    fn lower(&self) -> LoweredValue<'_> {
+        match self {
+            Self::MacroDefine { name, value } => {
+                LoweredValue::string(Cow::Borrowed(name), Some(Cow::Borrowed(value)))
+            }
+            Self::Include(s)
+            | Self::ModuleName(s)
+            | Self::EnableWarning(s)
+            | Self::DisableWarning(s)
+            | Self::TypeConformance(s) => LoweredValue::string(Cow::Borrowed(s), None),
+            Self::WarningsAsErrors(xs) if xs.is_empty() => {
+                LoweredValue::string(Cow::Borrowed("all"), None)
+            }
+            Self::WarningsAsErrors(xs) | Self::DisableWarnings(xs) => {
+                LoweredValue::string(Cow::Owned(xs.join(",")), None)
+            }
+            Self::DownstreamArgs { compiler, args } => {
+                LoweredValue::string(Cow::Borrowed(compiler), Some(Cow::Owned(args.join("\n"))))
+            }
+            &Self::Language(v)
+            | &Self::Target(v)
+            | &Self::Capability(v)
+            | &Self::EmitSpirvMethod(v) => LoweredValue::int(v, 0),
+            &Self::Profile(v)
+            | &Self::Stage(v)
+            | &Self::FloatingPointMode(v)
+            | &Self::DebugInformation(v)
+            | &Self::DebugInformationFormat(v)
+            | &Self::LineDirectiveMode(v)
+            | &Self::Optimization(v)
+            | &Self::LanguageVersion(v) => LoweredValue::int(v as _, 0),
+            &Self::VulkanBindShift { kind, set, shift } => {
+                LoweredValue::int(((kind as i32) << 24) | (set as i32 & 0xff_ffff), shift as _)
+            }
+            &Self::VulkanBindShiftAll { kind, shift } => LoweredValue::int(kind as _, shift as _),
+            &Self::VulkanBindGlobals { binding, set } => LoweredValue::int(binding as _, set as _),
+            &Self::MatrixLayoutColumn(b)
+            | &Self::MatrixLayoutRow(b)
+            | &Self::ZeroInitialize(b)
+            | &Self::IgnoreCapabilities(b)
+            | &Self::RestrictiveCapabilityCheck(b)
+            | &Self::EmitIr(b)
+            | &Self::ReportDownstreamTime(b)
+            | &Self::ReportPerfBenchmark(b)
+            | &Self::SkipSpirvValidation(b)
+            | &Self::DisableShortCircuit(b)
+            | &Self::MinimumSlangOptimization(b)
+            | &Self::DisableNonEssentialValidations(b)
+            | &Self::DisableSourceMap(b)
+            | &Self::UnscopedEnum(b)
+            | &Self::PreserveParameters(b)
+            | &Self::DefaultImageFormatUnknown(b)
+            | &Self::DisableDynamicDispatch(b)
+            | &Self::DisableSpecialization(b)
+            | &Self::Obfuscate(b)
+            | &Self::VulkanInvertY(b)
+            | &Self::VulkanUseDxPositionW(b)
+            | &Self::VulkanUseEntryPointName(b)
+            | &Self::VulkanUseGlLayout(b)
+            | &Self::VulkanEmitReflection(b)
+            | &Self::GlslForceScalarLayout(b)
+            | &Self::ForceDxLayout(b)
+            | &Self::EnableEffectAnnotations(b)
+            | &Self::IncompleteLibrary(b)
+            | &Self::DumpIntermediates(b)
+            | &Self::DumpIr(b)
+            | &Self::SkipCodeGen(b)
+            | &Self::ValidateIr(b)
+            | &Self::LoopInversion(b)
+            | &Self::EmitReflectionJson(b)
+            | &Self::GenerateWholeProgram(b)
+            | &Self::UseUpToDateBinaryModule(b)
+            | &Self::EmbedDownstreamIr(b) => LoweredValue::int(b as _, 0),
+            Self::Other {
+                int_values,
+                string_values: [s0, s1],
+                ..
+            } => LoweredValue {
+                kind: if s0.is_some() || s1.is_some() {
+                    CompilerOptionValueKind::String
+                } else {
+                    CompilerOptionValueKind::Int
+                },
+                int_values: *int_values,
+                string_values: [
+                    s0.as_deref().map(Cow::Borrowed),
+                    s1.as_deref().map(Cow::Borrowed),
+                ],
+            },
+        }
+    }
+}
+
+/// Owned list of [`CompilerOptionEntry`] together with the strings they point to.
+#[derive(Default)]
+pub struct CompilerOptionEntries {
+    entries: Vec<CompilerOptionEntry>,
+    strings: Vec<CString>,
+}
+impl CompilerOptionEntries {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// # Panics
+    /// Panics if a string in `option` contains an interior NUL character.
+    pub fn push(&mut self, option: &CompilerOption) {
+        let LoweredValue {
+            kind,
+            int_values: [int_value0, int_value1],
+            string_values: [s0, s1],
+        } = option.lower();
+        let string_value0 = self.store(s0.as_deref());
+        let string_value1 = self.store(s1.as_deref());
+
+        self.entries.push(CompilerOptionEntry {
+            name: option.name(),
+            value: CompilerOptionValue {
+                kind,
+                int_value0,
+                int_value1,
+                string_value0,
+                string_value1,
+            },
+        });
+    }
+
+    /// Appends a raw entry.
+    ///
+    /// # Safety
+    /// Strings referenced by `entry` must outlive every use of this list.
+    #[inline]
+    pub unsafe fn push_raw(&mut self, entry: CompilerOptionEntry) {
+        self.entries.push(entry);
+    }
+
+    fn store(&mut self, s: Option<&str>) -> *const c_char {
+        let Some(s) = s else {
+            return core::ptr::null();
+        };
+
+        let s = CString::new(s).expect("compiler option strings must not contain NUL");
+        // the heap buffer of a CString does not move with the CString itself
+        let p = s.as_ptr();
+        self.strings.push(s);
+
+        p
+    }
+
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    #[inline(always)]
+    pub fn as_slice(&self) -> &[CompilerOptionEntry] {
+        &self.entries
+    }
+
+    #[inline(always)]
+    pub fn as_mut_slice(&mut self) -> &mut [CompilerOptionEntry] {
+        &mut self.entries
+    }
+}
+impl<'a> Extend<&'a CompilerOption> for CompilerOptionEntries {
+    fn extend<T: IntoIterator<Item = &'a CompilerOption>>(&mut self, iter: T) {
+        for o in iter {
+            self.push(o);
+        }
+    }
+}
+impl Extend<CompilerOption> for CompilerOptionEntries {
+    fn extend<T: IntoIterator<Item = CompilerOption>>(&mut self, iter: T) {
+        for o in iter {
+            self.push(&o);
+        }
+    }
+}
+impl<'a> FromIterator<&'a CompilerOption> for CompilerOptionEntries {
+    fn from_iter<T: IntoIterator<Item = &'a CompilerOption>>(iter: T) -> Self {
+        let mut x = Self::new();
+        x.extend(iter);
+        x
+    }
+}
+impl FromIterator<CompilerOption> for CompilerOptionEntries {
+    fn from_iter<T: IntoIterator<Item = CompilerOption>>(iter: T) -> Self {
+        let mut x = Self::new();
+        x.extend(iter);
+        x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::ffi::CStr;
+
+    use super::*;
+
+    fn string<'a>(p: *const c_char) -> Option<&'a str> {
+        if p.is_null() {
+            None
+        } else {
+            Some(unsafe { CStr::from_ptr(p) }.to_str().unwrap())
+        }
+    }
+
+    fn lower(option: CompilerOption) -> (CompilerOptionValueKind, [i32; 2], [Option<String>; 2]) {
+        let entries = CompilerOptionEntries::from_iter([option]);
+        let [entry] = entries.as_slice() else {
+            panic!("expected a single entry");
+        };
+        let v = &entry.value;
+
+        (
+            v.kind,
+            [v.int_value0, v.int_value1],
+            [
+                string(v.string_value0).map(ToOwned::to_owned),
+                string(v.string_value1).map(ToOwned::to_owned),
+            ],
+        )
+    }
+
+    #[test]
+    fn vulkan_bind_shift_packs_kind_and_set() {
+        let (kind, ints, _) = lower(CompilerOption::VulkanBindShift {
+            kind: VulkanShiftKind::UnorderedAccess,
+            set: 5,
+            shift: 100,
+        });
+
+        assert!(matches!(kind, CompilerOptionValueKind::Int));
+        assert_eq!(ints, [(2 << 24) | 5, 100]);
+
+        let (_, ints, _) = lower(CompilerOption::VulkanBindShiftAll {
+            kind: VulkanShiftKind::Sampler,
+            shift: 7,
+        });
+        assert_eq!(ints, [3, 7]);
+    }
+
+    #[test]
+    fn downstream_args_are_newline_joined() {
+        let (kind, _, strings) = lower(CompilerOption::DownstreamArgs {
+            compiler: "dxc".into(),
+            args: vec!["-Zi".into(), "-Qembed_debug".into()],
+        });
+
+        assert!(matches!(kind, CompilerOptionValueKind::String));
+        assert_eq!(
+            strings,
+            [Some("dxc".into()), Some("-Zi\n-Qembed_debug".into())]
+        );
+    }
+
+    #[test]
+    fn warnings_as_errors() {
+        let (_, _, strings) = lower(CompilerOption::WarningsAsErrors(Vec::new()));
+        assert_eq!(strings, [Some("all".into()), None]);
+
+        let (_, _, strings) = lower(CompilerOption::WarningsAsErrors(vec![
+            "15205".into(),
+            "30081".into(),
+        ]));
+        assert_eq!(strings, [Some("15205,30081".into()), None]);
+    }
+}
@@ -0,0 +1,867 @@
+//! D3D12 root signature generation from reflection, for programs compiled to `SLANG_DXIL`/`SLANG_HLSL`.
+//!
+//! [`RootSignature::new`] derives a root signature from the register assignment Slang made:
+//!
+//! * the descriptor ranges of the global scope and of every entry point become descriptor tables, one per register
+//!   space and shader visibility, with samplers in tables of their own as D3D12 requires
+//! * the contents of every `ParameterBlock<T>` (an automatically introduced constant buffer for the ordinary data of
+//!   `T`, then the resources of `T`) go to tables in the register space of the block
+//! * push-constant buffers (`[vk::push_constant]`) become root constants
+//! * single samplers are reported as [`StaticSamplerCandidate`]s; they stay in the sampler tables, and can be
+//!   replaced by `StaticSampler(...)` clauses if their state is known ahead of time
+//!
+//! [`RootSignature::to_hlsl`] renders the textual form accepted by `[RootSignature(...)]` and `dxc -rootsig-define`,
+//! so the result can be inspected and tested without Windows.
+
+use core::fmt::Write;
+
+use crate::{
+    ffi::{self, SlangInt},
+    reflection::{BindingType, ParameterCategory, Shader, VariableLayout},
+};
+
+/// Mirror of `D3D12_DESCRIPTOR_RANGE_TYPE`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[repr(i32)]
+pub enum DescriptorRangeType {
+    Srv = 0,
+    Uav = 1,
+    Cbv = 2,
+    Sampler = 3,
+}
+impl DescriptorRangeType {
+    pub const fn from_category(category: ParameterCategory) -> Option<Self> {
+        Some(match category {
+            ParameterCategory::ShaderResource => Self::Srv,
+            ParameterCategory::UnorderedAccess => Self::Uav,
+            ParameterCategory::ConstantBuffer => Self::Cbv,
+            ParameterCategory::SamplerState => Self::Sampler,
+            _ => return None,
+        })
+    }
+
+    const fn hlsl_name(self) -> &'static str {
+        match self {
+            Self::Srv => "SRV",
+            Self::Uav => "UAV",
+            Self::Cbv => "CBV",
+            Self::Sampler => "Sampler",
+        }
+    }
+
+    const fn register_prefix(self) -> char {
+        match self {
+            Self::Srv => 't',
+            Self::Uav => 'u',
+            Self::Cbv => 'b',
+            Self::Sampler => 's',
+        }
+    }
+}
+
+/// Mirror of `D3D12_SHADER_VISIBILITY`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[repr(i32)]
+pub enum ShaderVisibility {
+    All = 0,
+    Vertex = 1,
+    Hull = 2,
+    Domain = 3,
+    Geometry = 4,
+    Pixel = 5,
+    Amplification = 6,
+    Mesh = 7,
+}
+impl ShaderVisibility {
+    /// The visibility for a Slang stage; stages without a dedicated visibility (compute, ray tracing) use
+    /// [`ShaderVisibility::All`].
+    pub const fn from_stage(stage: crate::Stage) -> Self {
+        match stage {
+            ffi::SLANG_STAGE_VERTEX => Self::Vertex,
+            ffi::SLANG_STAGE_HULL => Self::Hull,
+            ffi::SLANG_STAGE_DOMAIN => Self::Domain,
+            ffi::SLANG_STAGE_GEOMETRY => Self::Geometry,
+            ffi::SLANG_STAGE_FRAGMENT => Self::Pixel,
+            ffi::SLANG_STAGE_AMPLIFICATION => Self::Amplification,
+            ffi::SLANG_STAGE_MESH => Self::Mesh,
+            _ => Self::All,
+        }
+    }
+
+    const fn hlsl_name(self) -> &'static str {
+        match self {
+            Self::All => "SHADER_VISIBILITY_ALL",
+            Self::Vertex => "SHADER_VISIBILITY_VERTEX",
+            Self::Hull => "SHADER_VISIBILITY_HULL",
+            Self::Domain => "SHADER_VISIBILITY_DOMAIN",
+            Self::Geometry => "SHADER_VISIBILITY_GEOMETRY",
+            Self::Pixel => "SHADER_VISIBILITY_PIXEL",
+            Self::Amplification => "SHADER_VISIBILITY_AMPLIFICATION",
+            Self::Mesh => "SHADER_VISIBILITY_MESH",
+        }
+    }
+}
+
+/// Shape of a `D3D12_DESCRIPTOR_RANGE`. Ranges are packed in table order
+/// (`D3D12_DESCRIPTOR_RANGE_OFFSET_APPEND`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DescriptorRange {
+    pub range_type: DescriptorRangeType,
+    /// Number of descriptors, or `None` for an unbounded array.
+    pub num_descriptors: Option<u32>,
+    pub base_shader_register: u32,
+    pub register_space: u32,
+}
+
+/// Shape of a `D3D12_ROOT_PARAMETER`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RootParameter {
+    DescriptorTable {
+        ranges: Vec<DescriptorRange>,
+        visibility: ShaderVisibility,
+    },
+    Constants {
+        shader_register: u32,
+        register_space: u32,
+        num_32bit_values: u32,
+        visibility: ShaderVisibility,
+    },
+}
+
+/// A sampler bound on its own, which could be turned into a static sampler.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StaticSamplerCandidate {
+    pub shader_register: u32,
+    pub register_space: u32,
+    pub visibility: ShaderVisibility,
+}
+
+/// Root signature description of a program.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RootSignature {
+    /// Set when the program has a vertex entry point (`D3D12_ROOT_SIGNATURE_FLAG_ALLOW_INPUT_ASSEMBLER_INPUT_LAYOUT`).
+    pub allow_input_assembler_input_layout: bool,
+    /// Root constants first, then descriptor tables ordered by register space and visibility.
+    pub parameters: Vec<RootParameter>,
+    pub static_sampler_candidates: Vec<StaticSamplerCandidate>,
+}
+impl RootSignature {
+    pub fn new(shader: Shader<'_>) -> Self {
+        let mut builder = Builder::default();
+
+        if let Some(globals) = shader.global_params_var_layout() {
+            builder.add_scope(globals, ShaderVisibility::All);
+        }
+        for entry_point in shader.iter_entry_point() {
+            let stage = entry_point.stage();
+            builder.allow_input_assembler_input_layout |= stage == ffi::SLANG_STAGE_VERTEX;
+            if let Some(scope) = entry_point.var_layout() {
+                builder.add_scope(scope, ShaderVisibility::from_stage(stage));
+            }
+        }
+
+        builder.finish()
+    }
+
+    /// Renders the HLSL root signature string, e.g.
+    /// `RootFlags(ALLOW_INPUT_ASSEMBLER_INPUT_LAYOUT), DescriptorTable(CBV(b0), SRV(t0, numDescriptors=2))`.
+    pub fn to_hlsl(&self) -> String {
+        let mut clauses = Vec::new();
+        if self.allow_input_assembler_input_layout {
+            clauses.push(String::from(
+                "RootFlags(ALLOW_INPUT_ASSEMBLER_INPUT_LAYOUT)",
+            ));
+        }
+
+        for p in &self.parameters {
+            let mut s = String::new();
+            match p {
+                RootParameter::Constants {
+                    shader_register,
+                    register_space,
+                    num_32bit_values,
+                    visibility,
+                } => {
+                    write!(
+                        s,
+                        "RootConstants(num32BitConstants={num_32bit_values}, b{shader_register}"
+                    )
+                    .unwrap();
+                    write_space_and_visibility(&mut s, *register_space, *visibility);
+                }
+                RootParameter::DescriptorTable { ranges, visibility } => {
+                    s.push_str("DescriptorTable(");
+                    for (n, r) in ranges.iter().enumerate() {
+                        if n > 0 {
+                            s.push_str(", ");
+                        }
+                        write!(
+                            s,
+                            "{}({}{}",
+                            r.range_type.hlsl_name(),
+                            r.range_type.register_prefix(),
+                            r.base_shader_register
+                        )
+                        .unwrap();
+                        match r.num_descriptors {
+                            Some(1) => (),
+                            Some(count) => write!(s, ", numDescriptors={count}").unwrap(),
+                            None => s.push_str(", numDescriptors=unbounded"),
+                        }
+                        write_space_and_visibility(&mut s, r.register_space, ShaderVisibility::All);
+                    }
+                    if *visibility != ShaderVisibility::All {
+                        write!(s, ", visibility={}", visibility.hlsl_name()).unwrap();
+                    }
+                    s.push(')');
+                }
+            }
+            clauses.push(s);
+        }
+
+        clauses.join(", ")
+    }
+}
+
+impl Shader<'_> {
+    /// Derives the D3D12 root signature of this program. See [`RootSignature::new`].
+    #[inline(always)]
+    pub fn d3d12_root_signature(self) -> RootSignature {
+        RootSignature::new(self)
+    }
+}
+
+/// Appends the optional `space`/`visibility` arguments and closes the clause.
+fn write_space_and_visibility(s: &mut String, space: u32, visibility: ShaderVisibility) {
+    if space != 0 {
+        write!(s, ", space={space}").unwrap();
+    }
+    if visibility != ShaderVisibility::All {
+        write!(s, ", visibility={}", visibility.hlsl_name()).unwrap();
+    }
+    s.push(')');
+}
+
+/// A descriptor range of a scope, with the space offset of the descriptor set it is in.
+#[derive(Debug, Clone, Copy)]
+struct ScopeRange {
+    binding_type: BindingType,
+    category: ParameterCategory,
+    descriptor_count: SlangInt,
+    index_offset: SlangInt,
+    space_offset: SlangInt,
+}
+
+/// A `ParameterBlock<T>` of a scope.
+#[derive(Debug, Clone, Copy)]
+struct ParameterBlock<S> {
+    /// Offset of the register space of the block from the space of the scope.
+    space_offset: SlangInt,
+    /// Layout of `T`.
+    element: S,
+    /// Layout of the constant buffer holding the ordinary data of `T`.
+    container: Option<S>,
+}
+
+/// A scope the builder assigns registers for: the global scope, the parameters of an entry point, or the element of a
+/// parameter block.
+///
+/// Descriptor ranges and push-constant buffers are numbered relative to the first register and space of their
+/// category in the scope. The contents of parameter blocks are scopes of their own, and are not part of the
+/// descriptor ranges.
+trait ScopeLayout: Copy {
+    /// First register of `category` in the scope, or 0 if the scope has none of that category.
+    fn register(self, category: ParameterCategory) -> SlangInt;
+    /// Register space of `category` relative to the enclosing parameter block, or 0 if the scope has none of that
+    /// category.
+    fn space(self, category: ParameterCategory) -> SlangInt;
+    /// Size in bytes of the uniform data.
+    fn uniform_size(self) -> usize;
+    fn descriptor_ranges(self) -> impl Iterator<Item = ScopeRange>;
+    /// Size in bytes of the contents and first descriptor range of every push-constant buffer.
+    fn push_constants(self) -> impl Iterator<Item = (usize, ScopeRange)>;
+    fn parameter_blocks(self) -> impl Iterator<Item = ParameterBlock<Self>>;
+}
+impl ScopeLayout for VariableLayout<'_> {
+    #[inline]
+    fn register(self, category: ParameterCategory) -> SlangInt {
+        self.offset(category) as _
+    }
+
+    #[inline]
+    fn space(self, category: ParameterCategory) -> SlangInt {
+        VariableLayout::space(self, category) as _
+    }
+
+    #[inline]
+    fn uniform_size(self) -> usize {
+        self.type_layout().size(ParameterCategory::Uniform)
+    }
+
+    fn descriptor_ranges(self) -> impl Iterator<Item = ScopeRange> {
+        self.type_layout().iter_descriptor_set().flat_map(|set| {
+            let space_offset = set.space_offset();
+            set.iter_descriptor_range().map(move |range| ScopeRange {
+                binding_type: range.r#type(),
+                category: range.category(),
+                descriptor_count: range.descriptor_count(),
+                index_offset: range.index_offset(),
+                space_offset,
+            })
+        })
+    }
+
+    fn push_constants(self) -> impl Iterator<Item = (usize, ScopeRange)> {
+        let type_layout = self.type_layout();
+        (0..type_layout.binding_range_count())
+            .filter(move |&i| type_layout.binding_range_type(i) == BindingType::PushConstant)
+            .map(move |binding_range| {
+                let size = type_layout
+                    .binding_range_leaf_type_layout(binding_range)
+                    .and_then(|l| l.element_type_layout())
+                    .map_or(0, |e| e.size(ParameterCategory::Uniform));
+                let set = type_layout.binding_range_descriptor_set_index(binding_range);
+                let first = type_layout.binding_range_first_descriptor_range_index(binding_range);
+                let range = ScopeRange {
+                    binding_type: BindingType::PushConstant,
+                    category: type_layout.descriptor_set_descriptor_range_category(set, first),
+                    descriptor_count: type_layout
+                        .descriptor_set_descriptor_range_descriptor_count(set, first),
+                    index_offset: type_layout
+                        .descriptor_set_descriptor_range_index_offset(set, first),
+                    space_offset: type_layout.descriptor_set_space_offset(set),
+                };
+                (size, range)
+            })
+    }
+
+    fn parameter_blocks(self) -> impl Iterator<Item = ParameterBlock<Self>> {
+        let type_layout = self.type_layout();
+        (0..type_layout.sub_object_range_count()).filter_map(move |sub_object| {
+            let binding_range = type_layout.sub_object_range_binding_range_index(sub_object);
+            if type_layout.binding_range_type(binding_range) != BindingType::ParameterBlock {
+                return None;
+            }
+            let block = type_layout.binding_range_leaf_type_layout(binding_range)?;
+            Some(ParameterBlock {
+                space_offset: type_layout.sub_object_range_space_offset(sub_object),
+                element: block.element_var_layout()?,
+                container: block.container_var_layout(),
+            })
+        })
+    }
+}
+
+/// Position of a scope: its layout, and the register space of the parameter block it is in.
+#[derive(Clone, Copy)]
+struct Origin<S> {
+    scope: S,
+    space: SlangInt,
+}
+impl<S: ScopeLayout> Origin<S> {
+    fn register(self, category: ParameterCategory) -> SlangInt {
+        self.scope.register(category)
+    }
+
+    fn space(self, category: ParameterCategory) -> SlangInt {
+        self.space + self.scope.space(category)
+    }
+}
+
+/// Key of a descriptor table: register space, visibility and whether it holds samplers.
+type TableKey = (u32, ShaderVisibility, bool);
+
+#[derive(Default)]
+struct Builder {
+    allow_input_assembler_input_layout: bool,
+    constants: Vec<RootParameter>,
+    tables: Vec<(TableKey, Vec<DescriptorRange>)>,
+    static_sampler_candidates: Vec<StaticSamplerCandidate>,
+}
+impl Builder {
+    fn add_scope(&mut self, scope: impl ScopeLayout, visibility: ShaderVisibility) {
+        self.add_layout(Origin { scope, space: 0 }, visibility);
+    }
+
+    fn add_layout(&mut self, origin: Origin<impl ScopeLayout>, visibility: ShaderVisibility) {
+        for range in origin.scope.descriptor_ranges() {
+            if range.binding_type == BindingType::PushConstant {
+                continue;
+            }
+            let Some(range_type) = DescriptorRangeType::from_category(range.category) else {
+                continue;
+            };
+            let range = DescriptorRange {
+                range_type,
+                num_descriptors: u32::try_from(range.descriptor_count).ok(),
+                base_shader_register: (origin.register(range.category) + range.index_offset) as _,
+                register_space: (origin.space(range.category) + range.space_offset) as _,
+            };
+            self.add_range(range, visibility);
+        }
+
+        self.add_root_constants(origin, visibility);
+        self.add_parameter_blocks(origin, visibility);
+    }
+
+    /// Adds the contents of the `ParameterBlock`s of the scope, which are not part of its descriptor sets.
+    fn add_parameter_blocks(
+        &mut self,
+        origin: Origin<impl ScopeLayout>,
+        visibility: ShaderVisibility,
+    ) {
+        for block in origin.scope.parameter_blocks() {
+            // the registers of a block start at zero in a space of its own
+            let space = origin.space + block.space_offset;
+
+            if block.element.uniform_size() > 0
+                && let Some(container) = block.container
+            {
+                let category = ParameterCategory::ConstantBuffer;
+                let range = DescriptorRange {
+                    range_type: DescriptorRangeType::Cbv,
+                    num_descriptors: Some(1),
+                    base_shader_register: container.register(category) as _,
+                    register_space: (space + container.space(category)) as _,
+                };
+                self.add_range(range, visibility);
+            }
+
+            let origin = Origin {
+                scope: block.element,
+                space,
+            };
+            self.add_layout(origin, visibility);
+        }
+    }
+
+    fn add_range(&mut self, range: DescriptorRange, visibility: ShaderVisibility) {
+        let is_sampler = range.range_type == DescriptorRangeType::Sampler;
+        if is_sampler && range.num_descriptors == Some(1) {
+            self.static_sampler_candidates.push(StaticSamplerCandidate {
+                shader_register: range.base_shader_register,
+                register_space: range.register_space,
+                visibility,
+            });
+        }
+
+        let key = (range.register_space, visibility, is_sampler);
+        match self.tables.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, ranges)) => ranges.push(range),
+            None => self.tables.push((key, vec![range])),
+        }
+    }
+
+    fn add_root_constants(
+        &mut self,
+        origin: Origin<impl ScopeLayout>,
+        visibility: ShaderVisibility,
+    ) {
+        for (size, range) in origin.scope.push_constants() {
+            if size == 0 {
+                continue;
+            }
+            self.constants.push(RootParameter::Constants {
+                shader_register: (origin.register(range.category) + range.index_offset) as _,
+                register_space: (origin.space(range.category) + range.space_offset) as _,
+                num_32bit_values: size.div_ceil(4) as _,
+                visibility,
+            });
+        }
+    }
+
+    fn finish(mut self) -> RootSignature {
+        self.tables.sort_by_key(|(k, _)| *k);
+
+        let mut parameters = self.constants;
+        parameters.extend(self.tables.into_iter().map(|((_, visibility, _), ranges)| {
+            RootParameter::DescriptorTable { ranges, visibility }
+        }));
+
+        RootSignature {
+            allow_input_assembler_input_layout: self.allow_input_assembler_input_layout,
+            parameters,
+            static_sampler_candidates: self.static_sampler_candidates,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const fn range(
+        range_type: DescriptorRangeType,
+        num_descriptors: Option<u32>,
+        base_shader_register: u32,
+        register_space: u32,
+    ) -> DescriptorRange {
+        DescriptorRange {
+            range_type,
+            num_descriptors,
+            base_shader_register,
+            register_space,
+        }
+    }
+
+    fn signature(parameters: Vec<RootParameter>) -> RootSignature {
+        RootSignature {
+            allow_input_assembler_input_layout: false,
+            parameters,
+            static_sampler_candidates: Vec::new(),
+        }
+    }
+
+    /// A hand-built scope. Categories missing from `registers` start at register zero of space zero.
+    #[derive(Default)]
+    struct Scope {
+        /// First register and register space of each category.
+        registers: Vec<(ParameterCategory, SlangInt, SlangInt)>,
+        uniform_size: usize,
+        ranges: Vec<ScopeRange>,
+        push_constants: Vec<(usize, ScopeRange)>,
+        /// Space offset, element and container of every parameter block.
+        blocks: Vec<(SlangInt, Scope, Option<Scope>)>,
+    }
+    impl ScopeLayout for &Scope {
+        fn register(self, category: ParameterCategory) -> SlangInt {
+            self.registers
+                .iter()
+                .find(|(c, _, _)| *c == category)
+                .map_or(0, |(_, register, _)| *register)
+        }
+
+        fn space(self, category: ParameterCategory) -> SlangInt {
+            self.registers
+                .iter()
+                .find(|(c, _, _)| *c == category)
+                .map_or(0, |(_, _, space)| *space)
+        }
+
+        fn uniform_size(self) -> usize {
+            self.uniform_size
+        }
+
+        fn descriptor_ranges(self) -> impl Iterator<Item = ScopeRange> {
+            self.ranges.iter().copied()
+        }
+
+        fn push_constants(self) -> impl Iterator<Item = (usize, ScopeRange)> {
+            self.push_constants.iter().copied()
+        }
+
+        fn parameter_blocks(self) -> impl Iterator<Item = ParameterBlock<Self>> {
+            self.blocks
+                .iter()
+                .map(|(space_offset, element, container)| ParameterBlock {
+                    space_offset: *space_offset,
+                    element,
+                    container: container.as_ref(),
+                })
+        }
+    }
+
+    const fn scope_range(
+        category: ParameterCategory,
+        descriptor_count: SlangInt,
+        index_offset: SlangInt,
+        space_offset: SlangInt,
+    ) -> ScopeRange {
+        let binding_type = match category {
+            ParameterCategory::ShaderResource => BindingType::Texture,
+            ParameterCategory::UnorderedAccess => BindingType::MutableTexture,
+            ParameterCategory::ConstantBuffer => BindingType::ConstantBuffer,
+            ParameterCategory::SamplerState => BindingType::Sampler,
+            _ => BindingType::Unknown,
+        };
+        ScopeRange {
+            binding_type,
+            category,
+            descriptor_count,
+            index_offset,
+            space_offset,
+        }
+    }
+
+    fn build(scopes: &[(&Scope, ShaderVisibility)]) -> RootSignature {
+        let mut builder = Builder::default();
+        for (scope, visibility) in scopes {
+            builder.add_scope(*scope, *visibility);
+        }
+        builder.finish()
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(signature(Vec::new()).to_hlsl(), "");
+        assert_eq!(
+            RootSignature {
+                allow_input_assembler_input_layout: true,
+                ..signature(Vec::new())
+            }
+            .to_hlsl(),
+            "RootFlags(ALLOW_INPUT_ASSEMBLER_INPUT_LAYOUT)"
+        );
+    }
+
+    #[test]
+    fn root_constants() {
+        let s = signature(vec![
+            RootParameter::Constants {
+                shader_register: 0,
+                register_space: 0,
+                num_32bit_values: 16,
+                visibility: ShaderVisibility::All,
+            },
+            RootParameter::Constants {
+                shader_register: 3,
+                register_space: 2,
+                num_32bit_values: 1,
+                visibility: ShaderVisibility::Mesh,
+            },
+        ]);
+
+        assert_eq!(
+            s.to_hlsl(),
+            "RootConstants(num32BitConstants=16, b0), \
+             RootConstants(num32BitConstants=1, b3, space=2, visibility=SHADER_VISIBILITY_MESH)"
+        );
+    }
+
+    #[test]
+    fn descriptor_tables() {
+        let s = signature(vec![
+            RootParameter::DescriptorTable {
+                ranges: vec![
+                    range(DescriptorRangeType::Cbv, Some(1), 0, 0),
+                    range(DescriptorRangeType::Srv, Some(4), 1, 0),
+                    range(DescriptorRangeType::Uav, None, 0, 3),
+                ],
+                visibility: ShaderVisibility::All,
+            },
+            RootParameter::DescriptorTable {
+                ranges: vec![range(DescriptorRangeType::Sampler, Some(1), 2, 1)],
+                visibility: ShaderVisibility::Pixel,
+            },
+        ]);
+
+        assert_eq!(
+            s.to_hlsl(),
+            "DescriptorTable(CBV(b0), SRV(t1, numDescriptors=4), UAV(u0, numDescriptors=unbounded, space=3)), \
+             DescriptorTable(Sampler(s2, space=1), visibility=SHADER_VISIBILITY_PIXEL)"
+        );
+    }
+
+    #[test]
+    fn everything() {
+        let s = RootSignature {
+            allow_input_assembler_input_layout: true,
+            ..signature(vec![
+                RootParameter::Constants {
+                    shader_register: 1,
+                    register_space: 0,
+                    num_32bit_values: 4,
+                    visibility: ShaderVisibility::Vertex,
+                },
+                RootParameter::DescriptorTable {
+                    ranges: vec![
+                        range(DescriptorRangeType::Cbv, Some(1), 0, 0),
+                        range(DescriptorRangeType::Srv, None, 2, 1),
+                    ],
+                    visibility: ShaderVisibility::All,
+                },
+                RootParameter::DescriptorTable {
+                    ranges: vec![range(DescriptorRangeType::Sampler, Some(2), 0, 0)],
+                    visibility: ShaderVisibility::Pixel,
+                },
+            ])
+        };
+
+        assert_eq!(
+            s.to_hlsl(),
+            "RootFlags(ALLOW_INPUT_ASSEMBLER_INPUT_LAYOUT), \
+             RootConstants(num32BitConstants=4, b1, visibility=SHADER_VISIBILITY_VERTEX), \
+             DescriptorTable(CBV(b0), SRV(t2, numDescriptors=unbounded, space=1)), \
+             DescriptorTable(Sampler(s0, numDescriptors=2), visibility=SHADER_VISIBILITY_PIXEL)"
+        );
+    }
+
+    #[test]
+    fn tables_by_space_and_visibility() {
+        let globals = Scope {
+            ranges: vec![
+                scope_range(ParameterCategory::ConstantBuffer, 1, 0, 0),
+                scope_range(ParameterCategory::SamplerState, 1, 0, 0),
+                scope_range(ParameterCategory::ShaderResource, 4, 1, 0),
+                scope_range(ParameterCategory::UnorderedAccess, -1, 0, 2),
+                scope_range(ParameterCategory::SamplerState, 2, 1, 0),
+                // not a register category
+                scope_range(ParameterCategory::Uniform, 1, 0, 0),
+            ],
+            ..Default::default()
+        };
+        // registers of an entry point follow the ones of the globals
+        let fragment = Scope {
+            registers: vec![(ParameterCategory::ShaderResource, 5, 1)],
+            ranges: vec![scope_range(ParameterCategory::ShaderResource, 1, 2, 0)],
+            ..Default::default()
+        };
+
+        let s = build(&[
+            (&fragment, ShaderVisibility::Pixel),
+            (&globals, ShaderVisibility::All),
+        ]);
+        assert_eq!(
+            s.parameters,
+            [
+                RootParameter::DescriptorTable {
+                    ranges: vec![
+                        range(DescriptorRangeType::Cbv, Some(1), 0, 0),
+                        range(DescriptorRangeType::Srv, Some(4), 1, 0),
+                    ],
+                    visibility: ShaderVisibility::All,
+                },
+                RootParameter::DescriptorTable {
+                    ranges: vec![
+                        range(DescriptorRangeType::Sampler, Some(1), 0, 0),
+                        range(DescriptorRangeType::Sampler, Some(2), 1, 0),
+                    ],
+                    visibility: ShaderVisibility::All,
+                },
+                RootParameter::DescriptorTable {
+                    ranges: vec![range(DescriptorRangeType::Srv, Some(1), 7, 1)],
+                    visibility: ShaderVisibility::Pixel,
+                },
+                RootParameter::DescriptorTable {
+                    ranges: vec![range(DescriptorRangeType::Uav, None, 0, 2)],
+                    visibility: ShaderVisibility::All,
+                },
+            ]
+        );
+        assert_eq!(
+            s.static_sampler_candidates,
+            [StaticSamplerCandidate {
+                shader_register: 0,
+                register_space: 0,
+                visibility: ShaderVisibility::All,
+            }]
+        );
+    }
+
+    #[test]
+    fn parameter_blocks() {
+        let inner = Scope {
+            uniform_size: 4,
+            ranges: vec![scope_range(ParameterCategory::SamplerState, 1, 0, 0)],
+            ..Default::default()
+        };
+        let element = Scope {
+            registers: vec![(ParameterCategory::ConstantBuffer, 1, 0)],
+            uniform_size: 16,
+            ranges: vec![
+                scope_range(ParameterCategory::ConstantBuffer, 1, 0, 0),
+                scope_range(ParameterCategory::ShaderResource, 2, 0, 0),
+            ],
+            blocks: vec![(1, inner, Some(Scope::default()))],
+            ..Default::default()
+        };
+        // a block without ordinary data gets no constant buffer
+        let resources = Scope {
+            ranges: vec![scope_range(ParameterCategory::UnorderedAccess, 1, 3, 0)],
+            ..Default::default()
+        };
+        let globals = Scope {
+            blocks: vec![
+                (1, element, Some(Scope::default())),
+                (3, resources, Some(Scope::default())),
+            ],
+            ..Default::default()
+        };
+
+        let s = build(&[(&globals, ShaderVisibility::All)]);
+        assert_eq!(
+            s.parameters,
+            [
+                RootParameter::DescriptorTable {
+                    ranges: vec![
+                        range(DescriptorRangeType::Cbv, Some(1), 0, 1),
+                        range(DescriptorRangeType::Cbv, Some(1), 1, 1),
+                        range(DescriptorRangeType::Srv, Some(2), 0, 1),
+                    ],
+                    visibility: ShaderVisibility::All,
+                },
+                RootParameter::DescriptorTable {
+                    ranges: vec![range(DescriptorRangeType::Cbv, Some(1), 0, 2)],
+                    visibility: ShaderVisibility::All,
+                },
+                RootParameter::DescriptorTable {
+                    ranges: vec![range(DescriptorRangeType::Sampler, Some(1), 0, 2)],
+                    visibility: ShaderVisibility::All,
+                },
+                RootParameter::DescriptorTable {
+                    ranges: vec![range(DescriptorRangeType::Uav, Some(1), 3, 3)],
+                    visibility: ShaderVisibility::All,
+                },
+            ]
+        );
+        assert_eq!(
+            s.static_sampler_candidates,
+            [StaticSamplerCandidate {
+                shader_register: 0,
+                register_space: 2,
+                visibility: ShaderVisibility::All,
+            }]
+        );
+    }
+
+    #[test]
+    fn push_constants() {
+        let push_constant = ScopeRange {
+            binding_type: BindingType::PushConstant,
+            ..scope_range(ParameterCategory::ConstantBuffer, 1, 2, 0)
+        };
+        let vertex = Scope {
+            registers: vec![(ParameterCategory::ConstantBuffer, 1, 0)],
+            ranges: vec![
+                scope_range(ParameterCategory::ConstantBuffer, 1, 0, 0),
+                push_constant,
+            ],
+            push_constants: vec![(18, push_constant), (0, push_constant)],
+            ..Default::default()
+        };
+        let element = Scope {
+            push_constants: vec![(64, scope_range(ParameterCategory::ConstantBuffer, 1, 0, 0))],
+            ..Default::default()
+        };
+        let globals = Scope {
+            blocks: vec![(2, element, None)],
+            ..Default::default()
+        };
+
+        let s = build(&[
+            (&globals, ShaderVisibility::All),
+            (&vertex, ShaderVisibility::Vertex),
+        ]);
+        assert_eq!(
+            s.parameters,
+            [
+                RootParameter::Constants {
+                    shader_register: 0,
+                    register_space: 2,
+                    num_32bit_values: 16,
+                    visibility: ShaderVisibility::All,
+                },
+                RootParameter::Constants {
+                    shader_register: 3,
+                    register_space: 0,
+                    num_32bit_values: 5,
+                    visibility: ShaderVisibility::Vertex,
+                },
+                RootParameter::DescriptorTable {
+                    ranges: vec![range(DescriptorRangeType::Cbv, Some(1), 1, 0)],
+                    visibility: ShaderVisibility::Vertex,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,433 @@
+//! Structured view of the diagnostic text Slang reports through its diagnostics blobs.
+//!
+//! Slang emits diagnostics as plain text lines such as
+//!
+//! ```text
+//! shader.slang(12): error 30015: undefined identifier 'foo'.
+//!     return foo;
+//!            ^~~
+//! shader.slang(3): note: see declaration of 'bar'
+//! ```
+//!
+//! [`parse`] turns that text into [`Diagnostic`] records. Formatting a record with [`Display`](core::fmt::Display)
+//! reproduces the original text, and [`Diagnostic::render_annotated`] produces a rustc-style annotated snippet.
+
+use core::fmt::Write as _;
+use std::ffi::CString;
+
+use crate::{IBlob, IFileSystem};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Note,
+    Warning,
+    Error,
+    Fatal,
+    Internal,
+}
+impl Severity {
+    /// The keyword Slang uses for this severity in diagnostic headers.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Note => "note",
+            Self::Warning => "warning",
+            Self::Error => "error",
+            Self::Fatal => "fatal error",
+            Self::Internal => "internal error",
+        }
+    }
+
+    #[inline]
+    pub const fn is_error(self) -> bool {
+        matches!(self, Self::Error | Self::Fatal | Self::Internal)
+    }
+}
+impl core::fmt::Display for Severity {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Diagnostic id (e.g. `30015`)
+    pub code: Option<u32>,
+    pub file: Option<String>,
+    /// 1-based line number
+    pub line: Option<u32>,
+    /// 1-based column number, recovered from the caret line of the snippet
+    pub column: Option<u32>,
+    pub message: String,
+    /// Lines following the header verbatim (typically the source line and a caret line)
+    pub snippet: Vec<String>,
+    /// Notes attached to this diagnostic
+    pub notes: Vec<Diagnostic>,
+}
+impl Diagnostic {
+    /// Parses a single header line (`file(line): severity code: message`, or `severity code: message` for
+    /// diagnostics without a location).
+    pub fn parse_header(line: &str) -> Option<Self> {
+        // a line starting with the severity has no location, whatever its message contains
+        let (file, line_number, (severity, code, message)) = match split_severity(line) {
+            Some(header) => (None, None, header),
+            None => {
+                let (file, line_number, rest) = split_location(line)?;
+                (Some(file), Some(line_number), split_severity(rest)?)
+            }
+        };
+
+        Some(Self {
+            severity,
+            code,
+            file: file.map(ToOwned::to_owned),
+            line: line_number,
+            column: None,
+            message: message.to_owned(),
+            snippet: Vec::new(),
+            notes: Vec::new(),
+        })
+    }
+
+    fn push_snippet_line(&mut self, line: &str) {
+        if self.column.is_none()
+            && let Some(c) = caret_column(line)
+        {
+            self.column = Some(c);
+        }
+
+        self.snippet.push(line.to_owned());
+    }
+
+    /// The source line quoted in the snippet, if Slang printed one.
+    pub fn quoted_source_line(&self) -> Option<&str> {
+        let caret = self
+            .snippet
+            .iter()
+            .position(|l| caret_column(l).is_some())?;
+
+        caret.checked_sub(1).map(|i| self.snippet[i].as_str())
+    }
+
+    /// Renders this diagnostic in a rustc-like annotated form.
+    ///
+    /// `load_source` is asked for the contents of [`file`](Self::file); when it returns `None` the source line quoted
+    /// by Slang itself is used instead.
+    pub fn render_annotated(&self, mut load_source: impl FnMut(&str) -> Option<String>) -> String {
+        let mut out = String::new();
+        self.write_annotated(&mut out, &mut load_source);
+
+        out
+    }
+
+    fn write_annotated(
+        &self,
+        out: &mut String,
+        load_source: &mut dyn FnMut(&str) -> Option<String>,
+    ) {
+        match self.code {
+            Some(c) => {
+                let _ = writeln!(out, "{}[{c}]: {}", self.severity, self.message);
+            }
+            None => {
+                let _ = writeln!(out, "{}: {}", self.severity, self.message);
+            }
+        }
+
+        if let (Some(file), Some(line)) = (self.file.as_deref(), self.line) {
+            let source_line = load_source(file)
+                .and_then(|s| {
+                    s.lines()
+                        .nth(line.saturating_sub(1) as usize)
+                        .map(ToOwned::to_owned)
+                })
+                .or_else(|| self.quoted_source_line().map(ToOwned::to_owned));
+            let line_label = line.to_string();
+            let gutter = " ".repeat(line_label.len());
+
+            match self.column {
+                Some(c) => {
+                    let _ = writeln!(out, "{gutter}--> {file}:{line}:{c}");
+                }
+                None => {
+                    let _ = writeln!(out, "{gutter}--> {file}:{line}");
+                }
+            }
+            if let Some(src) = source_line {
+                let column = self.column.map_or_else(
+                    || src.chars().take_while(|c| c.is_whitespace()).count(),
+                    |c| c.saturating_sub(1) as usize,
+                );
+                let column_offset = src.char_indices().nth(column).map_or(src.len(), |(i, _)| i);
+                let underline_len = src[column_offset..]
+                    .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+                    .next()
+                    .map_or(1, |w| w.chars().count().max(1));
+
+                let _ = writeln!(out, "{gutter} |");
+                let _ = writeln!(out, "{line_label} | {src}");
+                let _ = writeln!(
+                    out,
+                    "{gutter} | {}{}",
+                    " ".repeat(column),
+                    "^".repeat(underline_len)
+                );
+            }
+        } else if let Some(file) = self.file.as_deref() {
+            let _ = writeln!(out, " --> {file}");
+        }
+
+        for n in &self.notes {
+            if n.file.is_some() {
+                n.write_annotated(out, load_source);
+            } else {
+                let _ = writeln!(out, "  = {}: {}", n.severity, n.message);
+            }
+        }
+    }
+}
+impl core::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match (self.file.as_deref(), self.line) {
+            (Some(file), Some(line)) => write!(f, "{file}({line}): ")?,
+            (Some(file), None) => write!(f, "{file}: ")?,
+            _ => (),
+        }
+        f.write_str(self.severity.as_str())?;
+        if let Some(c) = self.code {
+            write!(f, " {c}")?;
+        }
+        write!(f, ": {}", self.message)?;
+        for l in &self.snippet {
+            write!(f, "\n{l}")?;
+        }
+        for n in &self.notes {
+            write!(f, "\n{n}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses the whole diagnostic output of a call into records.
+///
+/// Notes are attached to the diagnostic preceding them. Lines that precede the first recognised header are ignored.
+pub fn parse(text: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::<Diagnostic>::new();
+
+    for line in text.lines() {
+        match Diagnostic::parse_header(line) {
+            Some(d) if d.severity == Severity::Note && !diagnostics.is_empty() => {
+                diagnostics.last_mut().unwrap().notes.push(d);
+            }
+            Some(d) => diagnostics.push(d),
+            None => {
+                let Some(last) = diagnostics.last_mut() else {
+                    continue;
+                };
+
+                match last.notes.last_mut() {
+                    Some(n) => n.push_snippet_line(line),
+                    None => last.push_snippet_line(line),
+                }
+            }
+        }
+    }
+
+    diagnostics
+}
+
+/// Parses the text held by a diagnostics blob.
+#[inline]
+pub fn parse_blob(blob: &impl IBlob) -> Vec<Diagnostic> {
+    parse(&crate::blob_to_string(blob))
+}
+
+/// Reproduces the original diagnostic text from parsed records.
+pub fn render(diagnostics: &[Diagnostic]) -> String {
+    let mut out = String::new();
+    for d in diagnostics {
+        let _ = writeln!(out, "{d}");
+    }
+
+    out
+}
+
+/// Renders every record in the rustc-like annotated form (see [`Diagnostic::render_annotated`]).
+pub fn render_annotated(
+    diagnostics: &[Diagnostic],
+    mut load_source: impl FnMut(&str) -> Option<String>,
+) -> String {
+    let mut out = String::new();
+    for d in diagnostics {
+        d.write_annotated(&mut out, &mut load_source);
+        out.push('\n');
+    }
+
+    out
+}
+
+/// A source loader for the annotated renderer that reads files through a Slang file system
+/// (typically the one given to the session).
+pub fn file_system_source_loader(
+    file_system: &impl IFileSystem,
+) -> impl FnMut(&str) -> Option<String> + '_ {
+    move |path| {
+        let path = CString::new(path).ok()?;
+        let blob = file_system.load_file(&path).ok()?;
+
+        Some(crate::blob_to_string(&blob))
+    }
+}
+
+/// Splits `severity code: message` (the part of a header after the location).
+fn split_severity(rest: &str) -> Option<(Severity, Option<u32>, &str)> {
+    let (severity, rest) = [
+        Severity::Internal,
+        Severity::Fatal,
+        Severity::Error,
+        Severity::Warning,
+        Severity::Note,
+    ]
+    .into_iter()
+    .find_map(|s| rest.strip_prefix(s.as_str()).map(|r| (s, r)))?;
+    let (code, rest) = match rest.strip_prefix(' ') {
+        Some(r) => {
+            let digits = r.bytes().take_while(u8::is_ascii_digit).count();
+            if digits == 0 {
+                return None;
+            }
+
+            (Some(r[..digits].parse().ok()?), &r[digits..])
+        }
+        None => (None, rest),
+    };
+    let message = rest.strip_prefix(':')?;
+    let message = message.strip_prefix(' ').unwrap_or(message);
+
+    Some((severity, code, message))
+}
+
+/// Splits `file(line): rest` (the location prefix of a header).
+///
+/// The location is the first `(line): ` followed by a severity, so a location-like text in the message (e.g. a call
+/// `f(1): ` quoted by the message) is not mistaken for it.
+fn split_location(line: &str) -> Option<(&str, u32, &str)> {
+    let mut search_from = 0;
+    while let Some(p) = line[search_from..].find("): ") {
+        let close = search_from + p;
+        let rest = &line[close + 3..];
+        if let Some(open) = line[..close].rfind('(') {
+            let number = &line[open + 1..close];
+            if !number.is_empty()
+                && number.bytes().all(|c| c.is_ascii_digit())
+                && let Ok(n) = number.parse()
+                && split_severity(rest).is_some()
+            {
+                return Some((&line[..open], n, rest));
+            }
+        }
+
+        search_from = close + 3;
+    }
+
+    None
+}
+
+/// 1-based column of the caret in a caret line (`    ^~~~`).
+fn caret_column(line: &str) -> Option<u32> {
+    let trimmed = line.trim_end();
+    let marker = trimmed.trim_start();
+    if !marker.starts_with('^') || !marker.chars().all(|c| c == '^' || c == '~') {
+        return None;
+    }
+
+    Some((trimmed.chars().count() - marker.chars().count()) as u32 + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "\
+shader.slang(12): error 30015: undefined identifier 'foo'.
+    return foo;
+           ^~~
+shader.slang(3): note: see declaration of 'bar'
+dir (1)/lib.slang(40): warning 15205: implicit conversion
+    float x = y;
+        ^
+error 1: could not open 'a(2): b.slang'
+internal error: something broke";
+
+    #[test]
+    fn header_with_location() {
+        let d =
+            Diagnostic::parse_header("shader.slang(12): error 30015: undefined identifier 'foo'.")
+                .unwrap();
+
+        assert_eq!(d.severity, Severity::Error);
+        assert_eq!(d.code, Some(30015));
+        assert_eq!(d.file.as_deref(), Some("shader.slang"));
+        assert_eq!(d.line, Some(12));
+        assert_eq!(d.message, "undefined identifier 'foo'.");
+    }
+
+    #[test]
+    fn header_without_location() {
+        let d = Diagnostic::parse_header("fatal error: cannot open file 'x.slang'").unwrap();
+
+        assert_eq!(d.severity, Severity::Fatal);
+        assert_eq!(d.code, None);
+        assert_eq!(d.file, None);
+        assert_eq!(d.line, None);
+        assert_eq!(d.message, "cannot open file 'x.slang'");
+    }
+
+    #[test]
+    fn location_in_message() {
+        // no location: the `(2): ` belongs to the message
+        let d = Diagnostic::parse_header("error 1: could not open 'a(2): b.slang'").unwrap();
+        assert_eq!(d.file, None);
+        assert_eq!(d.message, "could not open 'a(2): b.slang'");
+
+        // the location is the first one followed by a severity
+        let d = Diagnostic::parse_header("f(1): x.slang(7): warning 2: y(3): z").unwrap();
+        assert_eq!(d.file.as_deref(), Some("f(1): x.slang"));
+        assert_eq!(d.line, Some(7));
+        assert_eq!(d.message, "y(3): z");
+
+        assert!(Diagnostic::parse_header("call(1): not a header").is_none());
+        assert!(Diagnostic::parse_header("    return foo;").is_none());
+    }
+
+    #[test]
+    fn parse_text() {
+        let diagnostics = parse(TEXT);
+        assert_eq!(diagnostics.len(), 4);
+
+        let d = &diagnostics[0];
+        assert_eq!(d.column, Some(12));
+        assert_eq!(d.snippet, ["    return foo;", "           ^~~"]);
+        assert_eq!(d.quoted_source_line(), Some("    return foo;"));
+        assert_eq!(d.notes.len(), 1);
+        assert_eq!(d.notes[0].severity, Severity::Note);
+        assert_eq!(d.notes[0].line, Some(3));
+
+        let d = &diagnostics[1];
+        assert_eq!(d.severity, Severity::Warning);
+        assert_eq!(d.file.as_deref(), Some("dir (1)/lib.slang"));
+        assert_eq!(d.column, Some(9));
+
+        assert_eq!(diagnostics[2].file, None);
+        assert_eq!(diagnostics[3].severity, Severity::Internal);
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let diagnostics = parse(TEXT);
+
+        assert_eq!(render(&diagnostics), format!("{TEXT}\n"));
+        assert_eq!(parse(&render(&diagnostics)), diagnostics);
+    }
+}
@@ -14,6 +14,68 @@ pub type SlangBool = bool;
 
 pub type SlangResult = i32;
 
+/// Facility area of a [`SlangResult`] (bits 16..31, excluding the severity bit)
+pub type SlangResultFacility = u32;
+pub const SLANG_FACILITY_WIN_GENERAL: SlangResultFacility = 0;
+pub const SLANG_FACILITY_WIN_INTERFACE: SlangResultFacility = 4;
+pub const SLANG_FACILITY_WIN_API: SlangResultFacility = 7;
+pub const SLANG_FACILITY_BASE: SlangResultFacility = 0x200;
+/// Base facility -> so as to not clash with HRESULT values (which can be in 0x200 range)
+pub const SLANG_FACILITY_CORE: SlangResultFacility = SLANG_FACILITY_BASE;
+/// Facility for codes, that are not uniquely defined/protected. Can be used to pass back a specific error without
+/// requiring system wide facility uniqueness. Codes should never be part of a public API.
+pub const SLANG_FACILITY_INTERNAL: SlangResultFacility = SLANG_FACILITY_BASE + 1;
+/// Base for external facilities. Facilities should be unique across modules.
+pub const SLANG_FACILITY_EXTERNAL_BASE: SlangResultFacility = 0x210;
+
+pub const fn slang_make_error(facility: SlangResultFacility, code: u32) -> SlangResult {
+    ((facility << 16) | code | 0x8000_0000) as SlangResult
+}
+pub const fn slang_make_success(facility: SlangResultFacility, code: u32) -> SlangResult {
+    ((facility << 16) | code) as SlangResult
+}
+pub const fn slang_get_result_facility(r: SlangResult) -> SlangResultFacility {
+    ((r as u32) >> 16) & 0x7fff
+}
+pub const fn slang_get_result_code(r: SlangResult) -> u32 {
+    (r as u32) & 0xffff
+}
+
+pub const SLANG_OK: SlangResult = 0;
+pub const SLANG_FAIL: SlangResult = slang_make_error(SLANG_FACILITY_WIN_GENERAL, 0x4005);
+/// Functionality is not implemented
+pub const SLANG_E_NOT_IMPLEMENTED: SlangResult =
+    slang_make_error(SLANG_FACILITY_WIN_GENERAL, 0x4001);
+/// Interface not be found
+pub const SLANG_E_NO_INTERFACE: SlangResult = slang_make_error(SLANG_FACILITY_WIN_GENERAL, 0x4002);
+/// Operation was aborted (did not correctly complete)
+pub const SLANG_E_ABORT: SlangResult = slang_make_error(SLANG_FACILITY_WIN_GENERAL, 0x4004);
+/// Indicates that a handle passed in as parameter to a method is invalid.
+pub const SLANG_E_INVALID_HANDLE: SlangResult = slang_make_error(SLANG_FACILITY_WIN_API, 6);
+/// Indicates that an argument passed in as parameter to a method is invalid.
+pub const SLANG_E_INVALID_ARG: SlangResult = slang_make_error(SLANG_FACILITY_WIN_API, 0x57);
+/// Operation could not complete - ran out of memory
+pub const SLANG_E_OUT_OF_MEMORY: SlangResult = slang_make_error(SLANG_FACILITY_WIN_API, 0xe);
+/// Supplied buffer is too small to be able to complete
+pub const SLANG_E_BUFFER_TOO_SMALL: SlangResult = slang_make_error(SLANG_FACILITY_CORE, 1);
+/// Used to identify a Result that has yet to be initialized.
+/// It defaults to failure such that if used incorrectly will fail, as similar in concept to using an uninitialized
+/// variable.
+pub const SLANG_E_UNINITIALIZED: SlangResult = slang_make_error(SLANG_FACILITY_CORE, 2);
+/// Returned from an async method meaning the output is invalid (thus an error), but a result for the request is
+/// pending, and will be returned on a subsequent call with the async handle.
+pub const SLANG_E_PENDING: SlangResult = slang_make_error(SLANG_FACILITY_CORE, 3);
+/// Indicates a file/resource could not be opened
+pub const SLANG_E_CANNOT_OPEN: SlangResult = slang_make_error(SLANG_FACILITY_CORE, 4);
+/// Indicates a file/resource could not be found
+pub const SLANG_E_NOT_FOUND: SlangResult = slang_make_error(SLANG_FACILITY_CORE, 5);
+/// An unhandled internal failure (typically from unhandled exception)
+pub const SLANG_E_INTERNAL_FAIL: SlangResult = slang_make_error(SLANG_FACILITY_CORE, 6);
+/// Could not complete because some underlying feature (hardware or software) was not available
+pub const SLANG_E_NOT_AVAILABLE: SlangResult = slang_make_error(SLANG_FACILITY_CORE, 7);
+/// Could not complete because the operation times out.
+pub const SLANG_E_TIME_OUT: SlangResult = slang_make_error(SLANG_FACILITY_CORE, 8);
+
 pub type SlangFuncPtr = extern "C" fn();
 
 /// Type that identifies how a path should be interpreted
@@ -23,6 +85,26 @@ pub const SLANG_PATH_TYPE_DIRECTORY: SlangPathType = 0;
 /// Path specified is to a file.
 pub const SLANG_PATH_TYPE_FILE: SlangPathType = 1;
 
+/// Kinds of path that can be requested from `ISlangFileSystemExt::getPath`.
+pub type SlangPathKind = c_uint;
+/// Given a path, returns a simplified version of that path (typically removing '..' and/or '.').
+pub const SLANG_PATH_KIND_SIMPLIFIED: SlangPathKind = 0;
+/// Given a path, returns a 'canonical path' to the item (may be the operating system 'canonical path').
+pub const SLANG_PATH_KIND_CANONICAL: SlangPathKind = 1;
+/// Given a path, returns the operating system path to the item (may be unavailable).
+pub const SLANG_PATH_KIND_OPERATING_SYSTEM: SlangPathKind = 2;
+/// Given a path, returns a path suitable for display to the user (e.g. in diagnostics).
+pub const SLANG_PATH_KIND_DISPLAY: SlangPathKind = 3;
+
+/// How the paths of a file system relate to the operating system's.
+pub type SlangOSPathKind = u8;
+/// Paths do not map to the file system.
+pub const SLANG_OS_PATH_KIND_NONE: SlangOSPathKind = 0;
+/// Paths map directly to the file system.
+pub const SLANG_OS_PATH_KIND_DIRECT: SlangOSPathKind = 1;
+/// Only paths gained via `getPath` with `SLANG_PATH_KIND_OPERATING_SYSTEM` map to the operating system file system.
+pub const SLANG_OS_PATH_KIND_OPERATING_SYSTEM: SlangOSPathKind = 2;
+
 pub type FileSystemContentsCallBack =
     extern "C" fn(path_type: SlangPathType, name: *const c_char, user_data: *mut c_void);
 
@@ -118,8 +200,45 @@ pub const SLANG_SCALAR_TYPE_UINTPT: SlangScalarType = 15;
 pub type SlangDeclKind = c_uint;
 
 pub type SlangResourceShape = c_uint;
+pub const SLANG_RESOURCE_BASE_SHAPE_MASK: SlangResourceShape = 0x0F;
+pub const SLANG_RESOURCE_NONE: SlangResourceShape = 0x00;
+pub const SLANG_TEXTURE_1D: SlangResourceShape = 0x01;
+pub const SLANG_TEXTURE_2D: SlangResourceShape = 0x02;
+pub const SLANG_TEXTURE_3D: SlangResourceShape = 0x03;
+pub const SLANG_TEXTURE_CUBE: SlangResourceShape = 0x04;
+pub const SLANG_TEXTURE_BUFFER: SlangResourceShape = 0x05;
+pub const SLANG_STRUCTURED_BUFFER: SlangResourceShape = 0x06;
+pub const SLANG_BYTE_ADDRESS_BUFFER: SlangResourceShape = 0x07;
+pub const SLANG_RESOURCE_UNKNOWN: SlangResourceShape = 0x08;
+pub const SLANG_ACCELERATION_STRUCTURE: SlangResourceShape = 0x09;
+pub const SLANG_TEXTURE_SUBPASS: SlangResourceShape = 0x0A;
+pub const SLANG_RESOURCE_EXT_SHAPE_MASK: SlangResourceShape = 0x1F0;
+pub const SLANG_TEXTURE_FEEDBACK_FLAG: SlangResourceShape = 0x10;
+pub const SLANG_TEXTURE_SHADOW_FLAG: SlangResourceShape = 0x20;
+pub const SLANG_TEXTURE_ARRAY_FLAG: SlangResourceShape = 0x40;
+pub const SLANG_TEXTURE_MULTISAMPLE_FLAG: SlangResourceShape = 0x80;
+pub const SLANG_TEXTURE_COMBINED_FLAG: SlangResourceShape = 0x100;
+pub const SLANG_TEXTURE_1D_ARRAY: SlangResourceShape = SLANG_TEXTURE_1D | SLANG_TEXTURE_ARRAY_FLAG;
+pub const SLANG_TEXTURE_2D_ARRAY: SlangResourceShape = SLANG_TEXTURE_2D | SLANG_TEXTURE_ARRAY_FLAG;
+pub const SLANG_TEXTURE_CUBE_ARRAY: SlangResourceShape =
+    SLANG_TEXTURE_CUBE | SLANG_TEXTURE_ARRAY_FLAG;
+pub const SLANG_TEXTURE_2D_MULTISAMPLE: SlangResourceShape =
+    SLANG_TEXTURE_2D | SLANG_TEXTURE_MULTISAMPLE_FLAG;
+pub const SLANG_TEXTURE_2D_MULTISAMPLE_ARRAY: SlangResourceShape =
+    SLANG_TEXTURE_2D | SLANG_TEXTURE_MULTISAMPLE_FLAG | SLANG_TEXTURE_ARRAY_FLAG;
+pub const SLANG_TEXTURE_SUBPASS_MULTISAMPLE: SlangResourceShape =
+    SLANG_TEXTURE_SUBPASS | SLANG_TEXTURE_MULTISAMPLE_FLAG;
 
 pub type SlangResourceAccess = c_uint;
+pub const SLANG_RESOURCE_ACCESS_NONE: SlangResourceAccess = 0;
+pub const SLANG_RESOURCE_ACCESS_READ: SlangResourceAccess = 1;
+pub const SLANG_RESOURCE_ACCESS_READ_WRITE: SlangResourceAccess = 2;
+pub const SLANG_RESOURCE_ACCESS_RASTER_ORDERED: SlangResourceAccess = 3;
+pub const SLANG_RESOURCE_ACCESS_APPEND: SlangResourceAccess = 4;
+pub const SLANG_RESOURCE_ACCESS_CONSUME: SlangResourceAccess = 5;
+pub const SLANG_RESOURCE_ACCESS_WRITE: SlangResourceAccess = 6;
+pub const SLANG_RESOURCE_ACCESS_FEEDBACK: SlangResourceAccess = 7;
+pub const SLANG_RESOURCE_ACCESS_UNKNOWN: SlangResourceAccess = 0x7FFFFFFF;
 
 pub type SlangParameterCategory = c_uint;
 pub const SLANG_PARAMETER_CATEGORY_NONE: SlangParameterCategory = 0;
@@ -194,6 +313,49 @@ pub const SLANG_LAYOUT_RULES_METAL_ARGUMENT_BUFFER_TIER_2: SlangLayoutRules = 1;
 pub type SlangModifierID = u32;
 
 pub type SlangImageFormat = u32;
+pub const SLANG_IMAGE_FORMAT_UNKNOWN: SlangImageFormat = 0;
+pub const SLANG_IMAGE_FORMAT_RGBA32F: SlangImageFormat = 1;
+pub const SLANG_IMAGE_FORMAT_RGBA16F: SlangImageFormat = 2;
+pub const SLANG_IMAGE_FORMAT_RG32F: SlangImageFormat = 3;
+pub const SLANG_IMAGE_FORMAT_RG16F: SlangImageFormat = 4;
+pub const SLANG_IMAGE_FORMAT_R11F_G11F_B10F: SlangImageFormat = 5;
+pub const SLANG_IMAGE_FORMAT_R32F: SlangImageFormat = 6;
+pub const SLANG_IMAGE_FORMAT_R16F: SlangImageFormat = 7;
+pub const SLANG_IMAGE_FORMAT_RGBA16: SlangImageFormat = 8;
+pub const SLANG_IMAGE_FORMAT_RGB10_A2: SlangImageFormat = 9;
+pub const SLANG_IMAGE_FORMAT_RGBA8: SlangImageFormat = 10;
+pub const SLANG_IMAGE_FORMAT_RG16: SlangImageFormat = 11;
+pub const SLANG_IMAGE_FORMAT_RG8: SlangImageFormat = 12;
+pub const SLANG_IMAGE_FORMAT_R16: SlangImageFormat = 13;
+pub const SLANG_IMAGE_FORMAT_R8: SlangImageFormat = 14;
+pub const SLANG_IMAGE_FORMAT_RGBA16_SNORM: SlangImageFormat = 15;
+pub const SLANG_IMAGE_FORMAT_RGBA8_SNORM: SlangImageFormat = 16;
+pub const SLANG_IMAGE_FORMAT_RG16_SNORM: SlangImageFormat = 17;
+pub const SLANG_IMAGE_FORMAT_RG8_SNORM: SlangImageFormat = 18;
+pub const SLANG_IMAGE_FORMAT_R16_SNORM: SlangImageFormat = 19;
+pub const SLANG_IMAGE_FORMAT_R8_SNORM: SlangImageFormat = 20;
+pub const SLANG_IMAGE_FORMAT_RGBA32I: SlangImageFormat = 21;
+pub const SLANG_IMAGE_FORMAT_RGBA16I: SlangImageFormat = 22;
+pub const SLANG_IMAGE_FORMAT_RGBA8I: SlangImageFormat = 23;
+pub const SLANG_IMAGE_FORMAT_RG32I: SlangImageFormat = 24;
+pub const SLANG_IMAGE_FORMAT_RG16I: SlangImageFormat = 25;
+pub const SLANG_IMAGE_FORMAT_RG8I: SlangImageFormat = 26;
+pub const SLANG_IMAGE_FORMAT_R32I: SlangImageFormat = 27;
+pub const SLANG_IMAGE_FORMAT_R16I: SlangImageFormat = 28;
+pub const SLANG_IMAGE_FORMAT_R8I: SlangImageFormat = 29;
+pub const SLANG_IMAGE_FORMAT_RGBA32UI: SlangImageFormat = 30;
+pub const SLANG_IMAGE_FORMAT_RGBA16UI: SlangImageFormat = 31;
+pub const SLANG_IMAGE_FORMAT_RGB10_A2UI: SlangImageFormat = 32;
+pub const SLANG_IMAGE_FORMAT_RGBA8UI: SlangImageFormat = 33;
+pub const SLANG_IMAGE_FORMAT_RG32UI: SlangImageFormat = 34;
+pub const SLANG_IMAGE_FORMAT_RG16UI: SlangImageFormat = 35;
+pub const SLANG_IMAGE_FORMAT_RG8UI: SlangImageFormat = 36;
+pub const SLANG_IMAGE_FORMAT_R32UI: SlangImageFormat = 37;
+pub const SLANG_IMAGE_FORMAT_R16UI: SlangImageFormat = 38;
+pub const SLANG_IMAGE_FORMAT_R8UI: SlangImageFormat = 39;
+pub const SLANG_IMAGE_FORMAT_R64UI: SlangImageFormat = 40;
+pub const SLANG_IMAGE_FORMAT_R64I: SlangImageFormat = 41;
+pub const SLANG_IMAGE_FORMAT_BGRA8: SlangImageFormat = 42;
 
 pub const SLANG_UNORDERED_SIZE: usize = !0;
 
@@ -274,6 +436,49 @@ pub const SLANG_LINE_DIRECTIVE_MODE_GLSL: SlangLineDirectiveMode = 3;
 /// Use a source map to track line mappings (ie no #line will appear in emitting source)
 pub const SLANG_LINE_DIRECTIVE_MODE_SOURCE_MAP: SlangLineDirectiveMode = 4;
 
+/// Options to control the level of optimization performed by downstream compilers.
+pub type SlangOptimizationLevel = core::ffi::c_uint;
+/// Don't optimize at all.
+pub const SLANG_OPTIMIZATION_LEVEL_NONE: SlangOptimizationLevel = 0;
+/// Default optimization level: balance code quality and compilation time.
+pub const SLANG_OPTIMIZATION_LEVEL_DEFAULT: SlangOptimizationLevel = 1;
+/// Optimize aggressively.
+pub const SLANG_OPTIMIZATION_LEVEL_HIGH: SlangOptimizationLevel = 2;
+/// Include optimizations that may take a very long time, or may involve severe space-vs-speed tradeoffs.
+pub const SLANG_OPTIMIZATION_LEVEL_MAXIMAL: SlangOptimizationLevel = 3;
+
+/// Options to control the amount of debug information emitted.
+pub type SlangDebugInfoLevel = core::ffi::c_uint;
+/// Don't emit debug information at all.
+pub const SLANG_DEBUG_INFO_LEVEL_NONE: SlangDebugInfoLevel = 0;
+/// Emit as little debug information as possible, while still supporting stack traces.
+pub const SLANG_DEBUG_INFO_LEVEL_MINIMAL: SlangDebugInfoLevel = 1;
+/// Emit whatever is the standard level of debug information for each target.
+pub const SLANG_DEBUG_INFO_LEVEL_STANDARD: SlangDebugInfoLevel = 2;
+/// Emit as much debug information as possible for each target.
+pub const SLANG_DEBUG_INFO_LEVEL_MAXIMAL: SlangDebugInfoLevel = 3;
+
+/// Options to select the format of emitted debug information.
+pub type SlangDebugInfoFormat = core::ffi::c_uint;
+/// Use the default debugging format for the target.
+pub const SLANG_DEBUG_INFO_FORMAT_DEFAULT: SlangDebugInfoFormat = 0;
+/// CodeView C7 format (typically means debugging information is embedded in the binary).
+pub const SLANG_DEBUG_INFO_FORMAT_C7: SlangDebugInfoFormat = 1;
+/// Program database.
+pub const SLANG_DEBUG_INFO_FORMAT_PDB: SlangDebugInfoFormat = 2;
+/// Stabs.
+pub const SLANG_DEBUG_INFO_FORMAT_STABS: SlangDebugInfoFormat = 3;
+/// COFF debug.
+pub const SLANG_DEBUG_INFO_FORMAT_COFF: SlangDebugInfoFormat = 4;
+/// DWARF debug info (may vary across DWARF versions).
+pub const SLANG_DEBUG_INFO_FORMAT_DWARF: SlangDebugInfoFormat = 5;
+
+/// Options to select how SPIR-V is produced.
+pub type SlangEmitSpirvMethod = core::ffi::c_int;
+pub const SLANG_EMIT_SPIRV_DEFAULT: SlangEmitSpirvMethod = 0;
+pub const SLANG_EMIT_SPIRV_VIA_GLSL: SlangEmitSpirvMethod = 1;
+pub const SLANG_EMIT_SPIRV_DIRECTLY: SlangEmitSpirvMethod = 2;
+
 pub type SlangSourceLanguage = core::ffi::c_int;
 pub const SLANG_SOURCE_LANGUAGE_UNKNOWN: SlangSourceLanguage = 0;
 pub const SLANG_SOURCE_LANGUAGE_SLANG: SlangSourceLanguage = 1;
@@ -441,8 +646,200 @@ impl Default for SlangGlobalSessionDesc {
     }
 }
 
-unsafe extern "C" {
+/// Declares the C entry points of the Slang library.
+///
+/// By default they are linked at build time. With the `dynamic-loading` feature, functions with the same
+/// signatures are generated instead, forwarding to symbols resolved by [`load_library`]. Every symbol must then be
+/// exported by the library for [`load_library`] to succeed, except for those marked `#[optional]`, which panic when
+/// called if missing.
+macro_rules! slang_api {
+    (@optional) => { false };
+    (@optional optional) => { true };
+    ($($(#[$optional: ident])? pub fn $name: ident($($arg: ident: $ty: ty),* $(,)?) $(-> $ret: ty)?;)*) => {
+        #[cfg(not(feature = "dynamic-loading"))]
+        unsafe extern "C" {
+            $(pub fn $name($($arg: $ty),*) $(-> $ret)?;)*
+        }
+
+        /// Entry points resolved from a library loaded at runtime. Symbols missing from the library are `None`.
+        #[cfg(feature = "dynamic-loading")]
+        #[allow(non_snake_case)]
+        struct SlangApi {
+            path: std::path::PathBuf,
+            $($name: Option<unsafe extern "C" fn($($ty),*) $(-> $ret)?>,)*
+            _library: libloading::Library,
+        }
+        #[cfg(feature = "dynamic-loading")]
+        impl SlangApi {
+            /// # Safety
+            /// `library` must be a Slang library: the resolved symbols are assumed to have the declared signatures.
+            unsafe fn resolve(library: libloading::Library, path: std::path::PathBuf) -> Self {
+                Self {
+                    path,
+                    $($name: unsafe {
+                        library
+                            .get::<unsafe extern "C" fn($($ty),*) $(-> $ret)?>(
+                                concat!(stringify!($name), "\0").as_bytes(),
+                            )
+                            .ok()
+                            .map(|s| *s)
+                    },)*
+                    _library: library,
+                }
+            }
+
+            /// Names of the symbols the library does not export, and whether each is optional.
+            fn missing_symbols(&self) -> Vec<(&'static str, bool)> {
+                let mut names = Vec::new();
+                $(if self.$name.is_none() {
+                    names.push((stringify!($name), slang_api!(@optional $($optional)?)));
+                })*
+
+                names
+            }
+        }
+
+        $(
+            #[cfg(feature = "dynamic-loading")]
+            #[doc = concat!("Calls `", stringify!($name), "` from the library loaded with [`load_library`].")]
+            ///
+            /// # Safety
+            /// Same contract as the C function.
+            ///
+            /// # Panics
+            /// Panics if no library has been loaded yet, or for an optional symbol the loaded library does not
+            /// export.
+            #[allow(non_snake_case)]
+            #[inline]
+            pub unsafe fn $name($($arg: $ty),*) $(-> $ret)? {
+                let api = loaded_api();
+                let Some(f) = api.$name else {
+                    missing_symbol(stringify!($name), &api.path)
+                };
+
+                unsafe { f($($arg),*) }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "dynamic-loading")]
+static SLANG_API: std::sync::OnceLock<SlangApi> = std::sync::OnceLock::new();
+
+#[cfg(feature = "dynamic-loading")]
+#[inline]
+fn loaded_api() -> &'static SlangApi {
+    SLANG_API
+        .get()
+        .expect("the Slang library has not been loaded (call `slang::load_library` first)")
+}
+
+#[cfg(feature = "dynamic-loading")]
+#[cold]
+#[inline(never)]
+fn missing_symbol(name: &str, path: &std::path::Path) -> ! {
+    panic!(
+        "`{name}` is not exported by the Slang library loaded from {} (the library may be too old)",
+        path.display()
+    )
+}
+
+/// Failure of [`load_library`].
+#[cfg(feature = "dynamic-loading")]
+#[derive(Debug)]
+pub enum LoadLibraryError {
+    /// The library could not be opened.
+    Open(libloading::Error),
+    /// The library does not export these (non-optional) symbols, e.g. because it is too old.
+    MissingSymbols(Vec<&'static str>),
+    /// A library has already been loaded (from the given path).
+    AlreadyLoaded(std::path::PathBuf),
+}
+#[cfg(feature = "dynamic-loading")]
+impl core::fmt::Display for LoadLibraryError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Open(e) => write!(f, "failed to open the Slang library: {e}"),
+            Self::MissingSymbols(names) => write!(
+                f,
+                "the Slang library does not export {}",
+                names
+                    .iter()
+                    .map(|n| format!("`{n}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Self::AlreadyLoaded(path) => write!(
+                f,
+                "a Slang library has already been loaded from {}",
+                path.display()
+            ),
+        }
+    }
+}
+#[cfg(feature = "dynamic-loading")]
+impl std::error::Error for LoadLibraryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Open(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Loads the Slang library at `path` (e.g. `libslang-compiler.so`, `slang.dll`) and resolves its entry points.
+///
+/// This must be done once per process before any other API of this crate is used; the library stays loaded
+/// until the process exits. Loading fails if the library does not export every entry point of this crate, except for
+/// the optional ones (the deprecated compile-request writer functions), which panic when called if missing; see
+/// [`missing_symbols`].
+#[cfg(feature = "dynamic-loading")]
+pub fn load_library(path: impl AsRef<std::ffi::OsStr>) -> Result<(), LoadLibraryError> {
+    if let Some(api) = SLANG_API.get() {
+        return Err(LoadLibraryError::AlreadyLoaded(api.path.clone()));
+    }
+
+    let path = path.as_ref();
+    let library = unsafe { libloading::Library::new(path) }.map_err(LoadLibraryError::Open)?;
+    let api = unsafe { SlangApi::resolve(library, path.into()) };
+    let required: Vec<_> = api
+        .missing_symbols()
+        .into_iter()
+        .filter_map(|(name, optional)| (!optional).then_some(name))
+        .collect();
+    if !required.is_empty() {
+        return Err(LoadLibraryError::MissingSymbols(required));
+    }
+
+    SLANG_API
+        .set(api)
+        .map_err(|_| LoadLibraryError::AlreadyLoaded(loaded_api().path.clone()))
+}
+
+/// Names of the optional entry points the loaded library does not export (empty if no library has been loaded).
+#[cfg(feature = "dynamic-loading")]
+pub fn missing_symbols() -> Vec<&'static str> {
+    SLANG_API.get().map_or_else(Vec::new, |api| {
+        api.missing_symbols()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect()
+    })
+}
+
+slang_api! {
     pub fn spGetBuildTagString() -> *const c_char;
+    #[optional]
+    pub fn spSetWriter(
+        request: *mut c_void, /* SlangCompileRequest */
+        channel: SlangWriterChannel,
+        writer: *mut c_void, /* ISlangWriter */
+    );
+    #[optional]
+    pub fn spGetWriter(
+        request: *mut c_void, /* SlangCompileRequest */
+        channel: SlangWriterChannel,
+    ) -> *mut c_void /* ISlangWriter */;
     pub fn slang_createGlobalSession2(
         desc: *const SlangGlobalSessionDesc,
         out_global_session: *mut *mut c_void,
@@ -831,7 +1228,7 @@ unsafe extern "C" {
         value_param: *mut SlangReflectionVariable,
     ) -> i64;
     pub fn spReflectionGeneric_applySpecializations(
-        generic: *mut SlangReflectionGeneric,
+        curr_generic: *mut SlangReflectionGeneric,
         generic: *mut SlangReflectionGeneric,
     ) -> *mut SlangReflectionGeneric;
 
@@ -0,0 +1,1012 @@
+//! File systems implemented in Rust, handed to Slang as an `ISlangFileSystem`, `ISlangFileSystemExt` or
+//! `ISlangMutableFileSystem`.
+
+use core::{
+    ffi::{CStr, c_char, c_void},
+    mem::ManuallyDrop,
+};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    ffi::CString,
+    sync::{Arc, PoisonError, RwLock},
+};
+
+use crate::{
+    CASTABLE_IID, FILE_SYSTEM_EXT_IID, FILE_SYSTEM_IID, IBlobPtr, ICastableVTable,
+    IFileSystemExtPtr, IFileSystemExtVTable, IFileSystemPtr, IFileSystemVTable,
+    IMutableFileSystemPtr, IMutableFileSystemVTable, IUnknownVTable, MUTABLE_FILE_SYSTEM_IID,
+    OSPathKind, PathKind, PathType, Result, SlangError, SlangInterfacePtr, SlangUUID, UNKNOWN_IID,
+    blob::{new_blob, new_string_blob},
+    com::ComObject,
+    ffi::{
+        FileSystemContentsCallBack, SLANG_FAIL, SLANG_OK, SLANG_OS_PATH_KIND_NONE,
+        SLANG_PATH_KIND_OPERATING_SYSTEM, SLANG_PATH_TYPE_DIRECTORY, SLANG_PATH_TYPE_FILE,
+        SlangResult,
+    },
+};
+
+/// Source of files served to Slang through [`IFileSystemPtr::from_provider`].
+///
+/// Paths are given as Slang computed them (relative to the search paths, `/`-separated in most cases).
+pub trait FileSystemProvider: Send + Sync + 'static {
+    /// Returns the contents of the file at `path`, or [`SlangError::NOT_FOUND`] if there is no such file.
+    fn load_file(&self, path: &str) -> Result<Cow<'static, [u8]>>;
+}
+
+/// File system with path queries, served to Slang through [`IFileSystemExtPtr::from_provider`].
+///
+/// Without this Slang cannot tell whether two paths name the same file, so `#include` de-duplication and
+/// `#pragma once` only work on the literal path.
+pub trait FileSystemExtProvider: FileSystemProvider {
+    /// A string that is the same for every path naming the same file. Defaults to the simplified path.
+    fn file_unique_identity(&self, path: &str) -> Result<String> {
+        Ok(simplify_path(path))
+    }
+
+    /// `path` relative to `from_path` (a file or a directory, as given by `from_path_type`).
+    fn combine_path(
+        &self,
+        from_path_type: PathType,
+        from_path: &str,
+        path: &str,
+    ) -> Result<String> {
+        Ok(combine_paths(from_path_type, from_path, path))
+    }
+
+    fn path_type(&self, path: &str) -> Result<PathType>;
+
+    /// `path` in the requested form. Operating system paths are reported as unavailable by default.
+    fn path(&self, kind: PathKind, path: &str) -> Result<String> {
+        match kind {
+            SLANG_PATH_KIND_OPERATING_SYSTEM => Err(SlangError::NOT_AVAILABLE),
+            _ => Ok(simplify_path(path)),
+        }
+    }
+
+    fn clear_cache(&self) {}
+
+    /// Calls `callback` with the type and name of every item directly contained in the directory at `path`.
+    fn enumerate_path_contents(
+        &self,
+        path: &str,
+        callback: &mut dyn FnMut(PathType, &str),
+    ) -> Result<()>;
+
+    fn os_path_kind(&self) -> OSPathKind {
+        SLANG_OS_PATH_KIND_NONE
+    }
+}
+
+/// Writable file system, served to Slang through [`IMutableFileSystemPtr::from_provider`].
+pub trait MutableFileSystemProvider: FileSystemExtProvider {
+    fn save_file(&self, path: &str, data: &[u8]) -> Result<()>;
+
+    /// Removes the file or empty directory at `path`.
+    fn remove(&self, path: &str) -> Result<()>;
+
+    fn create_directory(&self, path: &str) -> Result<()>;
+}
+
+impl<P: FileSystemProvider> FileSystemProvider for Arc<P> {
+    #[inline(always)]
+    fn load_file(&self, path: &str) -> Result<Cow<'static, [u8]>> {
+        P::load_file(self, path)
+    }
+}
+impl<P: FileSystemExtProvider> FileSystemExtProvider for Arc<P> {
+    #[inline(always)]
+    fn file_unique_identity(&self, path: &str) -> Result<String> {
+        P::file_unique_identity(self, path)
+    }
+
+    #[inline(always)]
+    fn combine_path(
+        &self,
+        from_path_type: PathType,
+        from_path: &str,
+        path: &str,
+    ) -> Result<String> {
+        P::combine_path(self, from_path_type, from_path, path)
+    }
+
+    #[inline(always)]
+    fn path_type(&self, path: &str) -> Result<PathType> {
+        P::path_type(self, path)
+    }
+
+    #[inline(always)]
+    fn path(&self, kind: PathKind, path: &str) -> Result<String> {
+        P::path(self, kind, path)
+    }
+
+    #[inline(always)]
+    fn clear_cache(&self) {
+        P::clear_cache(self)
+    }
+
+    #[inline(always)]
+    fn enumerate_path_contents(
+        &self,
+        path: &str,
+        callback: &mut dyn FnMut(PathType, &str),
+    ) -> Result<()> {
+        P::enumerate_path_contents(self, path, callback)
+    }
+
+    #[inline(always)]
+    fn os_path_kind(&self) -> OSPathKind {
+        P::os_path_kind(self)
+    }
+}
+impl<P: MutableFileSystemProvider> MutableFileSystemProvider for Arc<P> {
+    #[inline(always)]
+    fn save_file(&self, path: &str, data: &[u8]) -> Result<()> {
+        P::save_file(self, path, data)
+    }
+
+    #[inline(always)]
+    fn remove(&self, path: &str) -> Result<()> {
+        P::remove(self, path)
+    }
+
+    #[inline(always)]
+    fn create_directory(&self, path: &str) -> Result<()> {
+        P::create_directory(self, path)
+    }
+}
+
+impl FileSystemProvider for HashMap<String, Vec<u8>> {
+    fn load_file(&self, path: &str) -> Result<Cow<'static, [u8]>> {
+        self.get(path)
+            .or_else(|| self.get(&simplify_path(path)))
+            .map(|x| Cow::Owned(x.clone()))
+            .ok_or(SlangError::NOT_FOUND)
+    }
+}
+impl FileSystemExtProvider for HashMap<String, Vec<u8>> {
+    fn path_type(&self, path: &str) -> Result<PathType> {
+        let keys = self.keys().map(|k| simplify_path(k)).collect::<Vec<_>>();
+
+        path_type_in(
+            keys.iter().map(|k| (k.as_str(), SLANG_PATH_TYPE_FILE)),
+            &simplify_path(path),
+        )
+    }
+
+    fn enumerate_path_contents(
+        &self,
+        path: &str,
+        callback: &mut dyn FnMut(PathType, &str),
+    ) -> Result<()> {
+        let keys = self.keys().map(|k| simplify_path(k)).collect::<Vec<_>>();
+        let path = simplify_path(path);
+        if path_type_in(
+            keys.iter().map(|k| (k.as_str(), SLANG_PATH_TYPE_FILE)),
+            &path,
+        )? != SLANG_PATH_TYPE_DIRECTORY
+        {
+            return Err(SlangError::INVALID_ARG);
+        }
+
+        for (name, t) in directory_entries(
+            keys.iter().map(|k| (k.as_str(), SLANG_PATH_TYPE_FILE)),
+            &path,
+        ) {
+            callback(t, name);
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+enum MemoryEntry {
+    File(Cow<'static, [u8]>),
+    Directory,
+}
+impl MemoryEntry {
+    const fn path_type(&self) -> PathType {
+        match self {
+            Self::File(_) => SLANG_PATH_TYPE_FILE,
+            Self::Directory => SLANG_PATH_TYPE_DIRECTORY,
+        }
+    }
+}
+
+/// An in-memory, writable file system.
+///
+/// Paths are simplified on insertion and lookup (`\` is treated as `/`, and `.`/`..` segments are resolved).
+/// Directories exist implicitly for every file, or explicitly once created.
+///
+/// Share it through an [`Arc`] to keep access to the files (e.g. ones saved by Slang) after handing it over.
+#[derive(Debug, Default)]
+pub struct MemoryFileSystem {
+    entries: RwLock<HashMap<String, MemoryEntry>>,
+}
+impl Clone for MemoryFileSystem {
+    fn clone(&self) -> Self {
+        Self {
+            entries: RwLock::new(self.read().clone()),
+        }
+    }
+}
+impl MemoryFileSystem {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, HashMap<String, MemoryEntry>> {
+        self.entries.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    #[inline]
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, HashMap<String, MemoryEntry>> {
+        self.entries.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Adds (or replaces) a file.
+    #[inline]
+    pub fn insert(&self, path: &str, contents: impl Into<Cow<'static, [u8]>>) {
+        self.write()
+            .insert(simplify_path(path), MemoryEntry::File(contents.into()));
+    }
+
+    #[inline]
+    pub fn with_file(self, path: &str, contents: impl Into<Cow<'static, [u8]>>) -> Self {
+        self.insert(path, contents);
+        self
+    }
+
+    /// Returns the contents of the file at `path`.
+    #[inline]
+    pub fn get(&self, path: &str) -> Option<Cow<'static, [u8]>> {
+        match self.read().get(&simplify_path(path)) {
+            Some(MemoryEntry::File(x)) => Some(x.clone()),
+            _ => None,
+        }
+    }
+
+    /// Removes the file at `path`, returning its contents.
+    #[inline]
+    pub fn remove_file(&self, path: &str) -> Option<Cow<'static, [u8]>> {
+        let mut entries = self.write();
+        let path = simplify_path(path);
+        match entries.get(&path) {
+            Some(MemoryEntry::File(_)) => match entries.remove(&path) {
+                Some(MemoryEntry::File(x)) => Some(x),
+                _ => unreachable!(),
+            },
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn contains(&self, path: &str) -> bool {
+        matches!(
+            self.read().get(&simplify_path(path)),
+            Some(MemoryEntry::File(_))
+        )
+    }
+
+    /// Paths of every file, in no particular order.
+    pub fn file_paths(&self) -> Vec<String> {
+        self.read()
+            .iter()
+            .filter(|(_, e)| matches!(e, MemoryEntry::File(_)))
+            .map(|(p, _)| p.clone())
+            .collect()
+    }
+}
+impl FileSystemProvider for MemoryFileSystem {
+    fn load_file(&self, path: &str) -> Result<Cow<'static, [u8]>> {
+        match self.read().get(&simplify_path(path)) {
+            Some(MemoryEntry::File(x)) => Ok(x.clone()),
+            Some(MemoryEntry::Directory) => Err(SlangError::CANNOT_OPEN),
+            None => Err(SlangError::NOT_FOUND),
+        }
+    }
+}
+impl FileSystemExtProvider for MemoryFileSystem {
+    fn path_type(&self, path: &str) -> Result<PathType> {
+        let entries = self.read();
+
+        path_type_in(
+            entries.iter().map(|(p, e)| (p.as_str(), e.path_type())),
+            &simplify_path(path),
+        )
+    }
+
+    fn enumerate_path_contents(
+        &self,
+        path: &str,
+        callback: &mut dyn FnMut(PathType, &str),
+    ) -> Result<()> {
+        let entries = self.read();
+        let path = simplify_path(path);
+        if path_type_in(
+            entries.iter().map(|(p, e)| (p.as_str(), e.path_type())),
+            &path,
+        )? != SLANG_PATH_TYPE_DIRECTORY
+        {
+            return Err(SlangError::INVALID_ARG);
+        }
+
+        for (name, t) in directory_entries(
+            entries.iter().map(|(p, e)| (p.as_str(), e.path_type())),
+            &path,
+        ) {
+            callback(t, name);
+        }
+
+        Ok(())
+    }
+}
+impl MutableFileSystemProvider for MemoryFileSystem {
+    fn save_file(&self, path: &str, data: &[u8]) -> Result<()> {
+        let mut entries = self.write();
+        let path = simplify_path(path);
+        if matches!(entries.get(&path), Some(MemoryEntry::Directory)) {
+            return Err(SlangError::CANNOT_OPEN);
+        }
+
+        entries.insert(path, MemoryEntry::File(Cow::Owned(data.to_vec())));
+        Ok(())
+    }
+
+    fn remove(&self, path: &str) -> Result<()> {
+        let mut entries = self.write();
+        let path = simplify_path(path);
+        let prefix = format!("{path}/");
+        if entries.keys().any(|p| p.starts_with(&prefix)) {
+            // non-empty directory
+            return Err(SlangError::FAIL);
+        }
+
+        entries.remove(&path).map(drop).ok_or(SlangError::NOT_FOUND)
+    }
+
+    fn create_directory(&self, path: &str) -> Result<()> {
+        let mut entries = self.write();
+        match entries.get(&simplify_path(path)) {
+            Some(MemoryEntry::File(_)) => Err(SlangError::FAIL),
+            Some(MemoryEntry::Directory) => Ok(()),
+            None => {
+                entries.insert(simplify_path(path), MemoryEntry::Directory);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A file system over a bundle embedded in the binary (e.g. built from `include_bytes!`).
+///
+/// Files are served without copying.
+#[derive(Debug, Clone, Copy)]
+pub struct StaticFileSystem {
+    files: &'static [(&'static str, &'static [u8])],
+}
+impl StaticFileSystem {
+    #[inline(always)]
+    pub const fn new(files: &'static [(&'static str, &'static [u8])]) -> Self {
+        Self { files }
+    }
+
+    fn simplified_paths(&self) -> Vec<String> {
+        self.files.iter().map(|(p, _)| simplify_path(p)).collect()
+    }
+}
+impl FileSystemProvider for StaticFileSystem {
+    fn load_file(&self, path: &str) -> Result<Cow<'static, [u8]>> {
+        let path = simplify_path(path);
+
+        self.files
+            .iter()
+            .find(|(p, _)| simplify_path(p) == path)
+            .map(|&(_, x)| Cow::Borrowed(x))
+            .ok_or(SlangError::NOT_FOUND)
+    }
+}
+impl FileSystemExtProvider for StaticFileSystem {
+    fn path_type(&self, path: &str) -> Result<PathType> {
+        let paths = self.simplified_paths();
+
+        path_type_in(
+            paths.iter().map(|p| (p.as_str(), SLANG_PATH_TYPE_FILE)),
+            &simplify_path(path),
+        )
+    }
+
+    fn enumerate_path_contents(
+        &self,
+        path: &str,
+        callback: &mut dyn FnMut(PathType, &str),
+    ) -> Result<()> {
+        let paths = self.simplified_paths();
+        let path = simplify_path(path);
+        if path_type_in(
+            paths.iter().map(|p| (p.as_str(), SLANG_PATH_TYPE_FILE)),
+            &path,
+        )? != SLANG_PATH_TYPE_DIRECTORY
+        {
+            return Err(SlangError::INVALID_ARG);
+        }
+
+        for (name, t) in directory_entries(
+            paths.iter().map(|p| (p.as_str(), SLANG_PATH_TYPE_FILE)),
+            &path,
+        ) {
+            callback(t, name);
+        }
+
+        Ok(())
+    }
+}
+
+/// Unifies separators to `/` and resolves `.` and `..` segments.
+fn simplify_path(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let absolute = path.starts_with('/');
+    let mut segments = Vec::new();
+    for s in path.split('/') {
+        match s {
+            "" | "." => (),
+            ".." => match segments.last() {
+                Some(&l) if l != ".." => {
+                    segments.pop();
+                }
+                _ if !absolute => segments.push(".."),
+                _ => (),
+            },
+            s => segments.push(s),
+        }
+    }
+
+    let joined = segments.join("/");
+    if absolute {
+        format!("/{joined}")
+    } else {
+        joined
+    }
+}
+
+fn is_absolute_path(path: &str) -> bool {
+    let b = path.as_bytes();
+
+    path.starts_with(['/', '\\']) || (b.len() >= 2 && b[0].is_ascii_alphabetic() && b[1] == b':')
+}
+
+/// Default `calcCombinedPath` behavior: `path` resolved against the directory named by (or containing) `from_path`.
+fn combine_paths(from_path_type: PathType, from_path: &str, path: &str) -> String {
+    if is_absolute_path(path) {
+        return simplify_path(path);
+    }
+
+    let from_path = from_path.replace('\\', "/");
+    let base = match from_path_type {
+        SLANG_PATH_TYPE_FILE => from_path.rsplit_once('/').map_or("", |(d, _)| d),
+        _ => &from_path,
+    };
+    if base.is_empty() {
+        simplify_path(path)
+    } else {
+        simplify_path(&format!("{base}/{path}"))
+    }
+}
+
+/// Type of the item at simplified `path` among simplified `items`.
+fn path_type_in<'a>(
+    items: impl IntoIterator<Item = (&'a str, PathType)>,
+    path: &str,
+) -> Result<PathType> {
+    if path.is_empty() {
+        // root
+        return Ok(SLANG_PATH_TYPE_DIRECTORY);
+    }
+
+    let mut found = None;
+    for (p, t) in items {
+        if p == path {
+            return Ok(t);
+        }
+        if p.strip_prefix(path).is_some_and(|r| r.starts_with('/')) {
+            found = Some(SLANG_PATH_TYPE_DIRECTORY);
+        }
+    }
+
+    found.ok_or(SlangError::NOT_FOUND)
+}
+
+/// Names and types of the items directly under the simplified directory path `dir` among simplified `items`.
+fn directory_entries<'a>(
+    items: impl IntoIterator<Item = (&'a str, PathType)>,
+    dir: &str,
+) -> BTreeMap<&'a str, PathType> {
+    let mut entries = BTreeMap::new();
+    for (p, t) in items {
+        let rest = if dir.is_empty() {
+            Some(p.trim_start_matches('/'))
+        } else {
+            p.strip_prefix(dir).and_then(|r| r.strip_prefix('/'))
+        };
+        let Some(rest) = rest.filter(|r| !r.is_empty()) else {
+            continue;
+        };
+
+        match rest.split_once('/') {
+            Some((d, _)) => {
+                entries.insert(d, SLANG_PATH_TYPE_DIRECTORY);
+            }
+            None => {
+                entries.entry(rest).or_insert(t);
+            }
+        }
+    }
+
+    entries
+}
+
+/// Runs a provider call, turning panics into [`SlangError::INTERNAL_FAIL`] as they must not unwind into Slang.
+fn guarded<R>(f: impl FnOnce() -> Result<R>) -> Result<R> {
+    std::panic::catch_unwind(core::panic::AssertUnwindSafe(f))
+        .unwrap_or(Err(SlangError::INTERNAL_FAIL))
+}
+
+#[inline]
+unsafe fn str_arg<'a>(s: *const c_char) -> Result<&'a str> {
+    unsafe { CStr::from_ptr(s) }
+        .to_str()
+        .map_err(|_| SlangError::INVALID_ARG)
+}
+
+/// Writes the blob (or null on failure) to `out` and returns the matching result code.
+unsafe fn write_blob(out: *mut *mut c_void, r: Result<IBlobPtr>) -> SlangResult {
+    match r {
+        Ok(blob) => {
+            unsafe {
+                out.write(ManuallyDrop::new(blob).thisptr());
+            }
+
+            SLANG_OK
+        }
+        Err(e) => {
+            unsafe {
+                out.write(core::ptr::null_mut());
+            }
+
+            e.code()
+        }
+    }
+}
+
+#[inline]
+fn result_code(r: Result<()>) -> SlangResult {
+    r.map_or_else(|e| e.code(), |_| SLANG_OK)
+}
+
+type FileSystemObject<P> = ComObject<P>;
+
+struct Adapter<P>(core::marker::PhantomData<P>);
+impl<P: FileSystemProvider> Adapter<P> {
+    const INTERFACES: &'static [SlangUUID] = &[UNKNOWN_IID, CASTABLE_IID, FILE_SYSTEM_IID];
+    const VTABLE: IFileSystemVTable = IFileSystemVTable {
+        base: ICastableVTable {
+            base: IUnknownVTable {
+                query_interface: FileSystemObject::<P>::query_interface,
+                add_ref: FileSystemObject::<P>::add_ref,
+                release: FileSystemObject::<P>::release,
+            },
+            cast_as: FileSystemObject::<P>::cast_as,
+        },
+        load_file: Self::load_file,
+    };
+
+    #[inline(always)]
+    unsafe fn provider<'a>(this: *mut c_void) -> &'a P {
+        unsafe { FileSystemObject::<P>::inner(this) }
+    }
+
+    unsafe extern "system" fn load_file(
+        this: *mut c_void,
+        path: *const c_char,
+        out_blob: *mut *mut c_void,
+    ) -> SlangResult {
+        let r = guarded(|| {
+            let data = unsafe { Self::provider(this) }.load_file(unsafe { str_arg(path)? })?;
+
+            Ok(new_blob(data))
+        });
+
+        unsafe { write_blob(out_blob, r) }
+    }
+}
+impl<P: FileSystemExtProvider> Adapter<P> {
+    const EXT_INTERFACES: &'static [SlangUUID] = &[
+        UNKNOWN_IID,
+        CASTABLE_IID,
+        FILE_SYSTEM_IID,
+        FILE_SYSTEM_EXT_IID,
+    ];
+    const EXT_VTABLE: IFileSystemExtVTable = IFileSystemExtVTable {
+        base: Self::VTABLE,
+        get_file_unique_identity: Self::get_file_unique_identity,
+        calc_combined_path: Self::calc_combined_path,
+        get_path_type: Self::get_path_type,
+        get_path: Self::get_path,
+        clear_cache: Self::clear_cache,
+        enumerate_path_contents: Self::enumerate_path_contents,
+        get_os_path_kind: Self::get_os_path_kind,
+    };
+
+    unsafe extern "system" fn get_file_unique_identity(
+        this: *mut c_void,
+        path: *const c_char,
+        out_unique_identity: *mut *mut c_void,
+    ) -> SlangResult {
+        let r = guarded(|| {
+            unsafe { Self::provider(this) }
+                .file_unique_identity(unsafe { str_arg(path)? })
+                .map(new_string_blob)
+        });
+
+        unsafe { write_blob(out_unique_identity, r) }
+    }
+
+    unsafe extern "system" fn calc_combined_path(
+        this: *mut c_void,
+        from_path_type: PathType,
+        from_path: *const c_char,
+        path: *const c_char,
+        path_out: *mut *mut c_void,
+    ) -> SlangResult {
+        let r = guarded(|| {
+            unsafe { Self::provider(this) }
+                .combine_path(from_path_type, unsafe { str_arg(from_path)? }, unsafe {
+                    str_arg(path)?
+                })
+                .map(new_string_blob)
+        });
+
+        unsafe { write_blob(path_out, r) }
+    }
+
+    unsafe extern "system" fn get_path_type(
+        this: *mut c_void,
+        path: *const c_char,
+        path_type_out: *mut PathType,
+    ) -> SlangResult {
+        let r = guarded(|| unsafe { Self::provider(this) }.path_type(unsafe { str_arg(path)? }));
+
+        result_code(r.map(|t| unsafe { path_type_out.write(t) }))
+    }
+
+    unsafe extern "system" fn get_path(
+        this: *mut c_void,
+        kind: PathKind,
+        path: *const c_char,
+        out_path: *mut *mut c_void,
+    ) -> SlangResult {
+        let r = guarded(|| {
+            unsafe { Self::provider(this) }
+                .path(kind, unsafe { str_arg(path)? })
+                .map(new_string_blob)
+        });
+
+        unsafe { write_blob(out_path, r) }
+    }
+
+    unsafe extern "system" fn clear_cache(this: *mut c_void) {
+        let _ = guarded(|| {
+            unsafe { Self::provider(this) }.clear_cache();
+            Ok(())
+        });
+    }
+
+    unsafe extern "system" fn enumerate_path_contents(
+        this: *mut c_void,
+        path: *const c_char,
+        callback: FileSystemContentsCallBack,
+        user_data: *mut c_void,
+    ) -> SlangResult {
+        // a panic may come from the provider or from the Rust side of the callback after some entries were reported,
+        // so the listing Slang received is incomplete: report a plain failure instead of unwinding into Slang
+        std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| {
+            let path = match unsafe { str_arg(path) } {
+                Ok(path) => path,
+                Err(e) => return e.code(),
+            };
+
+            result_code(unsafe { Self::provider(this) }.enumerate_path_contents(
+                path,
+                &mut |t, name| {
+                    // names containing NUL cannot be represented
+                    if let Ok(name) = CString::new(name) {
+                        callback(t, name.as_ptr(), user_data);
+                    }
+                },
+            ))
+        }))
+        .unwrap_or(SLANG_FAIL)
+    }
+
+    unsafe extern "system" fn get_os_path_kind(this: *mut c_void) -> OSPathKind {
+        guarded(|| Ok(unsafe { Self::provider(this) }.os_path_kind()))
+            .unwrap_or(SLANG_OS_PATH_KIND_NONE)
+    }
+}
+impl<P: MutableFileSystemProvider> Adapter<P> {
+    const MUTABLE_INTERFACES: &'static [SlangUUID] = &[
+        UNKNOWN_IID,
+        CASTABLE_IID,
+        FILE_SYSTEM_IID,
+        FILE_SYSTEM_EXT_IID,
+        MUTABLE_FILE_SYSTEM_IID,
+    ];
+    const MUTABLE_VTABLE: IMutableFileSystemVTable = IMutableFileSystemVTable {
+        base: Self::EXT_VTABLE,
+        save_file: Self::save_file,
+        save_file_blob: Self::save_file_blob,
+        remove: Self::remove,
+        create_directory: Self::create_directory,
+    };
+
+    unsafe extern "system" fn save_file(
+        this: *mut c_void,
+        path: *const c_char,
+        data: *const c_void,
+        size: usize,
+    ) -> SlangResult {
+        result_code(guarded(|| {
+            let data = match size {
+                0 => &[][..],
+                _ => unsafe { core::slice::from_raw_parts(data.cast::<u8>(), size) },
+            };
+
+            unsafe { Self::provider(this) }.save_file(unsafe { str_arg(path)? }, data)
+        }))
+    }
+
+    unsafe extern "system" fn save_file_blob(
+        this: *mut c_void,
+        path: *const c_char,
+        data_blob: *mut c_void,
+    ) -> SlangResult {
+        let Some(data_blob) = core::ptr::NonNull::new(data_blob) else {
+            return SlangError::INVALID_ARG.code();
+        };
+        // borrowed from the caller
+        let blob = ManuallyDrop::new(unsafe { IBlobPtr::from_ptr(data_blob) });
+        let (data, size) = (
+            crate::IBlob::get_buffer_pointer(&*blob),
+            crate::IBlob::get_buffer_size(&*blob),
+        );
+
+        unsafe { Self::save_file(this, path, data, size) }
+    }
+
+    unsafe extern "system" fn remove(this: *mut c_void, path: *const c_char) -> SlangResult {
+        result_code(guarded(|| {
+            unsafe { Self::provider(this) }.remove(unsafe { str_arg(path)? })
+        }))
+    }
+
+    unsafe extern "system" fn create_directory(
+        this: *mut c_void,
+        path: *const c_char,
+    ) -> SlangResult {
+        result_code(guarded(|| {
+            unsafe { Self::provider(this) }.create_directory(unsafe { str_arg(path)? })
+        }))
+    }
+}
+
+impl IFileSystemPtr {
+    /// Wraps a Rust file system so it can be handed to Slang (e.g. through
+    /// [`SessionDescBuilder::file_system`](crate::SessionDescBuilder::file_system)).
+    pub fn from_provider<P: FileSystemProvider>(provider: P) -> Self {
+        unsafe {
+            Self::from_ptr(FileSystemObject::<P>::allocate(
+                &Adapter::<P>::VTABLE,
+                Adapter::<P>::INTERFACES,
+                provider,
+            ))
+        }
+    }
+}
+impl IFileSystemExtPtr {
+    /// Wraps a Rust file system with path queries.
+    ///
+    /// Use [`into_file_system`](Self::into_file_system) to hand it to
+    /// [`SessionDescBuilder::file_system`](crate::SessionDescBuilder::file_system).
+    pub fn from_provider<P: FileSystemExtProvider>(provider: P) -> Self {
+        unsafe {
+            Self::from_ptr(FileSystemObject::<P>::allocate(
+                &Adapter::<P>::EXT_VTABLE,
+                Adapter::<P>::EXT_INTERFACES,
+                provider,
+            ))
+        }
+    }
+
+    /// Views this object through its `ISlangFileSystem` base.
+    #[inline]
+    pub fn into_file_system(self) -> IFileSystemPtr {
+        let this = ManuallyDrop::new(self);
+
+        unsafe { IFileSystemPtr::from_ptr(this.0) }
+    }
+}
+impl IMutableFileSystemPtr {
+    /// Wraps a writable Rust file system.
+    pub fn from_provider<P: MutableFileSystemProvider>(provider: P) -> Self {
+        unsafe {
+            Self::from_ptr(FileSystemObject::<P>::allocate(
+                &Adapter::<P>::MUTABLE_VTABLE,
+                Adapter::<P>::MUTABLE_INTERFACES,
+                provider,
+            ))
+        }
+    }
+
+    /// Views this object through its `ISlangFileSystem` base.
+    #[inline]
+    pub fn into_file_system(self) -> IFileSystemPtr {
+        let this = ManuallyDrop::new(self);
+
+        unsafe { IFileSystemPtr::from_ptr(this.0) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::IFileSystemExt;
+
+    use super::*;
+
+    const FILE: PathType = SLANG_PATH_TYPE_FILE;
+    const DIRECTORY: PathType = SLANG_PATH_TYPE_DIRECTORY;
+
+    #[test]
+    fn simplify() {
+        assert_eq!(simplify_path("a/./b//c"), "a/b/c");
+        assert_eq!(simplify_path("a\\b\\..\\c"), "a/c");
+        assert_eq!(simplify_path("../a/../../b"), "../../b");
+        assert_eq!(simplify_path("/../a/"), "/a");
+        assert_eq!(simplify_path("./"), "");
+        assert_eq!(simplify_path("/"), "/");
+    }
+
+    #[test]
+    fn combine() {
+        assert_eq!(
+            combine_paths(FILE, "shaders/main.slang", "common.slang"),
+            "shaders/common.slang"
+        );
+        assert_eq!(
+            combine_paths(DIRECTORY, "shaders", "lib/a.slang"),
+            "shaders/lib/a.slang"
+        );
+        assert_eq!(combine_paths(FILE, "main.slang", "./a.slang"), "a.slang");
+        assert_eq!(
+            combine_paths(FILE, "shaders\\sub\\main.slang", "../a.slang"),
+            "shaders/a.slang"
+        );
+        assert_eq!(
+            combine_paths(FILE, "shaders/main.slang", "/abs/a.slang"),
+            "/abs/a.slang"
+        );
+        assert_eq!(
+            combine_paths(FILE, "shaders/main.slang", "C:\\x\\a.slang"),
+            "C:/x/a.slang"
+        );
+    }
+
+    const ITEMS: &[(&str, PathType)] = &[
+        ("a.slang", FILE),
+        ("lib/b.slang", FILE),
+        ("lib/sub/c.slang", FILE),
+        ("empty", DIRECTORY),
+        ("library.slang", FILE),
+    ];
+
+    #[test]
+    fn path_types() {
+        assert_eq!(path_type_in(ITEMS.iter().copied(), ""), Ok(DIRECTORY));
+        assert_eq!(path_type_in(ITEMS.iter().copied(), "a.slang"), Ok(FILE));
+        assert_eq!(path_type_in(ITEMS.iter().copied(), "lib"), Ok(DIRECTORY));
+        assert_eq!(
+            path_type_in(ITEMS.iter().copied(), "lib/sub"),
+            Ok(DIRECTORY)
+        );
+        assert_eq!(path_type_in(ITEMS.iter().copied(), "empty"), Ok(DIRECTORY));
+        // a prefix of a name is not a directory
+        assert_eq!(
+            path_type_in(ITEMS.iter().copied(), "li"),
+            Err(SlangError::NOT_FOUND)
+        );
+        assert_eq!(
+            path_type_in(ITEMS.iter().copied(), "missing"),
+            Err(SlangError::NOT_FOUND)
+        );
+    }
+
+    #[test]
+    fn entries() {
+        assert_eq!(
+            directory_entries(ITEMS.iter().copied(), "")
+                .into_iter()
+                .collect::<Vec<_>>(),
+            [
+                ("a.slang", FILE),
+                ("empty", DIRECTORY),
+                ("lib", DIRECTORY),
+                ("library.slang", FILE)
+            ]
+        );
+        assert_eq!(
+            directory_entries(ITEMS.iter().copied(), "lib")
+                .into_iter()
+                .collect::<Vec<_>>(),
+            [("b.slang", FILE), ("sub", DIRECTORY)]
+        );
+        assert!(directory_entries(ITEMS.iter().copied(), "empty").is_empty());
+    }
+
+    #[test]
+    fn enumerate_through_the_interface() {
+        let fs = IFileSystemExtPtr::from_provider(
+            MemoryFileSystem::new()
+                .with_file("lib/b.slang", &b""[..])
+                .with_file("lib/sub/c.slang", &b""[..]),
+        );
+        let mut names = Vec::new();
+        fs.enumerate_path_contents(c"lib", |t, name| {
+            names.push((t, name.to_str().unwrap().to_owned()));
+        })
+        .unwrap();
+
+        assert_eq!(
+            names,
+            [(FILE, "b.slang".to_owned()), (DIRECTORY, "sub".to_owned())]
+        );
+        assert_eq!(
+            fs.enumerate_path_contents(c"lib/b.slang", |_, _| ()),
+            Err(SlangError::INVALID_ARG)
+        );
+    }
+
+    struct Panicking;
+    impl FileSystemProvider for Panicking {
+        fn load_file(&self, _path: &str) -> Result<Cow<'static, [u8]>> {
+            Err(SlangError::NOT_FOUND)
+        }
+    }
+    impl FileSystemExtProvider for Panicking {
+        fn path_type(&self, _path: &str) -> Result<PathType> {
+            Ok(DIRECTORY)
+        }
+
+        fn enumerate_path_contents(
+            &self,
+            _path: &str,
+            callback: &mut dyn FnMut(PathType, &str),
+        ) -> Result<()> {
+            callback(FILE, "a.slang");
+            panic!("enumeration failed");
+        }
+    }
+
+    #[test]
+    fn enumerate_panic_does_not_unwind() {
+        let fs = IFileSystemExtPtr::from_provider(Panicking);
+        let mut count = 0;
+
+        assert_eq!(
+            fs.enumerate_path_contents(c"", |_, _| count += 1),
+            Err(SlangError::FAIL)
+        );
+        assert_eq!(count, 1);
+    }
+}
@@ -0,0 +1,882 @@
+//! Checking Rust types against the uniform layout of shader types.
+//!
+//! [`ShaderLayout`] describes the memory layout of a Rust type (implemented for scalars and arrays here, and for
+//! `#[repr(C)]` structs with `#[derive(ShaderLayout)]` when the `derive` feature is enabled). [`verify`] compares it
+//! with a [`TypeLayout`] from reflection and reports every difference, so std140/std430/scalar layout mistakes show
+//! up as a readable list instead of garbage on the GPU.
+//!
+//! Rust fields whose name starts with `_` are treated as padding and are not required to exist in the shader.
+
+use crate::{
+    ffi,
+    reflection::{ParameterCategory, ScalarType, TypeKind, TypeLayout, owned_name},
+};
+
+/// Memory layout of a Rust type, as relevant to a shader.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HostType {
+    Scalar(ScalarType),
+    /// `[T; N]`; also matches vectors (of scalars) and matrices (of arrays of scalars).
+    Array {
+        element: Box<HostType>,
+        count: usize,
+        /// Distance between elements in bytes.
+        stride: usize,
+    },
+    Struct(HostStruct),
+}
+impl HostType {
+    /// Size in bytes.
+    pub fn size(&self) -> usize {
+        match self {
+            Self::Scalar(s) => scalar_size(*s),
+            Self::Array { count, stride, .. } => count * stride,
+            Self::Struct(s) => s.size,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostStruct {
+    pub name: &'static str,
+    pub size: usize,
+    pub fields: Vec<HostField>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostField {
+    /// Name of the field in the shader.
+    pub name: &'static str,
+    pub offset: usize,
+    pub ty: HostType,
+}
+
+/// A Rust type with a known shader-visible layout.
+pub trait ShaderLayout {
+    fn host_type() -> HostType;
+}
+
+macro_rules! scalar_layout {
+    ($($t: ty => $s: ident),* $(,)?) => {
+        $(
+            impl ShaderLayout for $t {
+                #[inline]
+                fn host_type() -> HostType {
+                    HostType::Scalar(ScalarType::$s)
+                }
+            }
+        )*
+    };
+}
+scalar_layout!(
+    i8 => Int8,
+    u8 => UInt8,
+    i16 => Int16,
+    u16 => UInt16,
+    i32 => Int32,
+    u32 => UInt32,
+    i64 => Int64,
+    u64 => UInt64,
+    f32 => Float32,
+    f64 => Float64,
+);
+
+impl<T: ShaderLayout, const N: usize> ShaderLayout for [T; N] {
+    fn host_type() -> HostType {
+        HostType::Array {
+            element: Box::new(T::host_type()),
+            count: N,
+            stride: core::mem::size_of::<T>(),
+        }
+    }
+}
+
+/// A difference between a Rust type and a shader type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayoutMismatch {
+    /// Location of the difference (e.g. `Globals.lights[].color`).
+    pub path: String,
+    pub kind: MismatchKind,
+}
+impl core::fmt::Display for LayoutMismatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}: ", self.path)?;
+
+        match &self.kind {
+            MismatchKind::MissingField => f.write_str("field exists in the shader but not in Rust"),
+            MismatchKind::ExtraField => f.write_str("field exists in Rust but not in the shader"),
+            MismatchKind::Offset { rust, shader } => {
+                write!(f, "offset is {rust} in Rust but {shader} in the shader")
+            }
+            MismatchKind::Size { rust, shader } => {
+                write!(f, "size is {rust} in Rust but {shader} in the shader")
+            }
+            MismatchKind::ScalarType { rust, shader } => {
+                write!(
+                    f,
+                    "scalar type is {rust:?} in Rust but {shader:?} in the shader"
+                )
+            }
+            MismatchKind::Shape { rust, shader } => {
+                write!(f, "type is {rust} in Rust but {shader} in the shader")
+            }
+            MismatchKind::Stride { rust, shader } => {
+                write!(
+                    f,
+                    "array stride is {rust} in Rust but {shader} in the shader"
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MismatchKind {
+    MissingField,
+    ExtraField,
+    Offset {
+        rust: usize,
+        shader: usize,
+    },
+    Size {
+        rust: usize,
+        shader: usize,
+    },
+    ScalarType {
+        rust: ScalarType,
+        shader: ScalarType,
+    },
+    /// Different kind of type or dimensions (described as e.g. `[f32; 3]` / `float4`).
+    Shape {
+        rust: String,
+        shader: String,
+    },
+    Stride {
+        rust: usize,
+        shader: usize,
+    },
+}
+
+/// Compares the layout of `T` with the uniform layout of `layout`.
+pub fn verify<T: ShaderLayout>(layout: TypeLayout<'_>) -> Result<(), Vec<LayoutMismatch>> {
+    verify_layout(&T::host_type(), layout)
+}
+
+/// Compares `host` with `layout`, appending the differences to `out`.
+#[inline]
+pub fn verify_type(
+    host: &HostType,
+    layout: TypeLayout<'_>,
+    path: &str,
+    out: &mut Vec<LayoutMismatch>,
+) {
+    compare(host, layout, path, out);
+}
+
+/// The parts of a shader type layout the comparison and [`crate::codegen`] read.
+///
+/// Implemented for [`TypeLayout`]; the tests implement it for hand-built layouts so the comparison can be checked
+/// without compiling a shader.
+pub(crate) trait ShaderTypeLayout: Copy {
+    fn kind(self) -> TypeKind;
+    /// Size in bytes of the uniform data.
+    fn size(self) -> usize;
+    fn scalar_type(self) -> ScalarType;
+    fn row_count(self) -> usize;
+    fn column_count(self) -> usize;
+    fn row_major(self) -> bool;
+    fn element_count(self) -> usize;
+    /// Distance in bytes between array elements.
+    fn element_stride(self) -> usize;
+    fn element(self) -> Option<Self>;
+    /// Name, uniform offset and layout of every field.
+    fn fields(self) -> impl Iterator<Item = (String, usize, Self)>;
+    fn name(self) -> Option<String>;
+}
+impl ShaderTypeLayout for TypeLayout<'_> {
+    #[inline]
+    fn kind(self) -> TypeKind {
+        TypeLayout::kind(self)
+    }
+
+    #[inline]
+    fn size(self) -> usize {
+        TypeLayout::size(self, ParameterCategory::Uniform)
+    }
+
+    #[inline]
+    fn scalar_type(self) -> ScalarType {
+        TypeLayout::scalar_type(self)
+    }
+
+    #[inline]
+    fn row_count(self) -> usize {
+        TypeLayout::row_count(self) as _
+    }
+
+    #[inline]
+    fn column_count(self) -> usize {
+        TypeLayout::column_count(self) as _
+    }
+
+    #[inline]
+    fn row_major(self) -> bool {
+        self.matrix_layout_mode() == ffi::SLANG_MATRIX_LAYOUT_ROW_MAJOR
+    }
+
+    #[inline]
+    fn element_count(self) -> usize {
+        TypeLayout::element_count(self, None)
+    }
+
+    #[inline]
+    fn element_stride(self) -> usize {
+        TypeLayout::element_stride(self, ParameterCategory::Uniform)
+    }
+
+    #[inline]
+    fn element(self) -> Option<Self> {
+        self.element_type_layout()
+    }
+
+    fn fields(self) -> impl Iterator<Item = (String, usize, Self)> {
+        self.iter_field().map(|f| {
+            (
+                owned_name(f.name()).unwrap_or_default(),
+                f.offset(ParameterCategory::Uniform),
+                f.type_layout(),
+            )
+        })
+    }
+
+    #[inline]
+    fn name(self) -> Option<String> {
+        owned_name(TypeLayout::name(self))
+    }
+}
+
+fn verify_layout<L: ShaderTypeLayout>(
+    host: &HostType,
+    layout: L,
+) -> Result<(), Vec<LayoutMismatch>> {
+    let path = match host {
+        HostType::Struct(s) => s.name.to_owned(),
+        _ => String::new(),
+    };
+
+    let mut mismatches = Vec::new();
+    compare(host, layout, &path, &mut mismatches);
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(mismatches)
+    }
+}
+
+fn compare<L: ShaderTypeLayout>(
+    host: &HostType,
+    layout: L,
+    path: &str,
+    out: &mut Vec<LayoutMismatch>,
+) {
+    let mut kinds = Vec::new();
+
+    let size = layout.size();
+
+    match (host, layout.kind()) {
+        (HostType::Scalar(rust), TypeKind::Scalar) => {
+            if !scalar_compatible(*rust, layout.scalar_type()) {
+                kinds.push(MismatchKind::ScalarType {
+                    rust: *rust,
+                    shader: layout.scalar_type(),
+                });
+            }
+        }
+        (HostType::Array { element, count, .. }, TypeKind::Vector) => {
+            let n = layout.element_count();
+            match **element {
+                HostType::Scalar(rust) if *count == n => {
+                    if !scalar_compatible(rust, layout.scalar_type()) {
+                        kinds.push(MismatchKind::ScalarType {
+                            rust,
+                            shader: layout.scalar_type(),
+                        });
+                    }
+                }
+                _ => kinds.push(shape_mismatch(host, layout)),
+            }
+        }
+        (HostType::Array { element, count, .. }, TypeKind::Matrix) => {
+            let (outer, inner) = if layout.row_major() {
+                (layout.row_count(), layout.column_count())
+            } else {
+                (layout.column_count(), layout.row_count())
+            };
+            let stride = matrix_stride(size, outer, inner, scalar_size(layout.scalar_type()));
+            match **element {
+                // rows/columns padded to the stride between them (a wrong total size is reported as such)
+                HostType::Array {
+                    element: ref scalar,
+                    ..
+                } if *count == outer
+                    && (host.size() != size
+                        || stride.is_some_and(|s| s * outer == size && element.size() == s)) =>
+                {
+                    match **scalar {
+                        HostType::Scalar(rust)
+                            if !scalar_compatible(rust, layout.scalar_type()) =>
+                        {
+                            kinds.push(MismatchKind::ScalarType {
+                                rust,
+                                shader: layout.scalar_type(),
+                            })
+                        }
+                        HostType::Scalar(_) => (),
+                        _ => kinds.push(shape_mismatch(host, layout)),
+                    }
+                }
+                // flat storage (as generated when the last row/column is not padded)
+                HostType::Scalar(rust)
+                    if stride.is_some() && *count * scalar_size(rust) == size =>
+                {
+                    if !scalar_compatible(rust, layout.scalar_type()) {
+                        kinds.push(MismatchKind::ScalarType {
+                            rust,
+                            shader: layout.scalar_type(),
+                        });
+                    }
+                }
+                _ => kinds.push(shape_mismatch(host, layout)),
+            }
+        }
+        (
+            HostType::Array {
+                element,
+                count,
+                stride,
+            },
+            TypeKind::Array,
+        ) => {
+            let shader_count = layout.element_count();
+            let shader_stride = layout.element_stride();
+            if *count != shader_count {
+                kinds.push(shape_mismatch(host, layout));
+            } else if *stride != shader_stride {
+                kinds.push(MismatchKind::Stride {
+                    rust: *stride,
+                    shader: shader_stride,
+                });
+            }
+            if let Some(e) = layout.element() {
+                compare(unwrap_padded(element), e, &format!("{path}[]"), out);
+            }
+        }
+        (HostType::Struct(s), TypeKind::Struct) => compare_struct(s, layout, path, out),
+        _ => kinds.push(shape_mismatch(host, layout)),
+    }
+
+    // Arrays report their stride instead; the last element may legitimately be unpadded in the shader.
+    if !matches!(
+        (host, layout.kind()),
+        (HostType::Array { .. }, TypeKind::Array)
+    ) && host.size() != size
+    {
+        kinds.push(MismatchKind::Size {
+            rust: host.size(),
+            shader: size,
+        });
+    }
+
+    out.extend(kinds.into_iter().map(|kind| LayoutMismatch {
+        path: path.to_owned(),
+        kind,
+    }));
+}
+
+fn compare_struct<L: ShaderTypeLayout>(
+    host: &HostStruct,
+    layout: L,
+    path: &str,
+    out: &mut Vec<LayoutMismatch>,
+) {
+    let mut seen = Vec::new();
+    for (name, offset, f_layout) in layout.fields() {
+        let field_path = format!("{path}.{name}");
+        if f_layout.size() == 0 {
+            // resources and the like have no uniform data
+            continue;
+        }
+
+        let Some(h) = host.fields.iter().find(|h| h.name == name) else {
+            out.push(LayoutMismatch {
+                path: field_path,
+                kind: MismatchKind::MissingField,
+            });
+            continue;
+        };
+        seen.push(h.name);
+
+        if h.offset != offset {
+            out.push(LayoutMismatch {
+                path: field_path.clone(),
+                kind: MismatchKind::Offset {
+                    rust: h.offset,
+                    shader: offset,
+                },
+            });
+        }
+        compare(&h.ty, f_layout, &field_path, out);
+    }
+
+    for h in &host.fields {
+        if !h.name.starts_with('_') && !seen.contains(&h.name) {
+            out.push(LayoutMismatch {
+                path: format!("{path}.{}", h.name),
+                kind: MismatchKind::ExtraField,
+            });
+        }
+    }
+}
+
+/// The element of a struct padding an array element to the array stride (a struct whose only non-padding field is
+/// `value` at offset 0, as generated by [`codegen`](crate::codegen)), or `host` itself.
+fn unwrap_padded(host: &HostType) -> &HostType {
+    if let HostType::Struct(s) = host {
+        let mut fields = s.fields.iter().filter(|f| !f.name.starts_with('_'));
+        if let (Some(f), None) = (fields.next(), fields.next())
+            && f.name == "value"
+            && f.offset == 0
+        {
+            return &f.ty;
+        }
+    }
+
+    host
+}
+
+/// Distance in bytes between the rows (row-major) or columns (column-major) of a matrix of `size` bytes made of
+/// `outer` vectors of `inner` elements of `bytes` each, or `None` if no layout rule gives that size.
+///
+/// Vectors are packed tightly (scalar layout), aligned to a power of two (std430) or to 16 bytes (std140, D3D constant
+/// buffers), and the last one may be left unpadded (D3D constant buffers).
+pub(crate) fn matrix_stride(
+    size: usize,
+    outer: usize,
+    inner: usize,
+    bytes: usize,
+) -> Option<usize> {
+    let tight = inner * bytes;
+    let strides = [
+        tight,
+        inner.next_power_of_two() * bytes,
+        tight.next_multiple_of(16),
+    ];
+
+    strides.into_iter().find(|s| s * outer == size).or_else(|| {
+        strides
+            .into_iter()
+            .find(|s| outer > 1 && s * (outer - 1) + tight == size)
+    })
+}
+
+fn scalar_size(s: ScalarType) -> usize {
+    match s {
+        ScalarType::None | ScalarType::Void => 0,
+        ScalarType::Int8 | ScalarType::UInt8 => 1,
+        ScalarType::Int16 | ScalarType::UInt16 | ScalarType::Float16 => 2,
+        ScalarType::Bool | ScalarType::Int32 | ScalarType::UInt32 | ScalarType::Float32 => 4,
+        ScalarType::Int64 | ScalarType::UInt64 | ScalarType::Float64 => 8,
+    }
+}
+
+/// Whether a Rust scalar can hold a shader scalar (`bool` is stored as a 32-bit integer, `half` as raw `u16` bits).
+fn scalar_compatible(rust: ScalarType, shader: ScalarType) -> bool {
+    rust == shader
+        || matches!(
+            (rust, shader),
+            (ScalarType::UInt32 | ScalarType::Int32, ScalarType::Bool)
+                | (ScalarType::UInt16, ScalarType::Float16)
+        )
+}
+
+fn shape_mismatch<L: ShaderTypeLayout>(host: &HostType, layout: L) -> MismatchKind {
+    MismatchKind::Shape {
+        rust: describe_host(host),
+        shader: describe_shader(layout),
+    }
+}
+
+fn describe_host(host: &HostType) -> String {
+    match host {
+        HostType::Scalar(s) => format!("{s:?}"),
+        HostType::Array { element, count, .. } => format!("[{}; {count}]", describe_host(element)),
+        HostType::Struct(s) => s.name.to_owned(),
+    }
+}
+
+fn describe_shader<L: ShaderTypeLayout>(layout: L) -> String {
+    match layout.kind() {
+        TypeKind::Scalar => format!("{:?}", layout.scalar_type()),
+        TypeKind::Vector => format!("{:?}x{}", layout.scalar_type(), layout.element_count()),
+        TypeKind::Matrix => format!(
+            "{:?}x{}x{}",
+            layout.scalar_type(),
+            layout.row_count(),
+            layout.column_count()
+        ),
+        TypeKind::Array => match layout.element() {
+            Some(e) => format!("{}[{}]", describe_shader(e), layout.element_count()),
+            None => format!("array[{}]", layout.element_count()),
+        },
+        kind => layout.name().unwrap_or_else(|| format!("{kind:?}")),
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// A hand-built shader type layout.
+    #[derive(Debug, Clone)]
+    pub(crate) struct Shader {
+        pub kind: TypeKind,
+        pub size: usize,
+        pub scalar_type: ScalarType,
+        pub rows: usize,
+        pub columns: usize,
+        pub row_major: bool,
+        pub count: usize,
+        pub stride: usize,
+        pub element: Option<Box<Shader>>,
+        pub fields: Vec<(&'static str, usize, Shader)>,
+        pub name: Option<&'static str>,
+    }
+    impl ShaderTypeLayout for &Shader {
+        fn kind(self) -> TypeKind {
+            self.kind
+        }
+
+        fn size(self) -> usize {
+            self.size
+        }
+
+        fn scalar_type(self) -> ScalarType {
+            self.scalar_type
+        }
+
+        fn row_count(self) -> usize {
+            self.rows
+        }
+
+        fn column_count(self) -> usize {
+            self.columns
+        }
+
+        fn row_major(self) -> bool {
+            self.row_major
+        }
+
+        fn element_count(self) -> usize {
+            self.count
+        }
+
+        fn element_stride(self) -> usize {
+            self.stride
+        }
+
+        fn element(self) -> Option<Self> {
+            self.element.as_deref()
+        }
+
+        fn fields(self) -> impl Iterator<Item = (String, usize, Self)> {
+            self.fields
+                .iter()
+                .map(|(name, offset, layout)| ((*name).to_owned(), *offset, layout))
+        }
+
+        fn name(self) -> Option<String> {
+            self.name.map(str::to_owned)
+        }
+    }
+
+    pub(crate) fn shader(kind: TypeKind, size: usize) -> Shader {
+        Shader {
+            kind,
+            size,
+            scalar_type: ScalarType::None,
+            rows: 0,
+            columns: 0,
+            row_major: false,
+            count: 0,
+            stride: 0,
+            element: None,
+            fields: Vec::new(),
+            name: None,
+        }
+    }
+
+    pub(crate) fn shader_scalar(scalar_type: ScalarType) -> Shader {
+        Shader {
+            scalar_type,
+            ..shader(TypeKind::Scalar, scalar_size(scalar_type))
+        }
+    }
+
+    pub(crate) fn shader_vector(scalar_type: ScalarType, count: usize) -> Shader {
+        Shader {
+            scalar_type,
+            count,
+            ..shader(TypeKind::Vector, scalar_size(scalar_type) * count)
+        }
+    }
+
+    pub(crate) fn shader_array(element: Shader, count: usize, stride: usize) -> Shader {
+        Shader {
+            count,
+            stride,
+            element: Some(Box::new(element)),
+            ..shader(TypeKind::Array, stride * count)
+        }
+    }
+
+    pub(crate) fn shader_struct(size: usize, fields: Vec<(&'static str, usize, Shader)>) -> Shader {
+        Shader {
+            fields,
+            ..shader(TypeKind::Struct, size)
+        }
+    }
+
+    fn host_array(element: HostType, count: usize) -> HostType {
+        HostType::Array {
+            stride: element.size(),
+            element: Box::new(element),
+            count,
+        }
+    }
+
+    fn host_struct(
+        name: &'static str,
+        size: usize,
+        fields: Vec<(&'static str, usize, HostType)>,
+    ) -> HostType {
+        HostType::Struct(HostStruct {
+            name,
+            size,
+            fields: fields
+                .into_iter()
+                .map(|(name, offset, ty)| HostField { name, offset, ty })
+                .collect(),
+        })
+    }
+
+    const F32: HostType = HostType::Scalar(ScalarType::Float32);
+    const U32: HostType = HostType::Scalar(ScalarType::UInt32);
+
+    fn mismatch(path: &str, kind: MismatchKind) -> LayoutMismatch {
+        LayoutMismatch {
+            path: path.to_owned(),
+            kind,
+        }
+    }
+
+    #[test]
+    fn matching_struct() {
+        let host = host_struct(
+            "Params",
+            32,
+            vec![
+                ("color", 0, host_array(F32, 4)),
+                ("scale", 16, F32),
+                ("enabled", 20, U32),
+                ("_pad", 24, host_array(U32, 2)),
+            ],
+        );
+        let layout = shader_struct(
+            32,
+            vec![
+                ("color", 0, shader_vector(ScalarType::Float32, 4)),
+                ("scale", 16, shader_scalar(ScalarType::Float32)),
+                // bool is stored as a 32-bit integer
+                ("enabled", 20, shader_scalar(ScalarType::Bool)),
+                // no uniform data
+                ("texture", 0, shader(TypeKind::Resource, 0)),
+            ],
+        );
+
+        assert_eq!(verify_layout(&host, &layout), Ok(()));
+    }
+
+    #[test]
+    fn struct_mismatches() {
+        let host = host_struct(
+            "Params",
+            24,
+            vec![
+                ("color", 0, host_array(F32, 3)),
+                ("scale", 12, U32),
+                ("extra", 16, F32),
+            ],
+        );
+        let layout = shader_struct(
+            32,
+            vec![
+                ("color", 0, shader_vector(ScalarType::Float32, 3)),
+                ("scale", 16, shader_scalar(ScalarType::Float32)),
+                ("missing", 20, shader_scalar(ScalarType::Float32)),
+            ],
+        );
+
+        assert_eq!(
+            verify_layout(&host, &layout),
+            Err(vec![
+                mismatch(
+                    "Params.scale",
+                    MismatchKind::Offset {
+                        rust: 12,
+                        shader: 16
+                    }
+                ),
+                mismatch(
+                    "Params.scale",
+                    MismatchKind::ScalarType {
+                        rust: ScalarType::UInt32,
+                        shader: ScalarType::Float32,
+                    },
+                ),
+                mismatch("Params.missing", MismatchKind::MissingField),
+                mismatch("Params.extra", MismatchKind::ExtraField),
+                mismatch(
+                    "Params",
+                    MismatchKind::Size {
+                        rust: 24,
+                        shader: 32
+                    }
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn arrays() {
+        // std140 pads scalar array elements to 16 bytes
+        let layout = shader_array(shader_scalar(ScalarType::Float32), 4, 16);
+        let mut out = Vec::new();
+        compare(&host_array(F32, 4), &layout, "values", &mut out);
+        assert_eq!(
+            out,
+            [mismatch(
+                "values",
+                MismatchKind::Stride {
+                    rust: 4,
+                    shader: 16
+                }
+            )]
+        );
+
+        // a wrapper struct padding the element to the stride matches the element itself
+        let padded = host_struct(
+            "Padded",
+            16,
+            vec![("value", 0, F32), ("_pad", 4, host_array(U32, 3))],
+        );
+        assert_eq!(verify_layout(&host_array(padded, 4), &layout), Ok(()));
+
+        let mut out = Vec::new();
+        compare(&host_array(F32, 3), &layout, "values", &mut out);
+        assert_eq!(
+            out,
+            [mismatch(
+                "values",
+                MismatchKind::Shape {
+                    rust: "[Float32; 3]".to_owned(),
+                    shader: "Float32[4]".to_owned(),
+                },
+            )]
+        );
+    }
+
+    #[test]
+    fn matrices() {
+        // column-major float3x3 with padded columns
+        let layout = Shader {
+            scalar_type: ScalarType::Float32,
+            rows: 3,
+            columns: 3,
+            ..shader(TypeKind::Matrix, 48)
+        };
+
+        assert_eq!(
+            verify_layout(&host_array(host_array(F32, 4), 3), &layout),
+            Ok(())
+        );
+        assert_eq!(verify_layout(&host_array(F32, 12), &layout), Ok(()));
+        assert_eq!(
+            verify_layout(&host_array(host_array(F32, 3), 3), &layout),
+            Err(vec![mismatch(
+                "",
+                MismatchKind::Size {
+                    rust: 36,
+                    shader: 48
+                }
+            )])
+        );
+        assert_eq!(
+            verify_layout(&host_array(host_array(F32, 4), 2), &layout),
+            Err(vec![
+                mismatch(
+                    "",
+                    MismatchKind::Shape {
+                        rust: "[[Float32; 4]; 2]".to_owned(),
+                        shader: "Float32x3x3".to_owned(),
+                    },
+                ),
+                mismatch(
+                    "",
+                    MismatchKind::Size {
+                        rust: 32,
+                        shader: 48
+                    }
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn unpadded_last_column() {
+        // column-major float2x2 in a D3D constant buffer: columns at 0..8 and 16..24
+        let layout = Shader {
+            scalar_type: ScalarType::Float32,
+            rows: 2,
+            columns: 2,
+            ..shader(TypeKind::Matrix, 24)
+        };
+
+        assert_eq!(verify_layout(&host_array(F32, 6), &layout), Ok(()));
+        // same size, but puts the second column at 12
+        assert_eq!(
+            verify_layout(&host_array(host_array(F32, 3), 2), &layout),
+            Err(vec![mismatch(
+                "",
+                MismatchKind::Shape {
+                    rust: "[[Float32; 3]; 2]".to_owned(),
+                    shader: "Float32x2x2".to_owned(),
+                },
+            )])
+        );
+    }
+
+    #[test]
+    fn matrix_strides() {
+        // float2x2: scalar/std430, std140, D3D constant buffer
+        assert_eq!(matrix_stride(16, 2, 2, 4), Some(8));
+        assert_eq!(matrix_stride(32, 2, 2, 4), Some(16));
+        assert_eq!(matrix_stride(24, 2, 2, 4), Some(16));
+        // float3x3: scalar, std140/std430, D3D constant buffer
+        assert_eq!(matrix_stride(36, 3, 3, 4), Some(12));
+        assert_eq!(matrix_stride(48, 3, 3, 4), Some(16));
+        assert_eq!(matrix_stride(44, 3, 3, 4), Some(16));
+        // half3x2 (column-major, std430): columns aligned to 8 bytes
+        assert_eq!(matrix_stride(16, 2, 3, 2), Some(8));
+        assert_eq!(matrix_stride(20, 2, 2, 4), None);
+    }
+}
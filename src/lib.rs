@@ -3,10 +3,12 @@ use core::{
     mem::MaybeUninit,
     ptr::NonNull,
 };
+use std::ffi::CString;
 
 use crate::ffi::{
-    BuiltinModuleName, CompileCoreModuleFlags, SLANG_FLOATING_POINT_MODE_DEFAULT,
-    SLANG_LINE_DIRECTIVE_MODE_DEFAULT, SLANG_MATRIX_LAYOUT_ROW_MAJOR, SLANG_PROFILE_UNKNOWN,
+    BuiltinModuleName, CompileCoreModuleFlags, FileSystemContentsCallBack,
+    SLANG_FLOATING_POINT_MODE_DEFAULT, SLANG_LINE_DIRECTIVE_MODE_DEFAULT,
+    SLANG_MATRIX_LAYOUT_ROW_MAJOR, SLANG_PROFILE_UNKNOWN,
     SLANG_TARGET_FLAG_GENERATE_SPIRV_DIRECTLY, SLANG_TARGET_UNKNOWN, SlangBool, SlangFuncPtr,
     SlangInt, SlangLayoutRules, SlangMatrixLayoutMode, SlangParameterCategory, SlangReflection,
     SlangReflectionDecl, SlangReflectionFunction, SlangReflectionType, SlangReflectionTypeLayout,
@@ -14,26 +16,310 @@ use crate::ffi::{
 };
 
 pub mod ffi;
+#[cfg(feature = "dynamic-loading")]
+pub use ffi::{LoadLibraryError, load_library, missing_symbols};
 pub use ffi::{
     SlangArchiveType as ArchiveType, SlangCapabilityID as CapabilityID,
-    SlangCompileTarget as CompileTarget, SlangDeclKind as DeclKind,
-    SlangFloatingPointMode as FloatingPointMode, SlangGlobalSessionDesc as GlobalSessionDesc,
-    SlangImageFormat as ImageFormat, SlangLayoutRules as LayoutRules,
-    SlangLineDirectiveMode as LineDirectiveMode, SlangMatrixLayoutMode as MatrixLayoutMode,
-    SlangModifierID as ModifierID, SlangPassThrough as PassThrough, SlangProfileID as ProfileID,
-    SlangResourceAccess as ResourceAccess, SlangResourceShape as ResourceShape,
-    SlangSourceLanguage as SourceLanguage, SlangStage as Stage, SlangTargetFlags as TargetFlags,
+    SlangCompileTarget as CompileTarget, SlangDebugInfoFormat as DebugInfoFormat,
+    SlangDebugInfoLevel as DebugInfoLevel, SlangDeclKind as DeclKind,
+    SlangEmitSpirvMethod as EmitSpirvMethod, SlangFloatingPointMode as FloatingPointMode,
+    SlangGlobalSessionDesc as GlobalSessionDesc, SlangImageFormat as ImageFormat,
+    SlangLayoutRules as LayoutRules, SlangLineDirectiveMode as LineDirectiveMode,
+    SlangMatrixLayoutMode as MatrixLayoutMode, SlangModifierID as ModifierID,
+    SlangOSPathKind as OSPathKind, SlangOptimizationLevel as OptimizationLevel,
+    SlangPassThrough as PassThrough, SlangPathKind as PathKind, SlangPathType as PathType,
+    SlangProfileID as ProfileID, SlangResourceAccess as ResourceAccess,
+    SlangResourceShape as ResourceShape, SlangSourceLanguage as SourceLanguage,
+    SlangStage as Stage, SlangTargetFlags as TargetFlags, SlangWriterChannel as WriterChannel,
+    SlangWriterMode as WriterMode,
 };
+pub mod codegen;
+pub mod d3d12;
+pub mod diagnostics;
+pub mod layout;
 pub mod reflection;
+pub mod varying;
+pub mod vulkan;
+pub mod webgpu;
+
+mod bindings;
+mod blob;
+mod com;
+mod compile_pool;
+mod compiler_option;
+mod file_system;
+mod version;
+mod writer;
+pub use compile_pool::{CompileHandle, CompileJob, CompilePool, ModuleSource};
+pub use compiler_option::{CompilerOption, CompilerOptionEntries, VulkanShiftKind};
+pub use file_system::{
+    FileSystemExtProvider, FileSystemProvider, MemoryFileSystem, MutableFileSystemProvider,
+    StaticFileSystem,
+};
+pub use layout::ShaderLayout;
+/// Only `#[repr(C)]` structs are accepted:
+///
+/// ```compile_fail
+/// #[derive(slang::ShaderLayout)]
+/// struct Params {
+///     scale: f32,
+/// }
+/// ```
+///
+/// `#[shader(rename = "...")]` sets the name a field is matched with:
+///
+/// ```
+/// use slang::layout::{HostType, ShaderLayout};
+///
+/// #[derive(slang::ShaderLayout)]
+/// #[repr(C)]
+/// struct Params {
+///     #[shader(rename = "gScale")]
+///     scale: f32,
+///     r#type: u32,
+/// }
+///
+/// let HostType::Struct(s) = Params::host_type() else {
+///     unreachable!()
+/// };
+/// assert_eq!(s.fields[0].name, "gScale");
+/// assert_eq!(s.fields[1].name, "type");
+/// ```
+///
+/// and is the only attribute understood:
+///
+/// ```compile_fail
+/// #[derive(slang::ShaderLayout)]
+/// #[repr(C)]
+/// struct Params {
+///     #[shader(name = "gScale")]
+///     scale: f32,
+/// }
+/// ```
+#[cfg(feature = "derive")]
+pub use slang_derive::ShaderLayout;
+pub use version::{SUPPORTED_VERSIONS, SlangVersion, VersionError, check_version};
+pub use writer::{WriterProvider, Writers};
+
+/// A failed [`SlangResult`] code returned from the Slang API.
+///
+/// The raw code is kept as-is and can be taken back with [`SlangError::code`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct SlangError(SlangResult);
+impl SlangError {
+    pub const FAIL: Self = Self(ffi::SLANG_FAIL);
+    pub const NOT_IMPLEMENTED: Self = Self(ffi::SLANG_E_NOT_IMPLEMENTED);
+    pub const NO_INTERFACE: Self = Self(ffi::SLANG_E_NO_INTERFACE);
+    pub const ABORT: Self = Self(ffi::SLANG_E_ABORT);
+    pub const INVALID_HANDLE: Self = Self(ffi::SLANG_E_INVALID_HANDLE);
+    pub const INVALID_ARG: Self = Self(ffi::SLANG_E_INVALID_ARG);
+    pub const OUT_OF_MEMORY: Self = Self(ffi::SLANG_E_OUT_OF_MEMORY);
+    pub const BUFFER_TOO_SMALL: Self = Self(ffi::SLANG_E_BUFFER_TOO_SMALL);
+    pub const UNINITIALIZED: Self = Self(ffi::SLANG_E_UNINITIALIZED);
+    pub const PENDING: Self = Self(ffi::SLANG_E_PENDING);
+    pub const CANNOT_OPEN: Self = Self(ffi::SLANG_E_CANNOT_OPEN);
+    pub const NOT_FOUND: Self = Self(ffi::SLANG_E_NOT_FOUND);
+    pub const INTERNAL_FAIL: Self = Self(ffi::SLANG_E_INTERNAL_FAIL);
+    pub const NOT_AVAILABLE: Self = Self(ffi::SLANG_E_NOT_AVAILABLE);
+    pub const TIME_OUT: Self = Self(ffi::SLANG_E_TIME_OUT);
+
+    /// Wraps a raw result code. Returns `None` if `code` is not a failure code.
+    #[inline]
+    pub const fn from_code(code: SlangResult) -> Option<Self> {
+        if code < 0 { Some(Self(code)) } else { None }
+    }
+
+    /// The raw result code
+    #[inline(always)]
+    pub const fn code(self) -> SlangResult {
+        self.0
+    }
+
+    /// The facility part of the result code
+    #[inline(always)]
+    pub const fn facility(self) -> ffi::SlangResultFacility {
+        ffi::slang_get_result_facility(self.0)
+    }
+
+    /// The facility-specific part of the result code
+    #[inline(always)]
+    pub const fn facility_code(self) -> u32 {
+        ffi::slang_get_result_code(self.0)
+    }
+
+    /// The symbolic name of the code as defined in slang.h, if it is a well-known one.
+    pub const fn name(self) -> Option<&'static str> {
+        Some(match self.0 {
+            ffi::SLANG_FAIL => "SLANG_FAIL",
+            ffi::SLANG_E_NOT_IMPLEMENTED => "SLANG_E_NOT_IMPLEMENTED",
+            ffi::SLANG_E_NO_INTERFACE => "SLANG_E_NO_INTERFACE",
+            ffi::SLANG_E_ABORT => "SLANG_E_ABORT",
+            ffi::SLANG_E_INVALID_HANDLE => "SLANG_E_INVALID_HANDLE",
+            ffi::SLANG_E_INVALID_ARG => "SLANG_E_INVALID_ARG",
+            ffi::SLANG_E_OUT_OF_MEMORY => "SLANG_E_OUT_OF_MEMORY",
+            ffi::SLANG_E_BUFFER_TOO_SMALL => "SLANG_E_BUFFER_TOO_SMALL",
+            ffi::SLANG_E_UNINITIALIZED => "SLANG_E_UNINITIALIZED",
+            ffi::SLANG_E_PENDING => "SLANG_E_PENDING",
+            ffi::SLANG_E_CANNOT_OPEN => "SLANG_E_CANNOT_OPEN",
+            ffi::SLANG_E_NOT_FOUND => "SLANG_E_NOT_FOUND",
+            ffi::SLANG_E_INTERNAL_FAIL => "SLANG_E_INTERNAL_FAIL",
+            ffi::SLANG_E_NOT_AVAILABLE => "SLANG_E_NOT_AVAILABLE",
+            ffi::SLANG_E_TIME_OUT => "SLANG_E_TIME_OUT",
+            _ => return None,
+        })
+    }
 
-pub type Result<T> = core::result::Result<T, SlangResult>;
+    const fn description(self) -> Option<&'static str> {
+        Some(match self.0 {
+            ffi::SLANG_FAIL => "unspecified failure",
+            ffi::SLANG_E_NOT_IMPLEMENTED => "functionality is not implemented",
+            ffi::SLANG_E_NO_INTERFACE => "interface not found",
+            ffi::SLANG_E_ABORT => "operation was aborted",
+            ffi::SLANG_E_INVALID_HANDLE => "invalid handle",
+            ffi::SLANG_E_INVALID_ARG => "invalid argument",
+            ffi::SLANG_E_OUT_OF_MEMORY => "out of memory",
+            ffi::SLANG_E_BUFFER_TOO_SMALL => "buffer too small",
+            ffi::SLANG_E_UNINITIALIZED => "uninitialized result",
+            ffi::SLANG_E_PENDING => "result is pending",
+            ffi::SLANG_E_CANNOT_OPEN => "cannot open",
+            ffi::SLANG_E_NOT_FOUND => "not found",
+            ffi::SLANG_E_INTERNAL_FAIL => "internal failure",
+            ffi::SLANG_E_NOT_AVAILABLE => "not available",
+            ffi::SLANG_E_TIME_OUT => "timed out",
+            _ => return None,
+        })
+    }
+}
+impl core::fmt::Debug for SlangError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.name() {
+            Some(n) => write!(f, "SlangError({n})"),
+            None => write!(f, "SlangError({:#010x})", self.0 as u32),
+        }
+    }
+}
+impl core::fmt::Display for SlangError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match (self.name(), self.description()) {
+            (Some(n), Some(d)) => write!(f, "{d} ({n}, {:#010x})", self.0 as u32),
+            _ => write!(
+                f,
+                "slang error {:#010x} (facility {:#x}, code {:#x})",
+                self.0 as u32,
+                self.facility(),
+                self.facility_code()
+            ),
+        }
+    }
+}
+impl std::error::Error for SlangError {}
+impl From<SlangError> for SlangResult {
+    #[inline(always)]
+    fn from(e: SlangError) -> Self {
+        e.0
+    }
+}
+
+pub type Result<T> = core::result::Result<T, SlangError>;
 #[inline(always)]
 pub(crate) const fn rw(r: SlangResult) -> Result<SlangResult> {
-    if r >= 0 { Ok(r) } else { Err(r) }
+    if r >= 0 { Ok(r) } else { Err(SlangError(r)) }
 }
 
-#[repr(C)]
+/// A failure together with the diagnostic output emitted by the call that produced it.
+#[derive(Debug, Clone)]
+pub struct CompileError {
+    pub error: SlangError,
+    pub diagnostics: Option<String>,
+}
+impl core::fmt::Display for CompileError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.diagnostics {
+            Some(ref d) => write!(f, "{}:\n{}", self.error, d.trim_end()),
+            None => core::fmt::Display::fmt(&self.error, f),
+        }
+    }
+}
+impl CompileError {
+    /// The diagnostic output parsed into structured records.
+    #[inline]
+    pub fn parsed_diagnostics(&self) -> Vec<diagnostics::Diagnostic> {
+        self.diagnostics
+            .as_deref()
+            .map_or_else(Vec::new, diagnostics::parse)
+    }
+}
+impl std::error::Error for CompileError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+impl From<SlangError> for CompileError {
+    #[inline]
+    fn from(error: SlangError) -> Self {
+        Self {
+            error,
+            diagnostics: None,
+        }
+    }
+}
+
+/// A successful value together with the diagnostic output (e.g. warnings) emitted by the call.
 #[derive(Debug, Clone)]
+pub struct Diagnosed<T> {
+    pub value: T,
+    pub diagnostics: Option<String>,
+}
+impl<T> Diagnosed<T> {
+    #[inline(always)]
+    pub fn into_value(self) -> T {
+        self.value
+    }
+
+    /// The diagnostic output parsed into structured records.
+    #[inline]
+    pub fn parsed_diagnostics(&self) -> Vec<diagnostics::Diagnostic> {
+        self.diagnostics
+            .as_deref()
+            .map_or_else(Vec::new, diagnostics::parse)
+    }
+
+    #[inline]
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Diagnosed<U> {
+        Diagnosed {
+            value: f(self.value),
+            diagnostics: self.diagnostics,
+        }
+    }
+}
+
+pub type DiagnosedResult<T> = core::result::Result<Diagnosed<T>, CompileError>;
+
+/// Runs `f` with a diagnostics out-parameter and attaches the decoded text to either side of the result.
+fn diagnosed<T>(
+    f: impl FnOnce(Option<&mut MaybeUninit<Option<IBlobPtr>>>) -> Result<T>,
+) -> DiagnosedResult<T> {
+    let mut d = MaybeUninit::new(None);
+    let r = f(Some(&mut d));
+    let diagnostics = unsafe { d.assume_init() }.map(|b| blob_to_string(&b));
+
+    match r {
+        Ok(value) => Ok(Diagnosed { value, diagnostics }),
+        Err(error) => Err(CompileError { error, diagnostics }),
+    }
+}
+
+/// Decodes a blob holding (possibly NUL-terminated) text.
+pub(crate) fn blob_to_string(blob: &impl IBlob) -> String {
+    let bytes = blob.as_bytes();
+    let bytes = bytes.split(|&c| c == 0).next().unwrap_or(bytes);
+
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct SlangUUID {
     pub data1: u32,
     pub data2: u16,
@@ -68,6 +354,11 @@ pub unsafe trait SlangInterfacePtr: Sized {
     }
 }
 
+/// Declares an owning (reference-counting) pointer type for a Slang interface.
+///
+/// The pointers are `Clone` and Slang's reference counts are not atomic, so none of them are `Send` or `Sync`: move
+/// sole references with [`SendBlob`]/[`SendSession`]/[`SendProgram`], copy the data out (e.g. [`IBlob::as_bytes`]), or use
+/// [`SharedSession`] to work with Slang from several threads.
 macro_rules! slang_interface_ptr {
     ($name: ident, $uuid: expr, $vtbl: ty) => {
         #[derive(Debug)]
@@ -145,6 +436,16 @@ pub trait IUnknown: SlangInterfacePtr {
     }
 }
 
+/// Whether `p` is the only reference to its object.
+pub(crate) fn is_sole_reference(p: &impl IUnknown) -> bool {
+    let count = p.add_ref();
+    unsafe {
+        p.release();
+    }
+
+    count == 2
+}
+
 slang_interface_ptr!(IUnknownPtr, UNKNOWN_IID, IUnknownVTable);
 impl IUnknown for IUnknownPtr {
     #[inline(always)]
@@ -153,6 +454,12 @@ impl IUnknown for IUnknownPtr {
     }
 }
 
+pub const CASTABLE_IID: SlangUUID = SlangUUID::new(
+    0x87ede0e1,
+    0x4852,
+    0x44b0,
+    [0x8b, 0xf2, 0xcb, 0x31, 0x87, 0x4d, 0xe2, 0x39],
+);
 #[repr(C)]
 pub struct ICastableVTable {
     pub base: IUnknownVTable,
@@ -201,6 +508,25 @@ pub trait IBlob: IUnknown {
     fn get_buffer_size(&self) -> usize {
         unsafe { (IBlob::vt(self).get_buffer_size)(self.thisptr()) }
     }
+
+    /// The contents of the blob.
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
+        let size = self.get_buffer_size();
+        if size == 0 {
+            return &[];
+        }
+
+        unsafe { core::slice::from_raw_parts(self.get_buffer_pointer().cast(), size) }
+    }
+
+    /// The contents of the blob as text, without a trailing NUL terminator.
+    #[inline]
+    fn as_str(&self) -> core::result::Result<&str, core::str::Utf8Error> {
+        let bytes = self.as_bytes();
+
+        core::str::from_utf8(bytes.strip_suffix(&[0]).unwrap_or(bytes))
+    }
 }
 
 slang_interface_ptr!(IBlobPtr, BLOB_IID, IBlobVTable);
@@ -217,6 +543,44 @@ impl IBlob for IBlobPtr {
     }
 }
 
+/// A blob that can be moved to another thread.
+///
+/// Blobs do not refer to other Slang objects, so the only reference to one can be moved freely. Blobs Slang keeps a
+/// reference to (e.g. the target code cached by a program) can be sent as a copy with [`copy_from`](Self::copy_from).
+#[derive(Debug)]
+pub struct SendBlob(IBlobPtr);
+// `new` checks that no other reference to the blob exists, and no new one can be made without `into_inner`.
+unsafe impl Send for SendBlob {}
+impl SendBlob {
+    /// Fails, giving the blob back, if other references to it exist (clones of the pointer, or ones kept by Slang).
+    pub fn new(blob: IBlobPtr) -> core::result::Result<Self, IBlobPtr> {
+        if is_sole_reference(&blob) {
+            Ok(Self(blob))
+        } else {
+            Err(blob)
+        }
+    }
+
+    /// Copies the contents of `blob` into a new Rust-owned blob (see [`IBlobPtr::from_bytes`]).
+    #[inline]
+    pub fn copy_from(blob: &impl IBlob) -> Self {
+        Self(IBlobPtr::from_bytes(blob.as_bytes()))
+    }
+
+    #[inline(always)]
+    pub fn into_inner(self) -> IBlobPtr {
+        self.0
+    }
+}
+impl TryFrom<IBlobPtr> for SendBlob {
+    type Error = IBlobPtr;
+
+    #[inline]
+    fn try_from(blob: IBlobPtr) -> core::result::Result<Self, IBlobPtr> {
+        Self::new(blob)
+    }
+}
+
 pub const FILE_SYSTEM_IID: SlangUUID = SlangUUID::new(
     0x003a09fc,
     0x3a4d,
@@ -265,6 +629,271 @@ impl IFileSystem for IFileSystemPtr {
     }
 }
 
+pub const FILE_SYSTEM_EXT_IID: SlangUUID = SlangUUID::new(
+    0x5fb632d2,
+    0x979d,
+    0x4481,
+    [0x9f, 0xee, 0x66, 0x3c, 0x3f, 0x14, 0x49, 0xe1],
+);
+#[repr(C)]
+pub struct IFileSystemExtVTable {
+    pub base: IFileSystemVTable,
+    pub get_file_unique_identity: unsafe extern "system" fn(
+        this: *mut c_void,
+        path: *const c_char,
+        out_unique_identity: *mut *mut c_void,
+    ) -> SlangResult,
+    pub calc_combined_path: unsafe extern "system" fn(
+        this: *mut c_void,
+        from_path_type: PathType,
+        from_path: *const c_char,
+        path: *const c_char,
+        path_out: *mut *mut c_void,
+    ) -> SlangResult,
+    pub get_path_type: unsafe extern "system" fn(
+        this: *mut c_void,
+        path: *const c_char,
+        path_type_out: *mut PathType,
+    ) -> SlangResult,
+    pub get_path: unsafe extern "system" fn(
+        this: *mut c_void,
+        kind: PathKind,
+        path: *const c_char,
+        out_path: *mut *mut c_void,
+    ) -> SlangResult,
+    pub clear_cache: unsafe extern "system" fn(this: *mut c_void),
+    pub enumerate_path_contents: unsafe extern "system" fn(
+        this: *mut c_void,
+        path: *const c_char,
+        callback: FileSystemContentsCallBack,
+        user_data: *mut c_void,
+    ) -> SlangResult,
+    pub get_os_path_kind: unsafe extern "system" fn(this: *mut c_void) -> OSPathKind,
+}
+pub trait IFileSystemExt: IFileSystem {
+    fn vt(&self) -> &IFileSystemExtVTable;
+
+    /// Get a uniquely identifying representation of the file at `path`.
+    ///
+    /// Two paths referring to the same file yield the same identity, which is what `#include` de-duplication is based on.
+    fn get_file_unique_identity(&self, path: &CStr) -> Result<IBlobPtr> {
+        let mut o = MaybeUninit::uninit();
+        rw(unsafe {
+            (IFileSystemExt::vt(self).get_file_unique_identity)(
+                self.thisptr(),
+                path.as_ptr(),
+                o.as_mut_ptr(),
+            )
+        })?;
+
+        Ok(IBlobPtr(unsafe { NonNull::new_unchecked(o.assume_init()) }))
+    }
+
+    /// Combine `path` with the location `from_path` (a file or directory, as given by `from_path_type`).
+    fn calc_combined_path(
+        &self,
+        from_path_type: PathType,
+        from_path: &CStr,
+        path: &CStr,
+    ) -> Result<IBlobPtr> {
+        let mut o = MaybeUninit::uninit();
+        rw(unsafe {
+            (IFileSystemExt::vt(self).calc_combined_path)(
+                self.thisptr(),
+                from_path_type,
+                from_path.as_ptr(),
+                path.as_ptr(),
+                o.as_mut_ptr(),
+            )
+        })?;
+
+        Ok(IBlobPtr(unsafe { NonNull::new_unchecked(o.assume_init()) }))
+    }
+
+    fn get_path_type(&self, path: &CStr) -> Result<PathType> {
+        let mut o = MaybeUninit::uninit();
+        rw(unsafe {
+            (IFileSystemExt::vt(self).get_path_type)(self.thisptr(), path.as_ptr(), o.as_mut_ptr())
+        })?;
+
+        Ok(unsafe { o.assume_init() })
+    }
+
+    fn get_path(&self, kind: PathKind, path: &CStr) -> Result<IBlobPtr> {
+        let mut o = MaybeUninit::uninit();
+        rw(unsafe {
+            (IFileSystemExt::vt(self).get_path)(self.thisptr(), kind, path.as_ptr(), o.as_mut_ptr())
+        })?;
+
+        Ok(IBlobPtr(unsafe { NonNull::new_unchecked(o.assume_init()) }))
+    }
+
+    #[inline]
+    fn clear_cache(&self) {
+        unsafe { (IFileSystemExt::vt(self).clear_cache)(self.thisptr()) }
+    }
+
+    /// Calls `callback` with the type and name of every item directly contained in the directory at `path`.
+    fn enumerate_path_contents(
+        &self,
+        path: &CStr,
+        mut callback: impl FnMut(PathType, &CStr),
+    ) -> Result<()> {
+        extern "C" fn trampoline(path_type: PathType, name: *const c_char, user_data: *mut c_void) {
+            let callback = unsafe { &mut *user_data.cast::<&mut dyn FnMut(PathType, &CStr)>() };
+
+            callback(path_type, unsafe { CStr::from_ptr(name) });
+        }
+
+        let mut callback: &mut dyn FnMut(PathType, &CStr) = &mut callback;
+        rw(unsafe {
+            (IFileSystemExt::vt(self).enumerate_path_contents)(
+                self.thisptr(),
+                path.as_ptr(),
+                trampoline,
+                (&mut callback as *mut &mut dyn FnMut(PathType, &CStr)).cast(),
+            )
+        })?;
+
+        Ok(())
+    }
+
+    #[inline]
+    fn get_os_path_kind(&self) -> OSPathKind {
+        unsafe { (IFileSystemExt::vt(self).get_os_path_kind)(self.thisptr()) }
+    }
+}
+
+slang_interface_ptr!(IFileSystemExtPtr, FILE_SYSTEM_EXT_IID, IFileSystemExtVTable);
+impl IUnknown for IFileSystemExtPtr {
+    #[inline(always)]
+    fn vt(&self) -> &IUnknownVTable {
+        &self.vtable().base.base.base
+    }
+}
+impl ICastable for IFileSystemExtPtr {
+    #[inline(always)]
+    fn vt(&self) -> &ICastableVTable {
+        &self.vtable().base.base
+    }
+}
+impl IFileSystem for IFileSystemExtPtr {
+    #[inline(always)]
+    fn vt(&self) -> &IFileSystemVTable {
+        &self.vtable().base
+    }
+}
+impl IFileSystemExt for IFileSystemExtPtr {
+    #[inline(always)]
+    fn vt(&self) -> &IFileSystemExtVTable {
+        self.vtable()
+    }
+}
+
+pub const MUTABLE_FILE_SYSTEM_IID: SlangUUID = SlangUUID::new(
+    0xa058675c,
+    0x1d65,
+    0x452a,
+    [0x84, 0x58, 0xcc, 0xde, 0xd1, 0x42, 0x71, 0x05],
+);
+#[repr(C)]
+pub struct IMutableFileSystemVTable {
+    pub base: IFileSystemExtVTable,
+    pub save_file: unsafe extern "system" fn(
+        this: *mut c_void,
+        path: *const c_char,
+        data: *const c_void,
+        size: usize,
+    ) -> SlangResult,
+    pub save_file_blob: unsafe extern "system" fn(
+        this: *mut c_void,
+        path: *const c_char,
+        data_blob: *mut c_void,
+    ) -> SlangResult,
+    pub remove: unsafe extern "system" fn(this: *mut c_void, path: *const c_char) -> SlangResult,
+    pub create_directory:
+        unsafe extern "system" fn(this: *mut c_void, path: *const c_char) -> SlangResult,
+}
+pub trait IMutableFileSystem: IFileSystemExt {
+    fn vt(&self) -> &IMutableFileSystemVTable;
+
+    fn save_file(&self, path: &CStr, data: &[u8]) -> Result<()> {
+        rw(unsafe {
+            (IMutableFileSystem::vt(self).save_file)(
+                self.thisptr(),
+                path.as_ptr(),
+                data.as_ptr().cast(),
+                data.len(),
+            )
+        })?;
+
+        Ok(())
+    }
+
+    fn save_file_blob(&self, path: &CStr, data_blob: &impl IBlob) -> Result<()> {
+        rw(unsafe {
+            (IMutableFileSystem::vt(self).save_file_blob)(
+                self.thisptr(),
+                path.as_ptr(),
+                data_blob.thisptr(),
+            )
+        })?;
+
+        Ok(())
+    }
+
+    /// Remove the file or (empty) directory at `path`.
+    fn remove(&self, path: &CStr) -> Result<()> {
+        rw(unsafe { (IMutableFileSystem::vt(self).remove)(self.thisptr(), path.as_ptr()) })?;
+
+        Ok(())
+    }
+
+    fn create_directory(&self, path: &CStr) -> Result<()> {
+        rw(unsafe {
+            (IMutableFileSystem::vt(self).create_directory)(self.thisptr(), path.as_ptr())
+        })?;
+
+        Ok(())
+    }
+}
+
+slang_interface_ptr!(
+    IMutableFileSystemPtr,
+    MUTABLE_FILE_SYSTEM_IID,
+    IMutableFileSystemVTable
+);
+impl IUnknown for IMutableFileSystemPtr {
+    #[inline(always)]
+    fn vt(&self) -> &IUnknownVTable {
+        &self.vtable().base.base.base.base
+    }
+}
+impl ICastable for IMutableFileSystemPtr {
+    #[inline(always)]
+    fn vt(&self) -> &ICastableVTable {
+        &self.vtable().base.base.base
+    }
+}
+impl IFileSystem for IMutableFileSystemPtr {
+    #[inline(always)]
+    fn vt(&self) -> &IFileSystemVTable {
+        &self.vtable().base.base
+    }
+}
+impl IFileSystemExt for IMutableFileSystemPtr {
+    #[inline(always)]
+    fn vt(&self) -> &IFileSystemExtVTable {
+        &self.vtable().base
+    }
+}
+impl IMutableFileSystem for IMutableFileSystemPtr {
+    #[inline(always)]
+    fn vt(&self) -> &IMutableFileSystemVTable {
+        self.vtable()
+    }
+}
+
 pub const SHARED_LIBRARY_IID: SlangUUID = SlangUUID::new(
     0x70dbc7c4,
     0xdc3b,
@@ -373,22 +1002,23 @@ pub const WRITER_IID: SlangUUID = SlangUUID::new(
 );
 #[repr(C)]
 pub struct IWriterVTable {
-    base: IUnknownVTable,
-    begin_append_buffer:
+    pub base: IUnknownVTable,
+    pub begin_append_buffer:
         unsafe extern "system" fn(this: *mut c_void, max_num_chars: usize) -> *mut c_char,
-    end_append_buffer: unsafe extern "system" fn(
+    pub end_append_buffer: unsafe extern "system" fn(
         this: *mut c_void,
         buffer: *mut c_char,
         num_chars: usize,
     ) -> SlangResult,
-    write: unsafe extern "system" fn(
+    pub write: unsafe extern "system" fn(
         this: *mut c_void,
         chars: *const c_char,
         num_chars: usize,
     ) -> SlangResult,
-    flush: unsafe extern "system" fn(this: *mut c_void),
-    is_console: unsafe extern "system" fn(this: *mut c_void) -> SlangBool,
-    set_mode: unsafe extern "system" fn(this: *mut c_void, mode: SlangWriterMode) -> SlangResult,
+    pub flush: unsafe extern "system" fn(this: *mut c_void),
+    pub is_console: unsafe extern "system" fn(this: *mut c_void) -> SlangBool,
+    pub set_mode:
+        unsafe extern "system" fn(this: *mut c_void, mode: SlangWriterMode) -> SlangResult,
 }
 /// A stream typically of text, used for outputting diagnostic as well as other information.
 pub trait IWriter: IUnknown {
@@ -438,6 +1068,20 @@ pub trait IWriter: IUnknown {
     }
 }
 
+slang_interface_ptr!(IWriterPtr, WRITER_IID, IWriterVTable);
+impl IUnknown for IWriterPtr {
+    #[inline(always)]
+    fn vt(&self) -> &IUnknownVTable {
+        &self.vtable().base
+    }
+}
+impl IWriter for IWriterPtr {
+    #[inline(always)]
+    fn vt(&self) -> &IWriterVTable {
+        self.vtable()
+    }
+}
+
 pub const PROFILER_IID: SlangUUID = SlangUUID::new(
     0x197772c7,
     0x0155,
@@ -479,6 +1123,31 @@ pub trait IProfiler: IUnknown {
 #[deprecated = "old interface"]
 #[repr(transparent)]
 pub struct ICompileRequestObject(NonNull<c_void>);
+#[allow(deprecated)]
+impl ICompileRequestObject {
+    /// Routes the output of `channel` to `writer`, or back to the default destination if `None`.
+    #[inline]
+    pub fn set_writer(&self, channel: WriterChannel, writer: Option<&IWriterPtr>) {
+        unsafe {
+            ffi::spSetWriter(
+                self.0.as_ptr(),
+                channel,
+                writer.map_or(core::ptr::null_mut(), |w| w.thisptr()),
+            )
+        }
+    }
+
+    /// The writer currently installed for `channel`.
+    #[inline]
+    pub fn get_writer(&self, channel: WriterChannel) -> Option<IWriterPtr> {
+        let w = NonNull::new(unsafe { ffi::spGetWriter(self.0.as_ptr(), channel) })?;
+        let w = IWriterPtr(w);
+        // returned without a reference of our own
+        w.add_ref();
+
+        Some(w)
+    }
+}
 
 pub const GLOBAL_SESSION_IID: SlangUUID = SlangUUID::new(
     0xc140b5fd,
@@ -611,7 +1280,16 @@ pub trait IGlobalSession: IUnknown {
     fn vt(&self) -> &IGlobalSessionVTable;
 
     /// Create a new session for loading and compiling code.
-    fn create_session(&self, desc: &SessionDesc) -> Result<ISessionPtr> {
+    #[inline]
+    fn create_session(&self, desc: &SessionDescBuilder) -> Result<ISessionPtr> {
+        desc.with_desc(|d| unsafe { self.create_session_raw(d) })
+    }
+
+    /// Create a new session from a raw [`SessionDesc`].
+    ///
+    /// # Safety
+    /// Every pointer in `desc` must be valid for the duration of the call.
+    unsafe fn create_session_raw(&self, desc: &SessionDesc) -> Result<ISessionPtr> {
         let mut o = MaybeUninit::uninit();
         rw(unsafe {
             (IGlobalSession::vt(self).create_session)(self.thisptr(), desc, o.as_mut_ptr())
@@ -1209,13 +1887,27 @@ pub trait ISession: IUnknown {
         .map(IModulePtr)
     }
 
+    /// [`load_module`](ISession::load_module) with its diagnostic output decoded into the result.
+    fn load_module_with_diagnostics(&self, module_name: &CStr) -> DiagnosedResult<IModulePtr> {
+        diagnosed(|d| self.load_module(module_name, d).ok_or(SlangError::FAIL))
+    }
+
+    /// [`load_module`](ISession::load_module) with its diagnostic output sent to `writers`.
+    fn load_module_with_writers(
+        &self,
+        module_name: &CStr,
+        writers: &Writers,
+    ) -> Option<IModulePtr> {
+        writers.run(|d| self.load_module(module_name, d))
+    }
+
     #[inline]
     fn load_module_from_source(
         &self,
         module_name: &CStr,
         path: &CStr,
         source: &impl IBlob,
-        out_diagnostics: Option<&mut MaybeUninit<IBlobPtr>>,
+        out_diagnostics: Option<&mut MaybeUninit<Option<IBlobPtr>>>,
     ) -> Option<IModulePtr> {
         NonNull::new(unsafe {
             (ISession::vt(self).load_module_from_source)(
@@ -1229,6 +1921,19 @@ pub trait ISession: IUnknown {
         .map(IModulePtr)
     }
 
+    /// [`load_module_from_source`](ISession::load_module_from_source) with its diagnostic output decoded into the result.
+    fn load_module_from_source_with_diagnostics(
+        &self,
+        module_name: &CStr,
+        path: &CStr,
+        source: &impl IBlob,
+    ) -> DiagnosedResult<IModulePtr> {
+        diagnosed(|d| {
+            self.load_module_from_source(module_name, path, source, d)
+                .ok_or(SlangError::FAIL)
+        })
+    }
+
     fn create_composite_component_type(
         &self,
         component_types: &[IComponentTypePtr],
@@ -1250,69 +1955,85 @@ pub trait ISession: IUnknown {
         }))
     }
 
-    #[inline]
+    /// [`create_composite_component_type`](ISession::create_composite_component_type) with its diagnostic output
+    /// decoded into the result.
+    fn create_composite_component_type_with_diagnostics(
+        &self,
+        component_types: &[IComponentTypePtr],
+    ) -> DiagnosedResult<IComponentTypePtr> {
+        diagnosed(|d| self.create_composite_component_type(component_types, d))
+    }
+
+    /// Returns `None` if the type could not be specialized (see `out_diagnostics`).
     fn specialize_type(
         &self,
-        r#type: &mut SlangReflectionType,
+        r#type: reflection::Type<'_>,
         specialization_args: &[SpecializationArg],
-        out_diagnostics: Option<&mut MaybeUninit<IBlobPtr>>,
-    ) -> &mut SlangReflectionType {
-        unsafe {
-            &mut *(ISession::vt(self).specialize_type)(
+        out_diagnostics: Option<&mut MaybeUninit<Option<IBlobPtr>>>,
+    ) -> Option<reflection::Type<'_>> {
+        let p = unsafe {
+            (ISession::vt(self).specialize_type)(
                 self.thisptr(),
-                r#type,
+                r#type.as_raw(),
                 specialization_args.as_ptr(),
                 specialization_args.len() as _,
                 out_diagnostics.map_or_else(core::ptr::null_mut, MaybeUninit::as_mut_ptr) as _,
             )
-        }
+        };
+        unsafe { reflection::Type::from_nullable(p) }
     }
 
-    #[inline]
+    /// Returns `None` if the layout could not be computed (see `out_diagnostics`).
     fn get_type_layout(
         &self,
-        r#type: &mut SlangReflectionType,
+        r#type: reflection::Type<'_>,
         target_index: SlangInt,
         layout_rules: SlangLayoutRules,
-        out_diagnostics: Option<&mut MaybeUninit<IBlobPtr>>,
-    ) -> &mut reflection::TypeLayout {
-        unsafe {
-            reflection::TypeLayout::from_mut_ptr((ISession::vt(self).get_type_layout)(
+        out_diagnostics: Option<&mut MaybeUninit<Option<IBlobPtr>>>,
+    ) -> Option<reflection::TypeLayout<'_>> {
+        let p = unsafe {
+            (ISession::vt(self).get_type_layout)(
                 self.thisptr(),
-                r#type,
+                r#type.as_raw(),
                 target_index,
                 layout_rules,
                 out_diagnostics.map_or_else(core::ptr::null_mut, MaybeUninit::as_mut_ptr) as _,
-            ))
-        }
+            )
+        };
+        unsafe { reflection::TypeLayout::from_nullable(p) }
     }
 
-    #[inline]
+    /// Returns `None` if the container type could not be created (see `out_diagnostics`).
     fn get_container_type(
         &self,
-        element_type: &mut SlangReflectionType,
+        element_type: reflection::Type<'_>,
         container_type: ContainerType,
-        out_diagnostics: Option<&mut MaybeUninit<IBlobPtr>>,
-    ) -> &mut SlangReflectionType {
-        unsafe {
-            &mut *(ISession::vt(self).get_container_type)(
+        out_diagnostics: Option<&mut MaybeUninit<Option<IBlobPtr>>>,
+    ) -> Option<reflection::Type<'_>> {
+        let p = unsafe {
+            (ISession::vt(self).get_container_type)(
                 self.thisptr(),
-                element_type,
+                element_type.as_raw(),
                 container_type,
                 out_diagnostics.map_or_else(core::ptr::null_mut, MaybeUninit::as_mut_ptr) as _,
             )
-        }
+        };
+        unsafe { reflection::Type::from_nullable(p) }
     }
 
-    #[inline]
-    fn get_dynamic_type(&self) -> &mut SlangReflectionType {
-        unsafe { &mut *(ISession::vt(self).get_dynamic_type)(self.thisptr()) }
+    fn get_dynamic_type(&self) -> Option<reflection::Type<'_>> {
+        let p = unsafe { (ISession::vt(self).get_dynamic_type)(self.thisptr()) };
+        unsafe { reflection::Type::from_nullable(p) }
     }
 
-    fn get_type_rtti_mangled_name(&self, r#type: &mut SlangReflectionType) -> Result<IBlobPtr> {
+    fn get_type_rtti_mangled_name(&self, r#type: reflection::Type<'_>) -> Result<IBlobPtr> {
         let mut o = MaybeUninit::uninit();
         rw(unsafe {
-            (ISession::vt(self).get_type_rtti_mangled_name)(self.thisptr(), r#type, o.as_mut_ptr())
+            (ISession::vt(self).get_type_rtti_mangled_name)(
+                self.thisptr(),
+                r#type.as_raw(),
+                o.as_mut_ptr(),
+            )
         })?;
 
         Ok(IBlobPtr(unsafe { NonNull::new_unchecked(o.assume_init()) }))
@@ -1320,15 +2041,15 @@ pub trait ISession: IUnknown {
 
     fn get_type_conformance_witness_mangled_name(
         &self,
-        r#type: &mut SlangReflectionType,
-        interface_type: &mut SlangReflectionType,
+        r#type: reflection::Type<'_>,
+        interface_type: reflection::Type<'_>,
     ) -> Result<IBlobPtr> {
         let mut o = MaybeUninit::uninit();
         rw(unsafe {
             (ISession::vt(self).get_type_conformance_witness_mangled_name)(
                 self.thisptr(),
-                r#type,
-                interface_type,
+                r#type.as_raw(),
+                interface_type.as_raw(),
                 o.as_mut_ptr(),
             )
         })?;
@@ -1338,15 +2059,15 @@ pub trait ISession: IUnknown {
 
     fn get_type_conformance_witness_sequential_id(
         &self,
-        r#type: &mut SlangReflectionType,
-        interface_type: &mut SlangReflectionType,
+        r#type: reflection::Type<'_>,
+        interface_type: reflection::Type<'_>,
     ) -> Result<u32> {
         let mut o = MaybeUninit::uninit();
         rw(unsafe {
             (ISession::vt(self).get_type_conformance_witness_sequential_id)(
                 self.thisptr(),
-                r#type,
-                interface_type,
+                r#type.as_raw(),
+                interface_type.as_raw(),
                 o.as_mut_ptr(),
             )
         })?;
@@ -1366,8 +2087,8 @@ pub trait ISession: IUnknown {
 
     fn create_type_conformance_component_type(
         &self,
-        r#type: &mut SlangReflectionType,
-        interface_type: &mut SlangReflectionType,
+        r#type: reflection::Type<'_>,
+        interface_type: reflection::Type<'_>,
         out_conformance: &mut [MaybeUninit<ITypeConformancePtr>],
         conformance_id_override: SlangInt,
         out_diagnostics: Option<&mut MaybeUninit<Option<IBlobPtr>>>,
@@ -1375,8 +2096,8 @@ pub trait ISession: IUnknown {
         rw(unsafe {
             (ISession::vt(self).create_type_conformance_component_type)(
                 self.thisptr(),
-                r#type,
-                interface_type,
+                r#type.as_raw(),
+                interface_type.as_raw(),
                 out_conformance.as_mut_ptr() as _,
                 conformance_id_override,
                 out_diagnostics.map_or_else(core::ptr::null_mut, MaybeUninit::as_mut_ptr) as _,
@@ -1406,6 +2127,20 @@ pub trait ISession: IUnknown {
         .map(IModulePtr)
     }
 
+    /// [`load_module_from_ir_blob`](ISession::load_module_from_ir_blob) with its diagnostic output decoded into the
+    /// result.
+    fn load_module_from_ir_blob_with_diagnostics(
+        &self,
+        module_name: &CStr,
+        path: &CStr,
+        source: &impl IBlob,
+    ) -> DiagnosedResult<IModulePtr> {
+        diagnosed(|d| {
+            self.load_module_from_ir_blob(module_name, path, source, d)
+                .ok_or(SlangError::FAIL)
+        })
+    }
+
     #[inline]
     fn get_loaded_module_count(&self) -> SlangInt {
         unsafe { (ISession::vt(self).get_loaded_module_count)(self.thisptr()) }
@@ -1456,17 +2191,31 @@ pub trait ISession: IUnknown {
         .map(IModulePtr)
     }
 
+    /// [`load_module_from_source_string`](ISession::load_module_from_source_string) with its diagnostic output
+    /// decoded into the result.
+    fn load_module_from_source_string_with_diagnostics(
+        &self,
+        module_name: &CStr,
+        path: &CStr,
+        string: &CStr,
+    ) -> DiagnosedResult<IModulePtr> {
+        diagnosed(|d| {
+            self.load_module_from_source_string(module_name, path, string, d)
+                .ok_or(SlangError::FAIL)
+        })
+    }
+
     fn get_dynamic_object_rtti_bytes(
         &self,
-        r#type: &mut SlangReflectionType,
-        interface_type: &mut SlangReflectionType,
+        r#type: reflection::Type<'_>,
+        interface_type: reflection::Type<'_>,
         out_rtti_data_buffer: &mut [MaybeUninit<u32>],
     ) -> Result<()> {
         rw(unsafe {
             (ISession::vt(self).get_dynamic_object_rtti_bytes)(
                 self.thisptr(),
-                r#type,
-                interface_type,
+                r#type.as_raw(),
+                interface_type.as_raw(),
                 out_rtti_data_buffer.as_mut_ptr() as _,
                 (out_rtti_data_buffer.len() << 2) as _,
             )
@@ -1514,6 +2263,95 @@ impl ISession for ISessionPtr {
     }
 }
 
+/// A session that can be moved to another thread (but not shared; see [`SharedSession`] for that).
+///
+/// Modules and programs loaded from a session share its internal state, so they can only be sent along with it: load
+/// them on the receiving thread after [`into_inner`](Self::into_inner), send a program in place of its session with
+/// [`SendProgram`], or send their code as a [`SendBlob`].
+#[derive(Debug)]
+pub struct SendSession(ISessionPtr);
+// `new` checks that no other reference to the session exists, and its contract rules out other users of its state.
+unsafe impl Send for SendSession {}
+impl SendSession {
+    /// Takes over `session`.
+    ///
+    /// Fails, giving the session back, if other references to it exist (clones of the pointer).
+    ///
+    /// # Safety
+    /// No object created from the session (modules, component types, entry points, ...) may be used after this call,
+    /// and the global session it was created from must not be used while the receiving thread uses the session:
+    /// they share the session's state and reference counts, which are not synchronized.
+    pub unsafe fn new(session: ISessionPtr) -> core::result::Result<Self, ISessionPtr> {
+        if is_sole_reference(&session) {
+            Ok(Self(session))
+        } else {
+            Err(session)
+        }
+    }
+
+    #[inline(always)]
+    pub fn into_inner(self) -> ISessionPtr {
+        self.0
+    }
+}
+
+/// A session that can be shared between threads (e.g. through an `Arc`).
+///
+/// Slang allows a session to be used by only one thread at a time, so every access goes through an internal mutex.
+/// Modules and programs obtained from the session share its internal state, so they must only live inside
+/// [`with`](Self::with): take out plain data (code bytes, strings, ...) or [`SendBlob`]s.
+pub struct SharedSession(std::sync::Mutex<ISessionPtr>);
+// The session is the only reference to itself (checked by `new`) and, by the contracts of `new` and `with`, neither it
+// nor anything created from it is used outside the lock.
+unsafe impl Send for SharedSession {}
+unsafe impl Sync for SharedSession {}
+impl SharedSession {
+    /// Takes over `session`.
+    ///
+    /// Fails, giving the session back, if other references to it exist (clones of the pointer).
+    ///
+    /// # Safety
+    /// No object created from the session (modules, component types, entry points, ...) may be used after this call,
+    /// and the global session it was created from must not be used while [`with`](Self::with) runs: they share the
+    /// session's state and reference counts, which are not synchronized.
+    pub unsafe fn new(session: ISessionPtr) -> core::result::Result<Self, ISessionPtr> {
+        if is_sole_reference(&session) {
+            Ok(Self(std::sync::Mutex::new(session)))
+        } else {
+            Err(session)
+        }
+    }
+
+    /// Runs `f` with exclusive use of the session.
+    ///
+    /// # Safety
+    /// Nothing referring to the session may outlive `f`: neither clones of the session pointer nor objects created
+    /// from it (e.g. a module or a program, which can give the session back through
+    /// [`IComponentType::get_session`]), whether returned or stored elsewhere (thread-locals, statics, ...).
+    #[inline]
+    pub unsafe fn with<R: Send>(&self, f: impl FnOnce(&ISessionPtr) -> R + Send) -> R {
+        // a panic while holding the lock leaves no Rust-side state to repair
+        let session = self
+            .0
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+        f(&session)
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> ISessionPtr {
+        self.0
+            .into_inner()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+impl core::fmt::Debug for SharedSession {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("SharedSession").field(&self.0).finish()
+    }
+}
+
 pub const METADATA_IID: SlangUUID = SlangUUID::new(
     0x8044a8a3,
     0xddc0,
@@ -1741,27 +2579,34 @@ pub trait IComponentType: IUnknown {
 
     #[inline]
     fn get_session(&self) -> ISessionPtr {
-        unsafe {
+        let session = unsafe {
             ISessionPtr(NonNull::new_unchecked((IComponentType::vt(self)
                 .get_session)(
                 self.thisptr()
             )))
-        }
+        };
+        // returned without a reference of our own
+        session.add_ref();
+
+        session
     }
 
-    #[inline]
+    /// The reflection of the program for the target `target_index`, which borrows this component type.
+    ///
+    /// Returns `None` if the layout could not be computed (see `out_diagnostics`).
     fn get_layout(
         &self,
         target_index: SlangInt,
         out_diagnostics: Option<&mut MaybeUninit<Option<IBlobPtr>>>,
-    ) -> &mut reflection::Shader {
-        unsafe {
-            reflection::Shader::from_mut_ptr((IComponentType::vt(self).get_layout)(
+    ) -> Option<reflection::Shader<'_>> {
+        let p = unsafe {
+            (IComponentType::vt(self).get_layout)(
                 self.thisptr(),
                 target_index,
                 out_diagnostics.map_or_else(core::ptr::null_mut, MaybeUninit::as_mut_ptr) as _,
-            ))
-        }
+            )
+        };
+        unsafe { reflection::Shader::from_nullable(p) }
     }
 
     #[inline]
@@ -1789,11 +2634,22 @@ pub trait IComponentType: IUnknown {
         Ok(unsafe { IBlobPtr(NonNull::new_unchecked(o.assume_init())) })
     }
 
+    /// [`get_entry_point_code`](IComponentType::get_entry_point_code) with its diagnostic output decoded into the
+    /// result.
+    fn get_entry_point_code_with_diagnostics(
+        &self,
+        entry_point_index: SlangInt,
+        target_index: SlangInt,
+    ) -> DiagnosedResult<IBlobPtr> {
+        diagnosed(|d| self.get_entry_point_code(entry_point_index, target_index, d))
+    }
+
+    /// Get the compilation result for an entry point as a file system (e.g. to enumerate every output file).
     fn get_result_as_file_system(
         &self,
         entry_point_index: SlangInt,
         target_index: SlangInt,
-    ) -> Result<NonNull<c_void>> {
+    ) -> Result<IMutableFileSystemPtr> {
         let mut o = MaybeUninit::uninit();
         rw(unsafe {
             (IComponentType::vt(self).get_result_as_file_system)(
@@ -1804,7 +2660,9 @@ pub trait IComponentType: IUnknown {
             )
         })?;
 
-        Ok(unsafe { NonNull::new_unchecked(o.assume_init()) })
+        Ok(IMutableFileSystemPtr(unsafe {
+            NonNull::new_unchecked(o.assume_init())
+        }))
     }
 
     fn get_entry_point_hash(
@@ -1846,6 +2704,14 @@ pub trait IComponentType: IUnknown {
         }))
     }
 
+    /// [`specialize`](IComponentType::specialize) with its diagnostic output decoded into the result.
+    fn specialize_with_diagnostics(
+        &self,
+        specialization_args: &[SpecializationArg],
+    ) -> DiagnosedResult<IComponentTypePtr> {
+        diagnosed(|d| self.specialize(specialization_args, d))
+    }
+
     fn link(
         &self,
         out_diagnostics: Option<&mut MaybeUninit<Option<IBlobPtr>>>,
@@ -1864,6 +2730,16 @@ pub trait IComponentType: IUnknown {
         }))
     }
 
+    /// [`link`](IComponentType::link) with its diagnostic output decoded into the result.
+    fn link_with_diagnostics(&self) -> DiagnosedResult<IComponentTypePtr> {
+        diagnosed(|d| self.link(d))
+    }
+
+    /// [`link`](IComponentType::link) with its diagnostic output sent to `writers`.
+    fn link_with_writers(&self, writers: &Writers) -> Result<IComponentTypePtr> {
+        writers.run(|d| self.link(d))
+    }
+
     fn get_entry_point_host_callable(
         &self,
         entry_point_index: c_int,
@@ -1901,7 +2777,22 @@ pub trait IComponentType: IUnknown {
         }))
     }
 
+    /// Link this component type with additional compiler options applied to the linked program.
     fn link_with_options(
+        &self,
+        options: &[CompilerOption],
+        out_diagnostics: Option<&mut MaybeUninit<Option<IBlobPtr>>>,
+    ) -> Result<IComponentTypePtr> {
+        let mut entries = options.iter().collect::<CompilerOptionEntries>();
+
+        unsafe { self.link_with_options_raw(entries.as_mut_slice(), out_diagnostics) }
+    }
+
+    /// [`link_with_options`](IComponentType::link_with_options) taking raw entries.
+    ///
+    /// # Safety
+    /// Strings referenced by `compiler_option_entries` must be valid for the duration of the call.
+    unsafe fn link_with_options_raw(
         &self,
         compiler_option_entries: &mut [CompilerOptionEntry],
         out_diagnostics: Option<&mut MaybeUninit<Option<IBlobPtr>>>,
@@ -1922,6 +2813,14 @@ pub trait IComponentType: IUnknown {
         }))
     }
 
+    /// [`link_with_options`](IComponentType::link_with_options) with its diagnostic output decoded into the result.
+    fn link_with_options_with_diagnostics(
+        &self,
+        options: &[CompilerOption],
+    ) -> DiagnosedResult<IComponentTypePtr> {
+        diagnosed(|d| self.link_with_options(options, d))
+    }
+
     fn get_target_code(
         &self,
         target_index: SlangInt,
@@ -1940,6 +2839,23 @@ pub trait IComponentType: IUnknown {
         Ok(IBlobPtr(unsafe { NonNull::new_unchecked(o.assume_init()) }))
     }
 
+    /// [`get_target_code`](IComponentType::get_target_code) with its diagnostic output decoded into the result.
+    fn get_target_code_with_diagnostics(
+        &self,
+        target_index: SlangInt,
+    ) -> DiagnosedResult<IBlobPtr> {
+        diagnosed(|d| self.get_target_code(target_index, d))
+    }
+
+    /// [`get_target_code`](IComponentType::get_target_code) with its diagnostic output sent to `writers`.
+    fn get_target_code_with_writers(
+        &self,
+        target_index: SlangInt,
+        writers: &Writers,
+    ) -> Result<IBlobPtr> {
+        writers.run(|d| self.get_target_code(target_index, d))
+    }
+
     fn get_target_metadata(
         &self,
         target_index: SlangInt,
@@ -1997,6 +2913,39 @@ impl IComponentType for IComponentTypePtr {
     }
 }
 
+/// A component type (e.g. a linked program) that can be moved to another thread along with its session.
+///
+/// A component type shares the internal state and reference counts of the session it was created from, so it can only
+/// be sent once nothing else refers to that session: drop the session, its modules and the other component types
+/// before wrapping the program (or send the session instead, with [`SendSession`]).
+#[derive(Debug)]
+pub struct SendProgram(IComponentTypePtr);
+// `new` checks that no other reference to the component type exists, and its contract rules out other users of the
+// session state it shares.
+unsafe impl Send for SendProgram {}
+impl SendProgram {
+    /// Takes over `program`.
+    ///
+    /// Fails, giving the program back, if other references to it exist (clones of the pointer).
+    ///
+    /// # Safety
+    /// No other reference to the session the program was created from, nor to anything created from that session
+    /// (modules, entry points, other component types, ...), may be used or released after this call: they share the
+    /// session's state and reference counts, which are not synchronized.
+    pub unsafe fn new(program: IComponentTypePtr) -> core::result::Result<Self, IComponentTypePtr> {
+        if is_sole_reference(&program) {
+            Ok(Self(program))
+        } else {
+            Err(program)
+        }
+    }
+
+    #[inline(always)]
+    pub fn into_inner(self) -> IComponentTypePtr {
+        self.0
+    }
+}
+
 pub const ENTRY_POINT_IID: SlangUUID = SlangUUID::new(
     0x8f241361,
     0xf5bd,
@@ -2012,9 +2961,9 @@ pub struct IEntryPointVTable {
 pub trait IEntryPoint: IComponentType {
     fn vt(&self) -> &IEntryPointVTable;
 
-    #[inline(always)]
-    fn get_function_reflection(&self) -> &mut SlangReflectionFunction {
-        unsafe { &mut *(IEntryPoint::vt(self).get_function_reflection)(self.thisptr()) }
+    fn get_function_reflection(&self) -> Option<reflection::Function<'_>> {
+        let p = unsafe { (IEntryPoint::vt(self).get_function_reflection)(self.thisptr()) };
+        unsafe { reflection::Function::from_nullable(p) }
     }
 }
 
@@ -2105,7 +3054,7 @@ pub trait IComponentType2: IUnknown {
     fn get_target_compile_result(
         &self,
         target_index: SlangInt,
-        out_diagnostics: Option<&mut MaybeUninit<IBlobPtr>>,
+        out_diagnostics: Option<&mut MaybeUninit<Option<IBlobPtr>>>,
     ) -> Result<ICompileResultPtr> {
         let mut o = MaybeUninit::uninit();
         rw(unsafe {
@@ -2126,7 +3075,7 @@ pub trait IComponentType2: IUnknown {
         &self,
         entry_point_index: SlangInt,
         target_index: SlangInt,
-        out_diagnostics: Option<&mut MaybeUninit<IBlobPtr>>,
+        out_diagnostics: Option<&mut MaybeUninit<Option<IBlobPtr>>>,
     ) -> Result<ICompileResultPtr> {
         let mut o = MaybeUninit::uninit();
         rw(unsafe {
@@ -2279,7 +3228,7 @@ pub trait IModule: IComponentType {
         &self,
         name: &CStr,
         stage: Stage,
-        out_diagnostics: Option<&mut MaybeUninit<IBlobPtr>>,
+        out_diagnostics: Option<&mut MaybeUninit<Option<IBlobPtr>>>,
     ) -> Result<IEntryPointPtr> {
         let mut o = MaybeUninit::uninit();
         rw(unsafe {
@@ -2297,6 +3246,16 @@ pub trait IModule: IComponentType {
         }))
     }
 
+    /// [`find_and_check_entry_point`](IModule::find_and_check_entry_point) with its diagnostic output decoded into
+    /// the result.
+    fn find_and_check_entry_point_with_diagnostics(
+        &self,
+        name: &CStr,
+        stage: Stage,
+    ) -> DiagnosedResult<IEntryPointPtr> {
+        diagnosed(|d| self.find_and_check_entry_point(name, stage, d))
+    }
+
     #[inline]
     fn get_dependency_file_count(&self) -> i32 {
         unsafe { (IModule::vt(self).get_dependency_file_count)(self.thisptr()) }
@@ -2312,9 +3271,9 @@ pub trait IModule: IComponentType {
         }
     }
 
-    #[inline]
-    fn get_module_reflection(&self) -> &mut SlangReflectionDecl {
-        unsafe { &mut *(IModule::vt(self).get_module_reflection)(self.thisptr()) }
+    fn get_module_reflection(&self) -> Option<reflection::Decl<'_>> {
+        let p = unsafe { (IModule::vt(self).get_module_reflection)(self.thisptr()) };
+        unsafe { reflection::Decl::from_nullable(p) }
     }
 
     fn disassemble(&self) -> Result<IBlobPtr> {
@@ -2346,6 +3305,7 @@ impl IModule for IModulePtr {
 }
 
 #[repr(C)]
+#[derive(Clone)]
 pub struct TargetDesc {
     pub structure_size: usize,
     pub format: CompileTarget,
@@ -2431,7 +3391,231 @@ impl Default for SessionDesc {
     }
 }
 
+/// Owning builder for [`SessionDesc`].
+///
+/// Keeps every array and string referenced by the descriptor alive, so a correctly-lifetimed [`SessionDesc`] can be
+/// handed out by [`IGlobalSession::create_session`].
+pub struct SessionDescBuilder {
+    targets: Vec<(TargetDesc, CompilerOptionEntries)>,
+    flags: SessionFlags,
+    default_matrix_layout_mode: SlangMatrixLayoutMode,
+    search_paths: Vec<CString>,
+    preprocessor_macros: Vec<(CString, CString)>,
+    file_system: Option<IFileSystemPtr>,
+    enable_effect_annotations: bool,
+    allow_glsl_syntax: bool,
+    compiler_option_entries: CompilerOptionEntries,
+    skip_spirv_validation: bool,
+}
+impl Default for SessionDescBuilder {
+    fn default() -> Self {
+        Self {
+            targets: Vec::new(),
+            flags: SESSION_FLAGS_NONE,
+            default_matrix_layout_mode: SLANG_MATRIX_LAYOUT_ROW_MAJOR,
+            search_paths: Vec::new(),
+            preprocessor_macros: Vec::new(),
+            file_system: None,
+            enable_effect_annotations: false,
+            allow_glsl_syntax: false,
+            compiler_option_entries: CompilerOptionEntries::new(),
+            skip_spirv_validation: false,
+        }
+    }
+}
+impl SessionDescBuilder {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a code generation target with default settings.
+    #[inline]
+    pub fn target(self, format: CompileTarget, profile: ProfileID) -> Self {
+        // SAFETY: the default descriptor carries no compiler option entries
+        unsafe {
+            self.target_desc(TargetDesc {
+                format,
+                profile,
+                ..Default::default()
+            })
+        }
+    }
+
+    /// Adds a code generation target with compiler options applied only to that target.
+    #[inline]
+    pub fn target_with_options(
+        mut self,
+        format: CompileTarget,
+        profile: ProfileID,
+        options: impl IntoIterator<Item = CompilerOption>,
+    ) -> Self {
+        self.targets.push((
+            TargetDesc {
+                format,
+                profile,
+                ..Default::default()
+            },
+            options.into_iter().collect(),
+        ));
+        self
+    }
+
+    /// Adds a code generation target described by a raw [`TargetDesc`].
+    ///
+    /// # Safety
+    /// Compiler option entries referenced by `desc` (and the strings they point to) must outlive every session
+    /// created from this builder.
+    #[inline]
+    pub unsafe fn target_desc(mut self, desc: TargetDesc) -> Self {
+        self.targets.push((desc, CompilerOptionEntries::new()));
+        self
+    }
+
+    #[inline]
+    pub fn flags(mut self, flags: SessionFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    #[inline]
+    pub fn default_matrix_layout_mode(mut self, mode: SlangMatrixLayoutMode) -> Self {
+        self.default_matrix_layout_mode = mode;
+        self
+    }
+
+    /// Adds a path searched for `import`ed modules and `#include`d files.
+    #[inline]
+    pub fn search_path(mut self, path: impl Into<CString>) -> Self {
+        self.search_paths.push(path.into());
+        self
+    }
+
+    #[inline]
+    pub fn search_paths<P: Into<CString>>(mut self, paths: impl IntoIterator<Item = P>) -> Self {
+        self.search_paths.extend(paths.into_iter().map(Into::into));
+        self
+    }
+
+    /// Defines a preprocessor macro visible to every module loaded in the session.
+    #[inline]
+    pub fn preprocessor_macro(
+        mut self,
+        name: impl Into<CString>,
+        value: impl Into<CString>,
+    ) -> Self {
+        self.preprocessor_macros.push((name.into(), value.into()));
+        self
+    }
+
+    /// Overrides the file system used to load modules and included files.
+    #[inline]
+    pub fn file_system(mut self, file_system: IFileSystemPtr) -> Self {
+        self.file_system = Some(file_system);
+        self
+    }
+
+    #[inline]
+    pub fn enable_effect_annotations(mut self, enable: bool) -> Self {
+        self.enable_effect_annotations = enable;
+        self
+    }
+
+    #[inline]
+    pub fn allow_glsl_syntax(mut self, allow: bool) -> Self {
+        self.allow_glsl_syntax = allow;
+        self
+    }
+
+    #[inline]
+    pub fn skip_spirv_validation(mut self, skip: bool) -> Self {
+        self.skip_spirv_validation = skip;
+        self
+    }
+
+    /// Adds a session-wide compiler option.
+    #[inline]
+    pub fn compiler_option(mut self, option: CompilerOption) -> Self {
+        self.compiler_option_entries.push(&option);
+        self
+    }
+
+    #[inline]
+    pub fn compiler_options(mut self, options: impl IntoIterator<Item = CompilerOption>) -> Self {
+        self.compiler_option_entries.extend(options);
+        self
+    }
+
+    /// Adds a raw session-wide compiler option entry.
+    ///
+    /// # Safety
+    /// Strings referenced by `entry` must outlive every session created from this builder.
+    #[inline]
+    pub unsafe fn compiler_option_entry(mut self, entry: CompilerOptionEntry) -> Self {
+        unsafe {
+            self.compiler_option_entries.push_raw(entry);
+        }
+        self
+    }
+
+    /// Calls `f` with a [`SessionDesc`] referencing the storage of this builder.
+    pub fn with_desc<R>(&self, f: impl FnOnce(&SessionDesc) -> R) -> R {
+        let targets = self
+            .targets
+            .iter()
+            .map(|(desc, options)| {
+                if options.is_empty() {
+                    return desc.clone();
+                }
+
+                TargetDesc {
+                    // slang only reads the entries
+                    compiler_option_entries: options.as_slice().as_ptr().cast_mut(),
+                    compiler_option_entry_count: options.len() as _,
+                    ..desc.clone()
+                }
+            })
+            .collect::<Vec<_>>();
+        let search_paths = self
+            .search_paths
+            .iter()
+            .map(|p| p.as_ptr())
+            .collect::<Vec<_>>();
+        let preprocessor_macros = self
+            .preprocessor_macros
+            .iter()
+            .map(|(n, v)| PreprocessorMacroDesc {
+                name: n.as_ptr(),
+                value: v.as_ptr(),
+            })
+            .collect::<Vec<_>>();
+
+        f(&SessionDesc {
+            targets: targets.as_ptr(),
+            target_count: targets.len() as _,
+            flags: self.flags,
+            default_matrix_layout_mode: self.default_matrix_layout_mode,
+            search_paths: search_paths.as_ptr(),
+            search_path_count: search_paths.len() as _,
+            preprocessor_macros: preprocessor_macros.as_ptr(),
+            preprocessor_macro_count: preprocessor_macros.len() as _,
+            file_system: self
+                .file_system
+                .as_ref()
+                .map_or(core::ptr::null_mut(), |x| x.thisptr()),
+            enable_effect_annotations: self.enable_effect_annotations,
+            allow_glsl_syntax: self.allow_glsl_syntax,
+            // slang only reads the entries
+            compiler_option_entries: self.compiler_option_entries.as_slice().as_ptr().cast_mut(),
+            compiler_option_entry_count: self.compiler_option_entries.len() as _,
+            skip_spirv_validation: self.skip_spirv_validation,
+            ..Default::default()
+        })
+    }
+}
+
 #[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum CompilerOptionName {
     MacroDefine,
     DepFile,
@@ -2605,6 +3789,7 @@ pub enum CompilerOptionName {
 }
 
 #[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum CompilerOptionValueKind {
     Int,
     String,
@@ -2664,3 +3849,14 @@ pub fn create_global_session(desc: &GlobalSessionDesc) -> Result<IGlobalSessionP
         NonNull::new_unchecked(o.assume_init())
     }))
 }
+
+/// [`create_global_session`], additionally checking that the library version is within [`SUPPORTED_VERSIONS`]
+/// (see [`check_version`]).
+pub fn create_global_session_checked(
+    desc: &GlobalSessionDesc,
+) -> core::result::Result<IGlobalSessionPtr, VersionError> {
+    let global_session = create_global_session(desc)?;
+    check_version(&global_session)?;
+
+    Ok(global_session)
+}
@@ -1,6 +1,6 @@
 use core::{
-    cell::UnsafeCell,
     ffi::{CStr, c_float, c_int, c_uint},
+    marker::PhantomData,
     mem::MaybeUninit,
     ptr::NonNull,
 };
@@ -11,73 +11,130 @@ pub use crate::ffi::{
     SlangReflectionGenericArg as GenericArg, SlangReflectionGenericArgType as GenericArgType,
 };
 
+pub mod snapshot;
+pub use snapshot::ProgramReflection;
+
+/// Defines a reflection object handle.
+///
+/// Handles are `Copy` references into the layout owned by the component type (or session) they were obtained from,
+/// so they cannot outlive it.
+macro_rules! reflection_handle {
+    ($name: ident, $raw: ty) => {
+        #[derive(Clone, Copy)]
+        #[repr(transparent)]
+        pub struct $name<'a>(NonNull<$raw>, PhantomData<&'a ()>);
+        impl<'a> $name<'a> {
+            /// # Safety
+            /// `ptr` must be non-null and stay valid for `'a`.
+            #[inline(always)]
+            pub const unsafe fn from_raw(ptr: *mut $raw) -> Self {
+                Self(unsafe { NonNull::new_unchecked(ptr) }, PhantomData)
+            }
+
+            /// `None` if `ptr` is null.
+            ///
+            /// # Safety
+            /// `ptr` must be null or stay valid for `'a`.
+            #[inline(always)]
+            pub const unsafe fn from_nullable(ptr: *mut $raw) -> Option<Self> {
+                match NonNull::new(ptr) {
+                    Some(p) => Some(Self(p, PhantomData)),
+                    None => None,
+                }
+            }
+
+            #[inline(always)]
+            pub const fn as_raw(self) -> *mut $raw {
+                self.0.as_ptr()
+            }
+        }
+    };
+}
+
 macro_rules! iter_something {
-    ($name: ident, $refl: ty, $out_ty: ty, $getter_sym: path, $counter_type: ty) => {
+    ($name: ident, $refl: ident, $out_ty: ident, $getter_sym: path, $counter_type: ty) => {
         pub struct $name<'x> {
-            refl: &'x $refl,
+            refl: $refl<'x>,
             current: $counter_type,
             count: $counter_type,
         }
         impl<'x> Iterator for $name<'x> {
-            type Item = &'x mut $out_ty;
+            type Item = $out_ty<'x>;
 
             fn next(&mut self) -> Option<Self::Item> {
-                if self.current >= self.count {
-                    return None;
+                while self.current < self.count {
+                    let r = unsafe {
+                        $out_ty::from_nullable($getter_sym(self.refl.0.as_ptr(), self.current))
+                    };
+                    self.current += 1;
+                    // skip the elements Slang has no object for, as the indexed getters return `None` for them
+                    if r.is_some() {
+                        return r;
+                    }
                 }
 
-                let r = unsafe {
-                    <$out_ty>::from_mut_ptr($getter_sym(self.refl.0.get(), self.current))
-                };
-                self.current += 1;
-                Some(r)
+                None
             }
         }
     };
 }
 
-#[repr(transparent)]
-pub struct Attribute(UnsafeCell<ffi::SlangReflectionUserAttribute>);
-impl Attribute {
-    pub const unsafe fn from_mut_ptr<'a>(
-        ptr: *mut ffi::SlangReflectionUserAttribute,
-    ) -> &'a mut Self {
-        unsafe { core::mem::transmute(UnsafeCell::from_mut(&mut *ptr)) }
+/// A string returned by reflection, or `None` if it is null.
+///
+/// # Safety
+/// `p` must be null or point to a NUL-terminated string valid for `'a`.
+#[inline(always)]
+unsafe fn nullable_str<'a>(p: *const core::ffi::c_char) -> Option<&'a CStr> {
+    if p.is_null() {
+        None
+    } else {
+        Some(unsafe { CStr::from_ptr(p) })
     }
+}
 
+/// Copies a name obtained from reflection, treating a missing name and an empty one alike.
+pub(crate) fn owned_name(name: Option<&CStr>) -> Option<String> {
+    let name = name?.to_string_lossy();
+    (!name.is_empty()).then(|| name.into_owned())
+}
+
+reflection_handle!(Attribute, ffi::SlangReflectionUserAttribute);
+impl<'a> Attribute<'a> {
     #[inline(always)]
-    pub fn name(&self) -> &CStr {
-        unsafe { CStr::from_ptr(ffi::spReflectionUserAttribute_GetName(self.0.get())) }
+    pub fn name(self) -> Option<&'a CStr> {
+        let p = unsafe { ffi::spReflectionUserAttribute_GetName(self.0.as_ptr()) };
+        unsafe { nullable_str(p) }
     }
 
     #[inline(always)]
-    pub fn argument_count(&self) -> u32 {
-        unsafe { ffi::spReflectionUserAttribute_GetArgumentCount(self.0.get()) }
+    pub fn argument_count(self) -> u32 {
+        unsafe { ffi::spReflectionUserAttribute_GetArgumentCount(self.0.as_ptr()) }
     }
 
     #[inline(always)]
-    pub fn argument_type(&self, index: u32) -> &mut Type {
-        unsafe {
-            core::mem::transmute(UnsafeCell::from_mut(
-                &mut *ffi::spReflectionUserAttribute_GetArgumentType(self.0.get(), index),
-            ))
-        }
+    pub fn argument_type(self, index: u32) -> Option<Type<'a>> {
+        let p = unsafe { ffi::spReflectionUserAttribute_GetArgumentType(self.0.as_ptr(), index) };
+        unsafe { Type::from_nullable(p) }
     }
 
-    pub fn argument_value_int(&self, index: u32) -> crate::Result<c_int> {
+    pub fn argument_value_int(self, index: u32) -> crate::Result<c_int> {
         let mut o = MaybeUninit::uninit();
         crate::rw(unsafe {
-            ffi::spReflectionUserAttribute_GetArgumentValueInt(self.0.get(), index, o.as_mut_ptr())
+            ffi::spReflectionUserAttribute_GetArgumentValueInt(
+                self.0.as_ptr(),
+                index,
+                o.as_mut_ptr(),
+            )
         })?;
 
         Ok(unsafe { o.assume_init() })
     }
 
-    pub fn argument_value_float(&self, index: u32) -> crate::Result<c_float> {
+    pub fn argument_value_float(self, index: u32) -> crate::Result<c_float> {
         let mut o = MaybeUninit::uninit();
         crate::rw(unsafe {
             ffi::spReflectionUserAttribute_GetArgumentValueFloat(
-                self.0.get(),
+                self.0.as_ptr(),
                 index,
                 o.as_mut_ptr(),
             )
@@ -86,23 +143,21 @@ impl Attribute {
         Ok(unsafe { o.assume_init() })
     }
 
-    pub fn argument_value_string(&self, index: u32) -> Option<&CStr> {
+    pub fn argument_value_string(self, index: u32) -> Option<&'a CStr> {
         let p = unsafe {
             ffi::spReflectionUserAttribute_GetArgumentValueString(
-                self.0.get(),
+                self.0.as_ptr(),
                 index,
                 core::ptr::null_mut(),
             )
         };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { CStr::from_ptr(p) })
-        }
+        unsafe { nullable_str(p) }
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 #[repr(u32)]
 pub enum TypeKind {
     None = ffi::SLANG_TYPE_KIND_NONE,
@@ -128,52 +183,49 @@ pub enum TypeKind {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 #[repr(u32)]
 pub enum ScalarType {
     None = ffi::SLANG_SCALAR_TYPE_NONE,
     Void = ffi::SLANG_SCALAR_TYPE_VOID,
     Bool = ffi::SLANG_SCALAR_TYPE_BOOL,
     Int32 = ffi::SLANG_SCALAR_TYPE_INT32,
+    #[cfg_attr(feature = "serde", serde(rename = "uint32"))]
     UInt32 = ffi::SLANG_SCALAR_TYPE_UINT32,
     Int64 = ffi::SLANG_SCALAR_TYPE_INT64,
+    #[cfg_attr(feature = "serde", serde(rename = "uint64"))]
     UInt64 = ffi::SLANG_SCALAR_TYPE_UINT64,
     Float16 = ffi::SLANG_SCALAR_TYPE_FLOAT16,
     Float32 = ffi::SLANG_SCALAR_TYPE_FLOAT32,
     Float64 = ffi::SLANG_SCALAR_TYPE_FLOAT64,
     Int8 = ffi::SLANG_SCALAR_TYPE_INT8,
+    #[cfg_attr(feature = "serde", serde(rename = "uint8"))]
     UInt8 = ffi::SLANG_SCALAR_TYPE_UINT8,
     Int16 = ffi::SLANG_SCALAR_TYPE_INT16,
+    #[cfg_attr(feature = "serde", serde(rename = "uint16"))]
     UInt16 = ffi::SLANG_SCALAR_TYPE_UINT16,
 }
 
-#[repr(transparent)]
-pub struct Type(UnsafeCell<SlangReflectionType>);
-impl Type {
-    pub const unsafe fn from_mut_ptr<'a>(ptr: *mut ffi::SlangReflectionType) -> &'a mut Self {
-        unsafe { core::mem::transmute(UnsafeCell::from_mut(&mut *ptr)) }
-    }
-
+reflection_handle!(Type, ffi::SlangReflectionType);
+impl<'a> Type<'a> {
     #[inline(always)]
-    pub fn kind(&self) -> TypeKind {
-        unsafe { core::mem::transmute(ffi::spReflectionType_GetKind(self.0.get())) }
+    pub fn kind(self) -> TypeKind {
+        unsafe { core::mem::transmute(ffi::spReflectionType_GetKind(self.0.as_ptr())) }
     }
 
     #[inline(always)]
-    pub fn field_count(&self) -> c_uint {
-        unsafe { ffi::spReflectionType_GetFieldCount(self.0.get()) }
+    pub fn field_count(self) -> c_uint {
+        unsafe { ffi::spReflectionType_GetFieldCount(self.0.as_ptr()) }
     }
 
-    pub fn field(&self, index: c_uint) -> Option<&mut Variable> {
-        let p = unsafe { ffi::spReflectionType_GetFieldByIndex(self.0.get(), index) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { core::mem::transmute(UnsafeCell::from_mut(&mut *p)) })
-        }
+    pub fn field(self, index: c_uint) -> Option<Variable<'a>> {
+        let p = unsafe { ffi::spReflectionType_GetFieldByIndex(self.0.as_ptr(), index) };
+        unsafe { Variable::from_nullable(p) }
     }
 
     #[inline(always)]
-    pub fn iter_field<'x>(&'x self) -> TypeFieldIterator<'x> {
+    pub fn iter_field(self) -> TypeFieldIterator<'a> {
         TypeFieldIterator {
             refl: self,
             current: 0,
@@ -182,86 +234,74 @@ impl Type {
     }
 
     #[inline(always)]
-    pub fn element_count(&self, reflection: Option<&Shader>) -> usize {
+    pub fn element_count(self, reflection: Option<Shader<'_>>) -> usize {
         unsafe {
             ffi::spReflectionType_GetSpecializedElementCount(
-                self.0.get(),
-                reflection.map_or_else(core::ptr::null_mut, |x| x.0.get()),
+                self.0.as_ptr(),
+                reflection.map_or_else(core::ptr::null_mut, |x| x.0.as_ptr()),
             )
         }
     }
 
-    pub fn element_type(&self) -> Option<&mut Type> {
-        let p = unsafe { ffi::spReflectionType_GetElementType(self.0.get()) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { core::mem::transmute(UnsafeCell::from_mut(&mut *p)) })
-        }
+    pub fn element_type(self) -> Option<Type<'a>> {
+        let p = unsafe { ffi::spReflectionType_GetElementType(self.0.as_ptr()) };
+        unsafe { Type::from_nullable(p) }
     }
 
     #[inline(always)]
-    pub fn row_count(&self) -> c_uint {
-        unsafe { ffi::spReflectionType_GetRowCount(self.0.get()) }
+    pub fn row_count(self) -> c_uint {
+        unsafe { ffi::spReflectionType_GetRowCount(self.0.as_ptr()) }
     }
 
     #[inline(always)]
-    pub fn column_count(&self) -> c_uint {
-        unsafe { ffi::spReflectionType_GetColumnCount(self.0.get()) }
+    pub fn column_count(self) -> c_uint {
+        unsafe { ffi::spReflectionType_GetColumnCount(self.0.as_ptr()) }
     }
 
     #[inline(always)]
-    pub fn scalar_type(&self) -> ScalarType {
-        unsafe { core::mem::transmute(ffi::spReflectionType_GetScalarType(self.0.get())) }
+    pub fn scalar_type(self) -> ScalarType {
+        unsafe { core::mem::transmute(ffi::spReflectionType_GetScalarType(self.0.as_ptr())) }
     }
 
-    pub fn resource_result_type(&self) -> Option<&mut Type> {
-        let p = unsafe { ffi::spReflectionType_GetResourceResultType(self.0.get()) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { core::mem::transmute(UnsafeCell::from_mut(&mut *p)) })
-        }
+    pub fn resource_result_type(self) -> Option<Type<'a>> {
+        let p = unsafe { ffi::spReflectionType_GetResourceResultType(self.0.as_ptr()) };
+        unsafe { Type::from_nullable(p) }
     }
 
     #[inline(always)]
-    pub fn resource_shape(&self) -> ffi::SlangResourceShape {
-        unsafe { ffi::spReflectionType_GetResourceShape(self.0.get()) }
+    pub fn resource_shape(self) -> ffi::SlangResourceShape {
+        unsafe { ffi::spReflectionType_GetResourceShape(self.0.as_ptr()) }
     }
 
     #[inline(always)]
-    pub fn resource_access(&self) -> ffi::SlangResourceAccess {
-        unsafe { ffi::spReflectionType_GetResourceAccess(self.0.get()) }
+    pub fn resource_access(self) -> ffi::SlangResourceAccess {
+        unsafe { ffi::spReflectionType_GetResourceAccess(self.0.as_ptr()) }
     }
 
-    #[inline(always)]
-    pub fn name(&self) -> &CStr {
-        unsafe { CStr::from_ptr(ffi::spReflectionType_GetName(self.0.get())) }
+    pub fn name(self) -> Option<&'a CStr> {
+        let p = unsafe { ffi::spReflectionType_GetName(self.0.as_ptr()) };
+        unsafe { nullable_str(p) }
     }
 
-    pub fn full_name(&self) -> crate::Result<crate::IBlobPtr> {
+    pub fn full_name(self) -> crate::Result<crate::IBlobPtr> {
         let mut o = MaybeUninit::uninit();
-        crate::rw(unsafe { ffi::spReflectionType_GetFullName(self.0.get(), o.as_mut_ptr()) })?;
+        crate::rw(unsafe { ffi::spReflectionType_GetFullName(self.0.as_ptr(), o.as_mut_ptr()) })?;
 
         Ok(IBlobPtr(unsafe { NonNull::new_unchecked(o.assume_init()) }))
     }
 
     #[inline(always)]
-    pub fn user_attribute_count(&self) -> c_uint {
-        unsafe { ffi::spReflectionType_GetUserAttributeCount(self.0.get()) }
+    pub fn user_attribute_count(self) -> c_uint {
+        unsafe { ffi::spReflectionType_GetUserAttributeCount(self.0.as_ptr()) }
     }
 
-    pub fn user_attribute(&self, index: c_uint) -> Option<&mut Attribute> {
-        let p = unsafe { ffi::spReflectionType_GetUserAttribute(self.0.get(), index) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { core::mem::transmute(UnsafeCell::from_mut(&mut *p)) })
-        }
+    pub fn user_attribute(self, index: c_uint) -> Option<Attribute<'a>> {
+        let p = unsafe { ffi::spReflectionType_GetUserAttribute(self.0.as_ptr(), index) };
+        unsafe { Attribute::from_nullable(p) }
     }
 
     #[inline(always)]
-    pub fn iter_user_attribute<'x>(&'x self) -> TypeUserAttributeIterator<'x> {
+    pub fn iter_user_attribute(self) -> TypeUserAttributeIterator<'a> {
         TypeUserAttributeIterator {
             refl: self,
             current: 0,
@@ -269,32 +309,23 @@ impl Type {
         }
     }
 
-    pub fn find_user_attribute_by_name(&self, name: &CStr) -> Option<&mut Attribute> {
-        let p =
-            unsafe { ffi::spReflectionType_FindUserAttributeByName(self.0.get(), name.as_ptr()) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { core::mem::transmute(UnsafeCell::from_mut(&mut *p)) })
-        }
+    pub fn find_user_attribute_by_name(self, name: &CStr) -> Option<Attribute<'a>> {
+        let p = unsafe {
+            ffi::spReflectionType_FindUserAttributeByName(self.0.as_ptr(), name.as_ptr())
+        };
+        unsafe { Attribute::from_nullable(p) }
     }
 
-    #[inline(always)]
-    pub fn apply_specializations(&self, generic: &Generic) -> &mut Type {
-        unsafe {
-            core::mem::transmute(UnsafeCell::from_mut(
-                &mut *ffi::spReflectionType_applySpecializations(self.0.get(), generic.0.get()),
-            ))
-        }
+    pub fn apply_specializations(self, generic: Generic<'_>) -> Option<Type<'a>> {
+        let p = unsafe {
+            ffi::spReflectionType_applySpecializations(self.0.as_ptr(), generic.0.as_ptr())
+        };
+        unsafe { Type::from_nullable(p) }
     }
 
-    pub fn generic_container(&self) -> Option<&mut Generic> {
-        let p = unsafe { ffi::spReflectionType_GetGenericContainer(self.0.get()) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { core::mem::transmute(UnsafeCell::from_mut(&mut *p)) })
-        }
+    pub fn generic_container(self) -> Option<Generic<'a>> {
+        let p = unsafe { ffi::spReflectionType_GetGenericContainer(self.0.as_ptr()) };
+        unsafe { Generic::from_nullable(p) }
     }
 }
 
@@ -314,6 +345,8 @@ iter_something!(
 );
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 #[repr(u32)]
 pub enum ParameterCategory {
     None = ffi::SLANG_PARAMETER_CATEGORY_NONE,
@@ -346,6 +379,8 @@ pub enum ParameterCategory {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 #[repr(u32)]
 pub enum BindingType {
     Unknown = ffi::SLANG_BINDING_TYPE_UNKNOWN,
@@ -371,54 +406,45 @@ pub enum BindingType {
     ExtMask = ffi::SLANG_BINDING_TYPE_EXT_MASK,
 }
 
-#[repr(transparent)]
-pub struct TypeLayout(UnsafeCell<ffi::SlangReflectionTypeLayout>);
-impl TypeLayout {
-    pub const unsafe fn from_mut_ptr<'a>(ptr: *mut ffi::SlangReflectionTypeLayout) -> &'a mut Self {
-        unsafe { core::mem::transmute(UnsafeCell::from_mut(&mut *ptr)) }
+reflection_handle!(TypeLayout, ffi::SlangReflectionTypeLayout);
+impl<'a> TypeLayout<'a> {
+    pub fn r#type(self) -> Option<Type<'a>> {
+        let p = unsafe { ffi::spReflectionTypeLayout_GetType(self.0.as_ptr()) };
+        unsafe { Type::from_nullable(p) }
     }
 
     #[inline]
-    pub fn r#type(&self) -> &mut Type {
-        unsafe { Type::from_mut_ptr(ffi::spReflectionTypeLayout_GetType(self.0.get())) }
+    pub fn kind(self) -> TypeKind {
+        unsafe { core::mem::transmute(ffi::spReflectionTypeLayout_getKind(self.0.as_ptr())) }
     }
 
     #[inline]
-    pub fn kind(&self) -> TypeKind {
-        unsafe { core::mem::transmute(ffi::spReflectionTypeLayout_getKind(self.0.get())) }
+    pub fn size(self, category: ParameterCategory) -> usize {
+        unsafe { ffi::spReflectionTypeLayout_GetSize(self.0.as_ptr(), category as _) }
     }
 
     #[inline]
-    pub fn size(&self, category: ParameterCategory) -> usize {
-        unsafe { ffi::spReflectionTypeLayout_GetSize(self.0.get(), category as _) }
+    pub fn stride(self, category: ParameterCategory) -> usize {
+        unsafe { ffi::spReflectionTypeLayout_GetStride(self.0.as_ptr(), category as _) }
     }
 
     #[inline]
-    pub fn stride(&self, category: ParameterCategory) -> usize {
-        unsafe { ffi::spReflectionTypeLayout_GetStride(self.0.get(), category as _) }
+    pub fn alignment(self, category: ParameterCategory) -> i32 {
+        unsafe { ffi::spReflectionTypeLayout_getAlignment(self.0.as_ptr(), category as _) }
     }
 
     #[inline]
-    pub fn alignment(&self, category: ParameterCategory) -> i32 {
-        unsafe { ffi::spReflectionTypeLayout_getAlignment(self.0.get(), category as _) }
+    pub fn field_count(self) -> c_uint {
+        unsafe { ffi::spReflectionTypeLayout_GetFieldCount(self.0.as_ptr()) }
     }
 
-    #[inline]
-    pub fn field_count(&self) -> c_uint {
-        unsafe { ffi::spReflectionTypeLayout_GetFieldCount(self.0.get()) }
-    }
-
-    pub fn field(&self, index: c_uint) -> Option<&mut VariableLayout> {
-        let p = unsafe { ffi::spReflectionTypeLayout_GetFieldByIndex(self.0.get(), index) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { VariableLayout::from_mut_ptr(p) })
-        }
+    pub fn field(self, index: c_uint) -> Option<VariableLayout<'a>> {
+        let p = unsafe { ffi::spReflectionTypeLayout_GetFieldByIndex(self.0.as_ptr(), index) };
+        unsafe { VariableLayout::from_nullable(p) }
     }
 
     #[inline(always)]
-    pub fn iter_field<'x>(&'x self) -> TypeLayoutFieldIterator<'x> {
+    pub fn iter_field(self) -> TypeLayoutFieldIterator<'a> {
         TypeLayoutFieldIterator {
             refl: self,
             current: 0,
@@ -427,88 +453,72 @@ impl TypeLayout {
     }
 
     #[inline]
-    pub fn find_field_index_by_name(&self, name_begin: &CStr, name_end: Option<&CStr>) -> SlangInt {
+    pub fn find_field_index_by_name(self, name_begin: &CStr, name_end: Option<&CStr>) -> SlangInt {
         unsafe {
             ffi::spReflectionTypeLayout_findFieldIndexByName(
-                self.0.get(),
+                self.0.as_ptr(),
                 name_begin.as_ptr(),
                 name_end.map_or_else(core::ptr::null, CStr::as_ptr),
             )
         }
     }
 
-    pub fn explicit_counter(&self) -> Option<&mut VariableLayout> {
-        let p = unsafe { ffi::spReflectionTypeLayout_GetExplicitCounter(self.0.get()) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { VariableLayout::from_mut_ptr(p) })
-        }
+    pub fn explicit_counter(self) -> Option<VariableLayout<'a>> {
+        let p = unsafe { ffi::spReflectionTypeLayout_GetExplicitCounter(self.0.as_ptr()) };
+        unsafe { VariableLayout::from_nullable(p) }
     }
 
     #[inline(always)]
-    pub fn element_count(&self, reflection: Option<&Shader>) -> usize {
-        self.r#type().element_count(reflection)
+    pub fn element_count(self, reflection: Option<Shader<'_>>) -> usize {
+        self.r#type().map_or(0, |t| t.element_count(reflection))
     }
 
     #[inline]
-    pub fn element_stride(&self, category: ParameterCategory) -> usize {
-        unsafe { ffi::spReflectionTypeLayout_GetElementStride(self.0.get(), category as _) }
+    pub fn element_stride(self, category: ParameterCategory) -> usize {
+        unsafe { ffi::spReflectionTypeLayout_GetElementStride(self.0.as_ptr(), category as _) }
     }
 
-    pub fn element_type_layout(&self) -> Option<&mut TypeLayout> {
-        let p = unsafe { ffi::spReflectionTypeLayout_GetElementTypeLayout(self.0.get()) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { TypeLayout::from_mut_ptr(p) })
-        }
+    pub fn element_type_layout(self) -> Option<TypeLayout<'a>> {
+        let p = unsafe { ffi::spReflectionTypeLayout_GetElementTypeLayout(self.0.as_ptr()) };
+        unsafe { TypeLayout::from_nullable(p) }
     }
 
-    pub fn element_var_layout(&self) -> Option<&mut VariableLayout> {
-        let p = unsafe { ffi::spReflectionTypeLayout_GetElementVarLayout(self.0.get()) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { VariableLayout::from_mut_ptr(p) })
-        }
+    pub fn element_var_layout(self) -> Option<VariableLayout<'a>> {
+        let p = unsafe { ffi::spReflectionTypeLayout_GetElementVarLayout(self.0.as_ptr()) };
+        unsafe { VariableLayout::from_nullable(p) }
     }
 
-    pub fn container_var_layout(&self) -> Option<&mut VariableLayout> {
-        let p = unsafe { ffi::spReflectionTypeLayout_getContainerVarLayout(self.0.get()) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { VariableLayout::from_mut_ptr(p) })
-        }
+    pub fn container_var_layout(self) -> Option<VariableLayout<'a>> {
+        let p = unsafe { ffi::spReflectionTypeLayout_getContainerVarLayout(self.0.as_ptr()) };
+        unsafe { VariableLayout::from_nullable(p) }
     }
 
     #[inline]
-    pub fn parameter_category(&self) -> ParameterCategory {
+    pub fn parameter_category(self) -> ParameterCategory {
         unsafe {
             core::mem::transmute(ffi::spReflectionTypeLayout_GetParameterCategory(
-                self.0.get(),
+                self.0.as_ptr(),
             ))
         }
     }
 
     #[inline]
-    pub fn category_count(&self) -> c_uint {
-        unsafe { ffi::spReflectionTypeLayout_GetCategoryCount(self.0.get()) }
+    pub fn category_count(self) -> c_uint {
+        unsafe { ffi::spReflectionTypeLayout_GetCategoryCount(self.0.as_ptr()) }
     }
 
     #[inline]
-    pub fn category(&self, index: c_uint) -> ParameterCategory {
+    pub fn category(self, index: c_uint) -> ParameterCategory {
         unsafe {
             core::mem::transmute(ffi::spReflectionTypeLayout_GetCategoryByIndex(
-                self.0.get(),
+                self.0.as_ptr(),
                 index,
             ))
         }
     }
 
     #[inline(always)]
-    pub fn iter_category<'x>(&'x self) -> TypeLayoutCategoryIterator<'x> {
+    pub fn iter_category(self) -> TypeLayoutCategoryIterator<'a> {
         TypeLayoutCategoryIterator {
             refl: self,
             current: 0,
@@ -517,157 +527,148 @@ impl TypeLayout {
     }
 
     #[inline(always)]
-    pub fn row_count(&self) -> c_uint {
-        self.r#type().row_count()
+    pub fn row_count(self) -> c_uint {
+        self.r#type().map_or(0, Type::row_count)
     }
 
     #[inline(always)]
-    pub fn column_count(&self) -> c_uint {
-        self.r#type().column_count()
+    pub fn column_count(self) -> c_uint {
+        self.r#type().map_or(0, Type::column_count)
     }
 
     #[inline(always)]
-    pub fn scalar_type(&self) -> ScalarType {
-        self.r#type().scalar_type()
+    pub fn scalar_type(self) -> ScalarType {
+        self.r#type().map_or(ScalarType::None, Type::scalar_type)
     }
 
     #[inline(always)]
-    pub fn resource_result_type(&self) -> Option<&mut Type> {
-        self.r#type().resource_result_type()
+    pub fn resource_result_type(self) -> Option<Type<'a>> {
+        self.r#type()?.resource_result_type()
     }
 
     #[inline(always)]
-    pub fn resource_shape(&self) -> crate::ResourceShape {
-        self.r#type().resource_shape()
+    pub fn resource_shape(self) -> crate::ResourceShape {
+        self.r#type()
+            .map_or(ffi::SLANG_RESOURCE_NONE, Type::resource_shape)
     }
 
     #[inline(always)]
-    pub fn resource_access(&self) -> crate::ResourceAccess {
-        self.r#type().resource_access()
+    pub fn resource_access(self) -> crate::ResourceAccess {
+        self.r#type()
+            .map_or(ffi::SLANG_RESOURCE_ACCESS_NONE, Type::resource_access)
     }
 
     #[inline(always)]
-    pub fn name(&self) -> &CStr {
-        self.r#type().name()
+    pub fn name(self) -> Option<&'a CStr> {
+        self.r#type()?.name()
     }
 
     #[inline]
-    pub fn matrix_layout_mode(&self) -> crate::MatrixLayoutMode {
-        unsafe { ffi::spReflectionTypeLayout_GetMatrixLayoutMode(self.0.get()) }
+    pub fn matrix_layout_mode(self) -> crate::MatrixLayoutMode {
+        unsafe { ffi::spReflectionTypeLayout_GetMatrixLayoutMode(self.0.as_ptr()) }
     }
 
     #[inline]
-    pub fn generic_param_index(&self) -> c_int {
-        unsafe { ffi::spReflectionTypeLayout_getGenericParamIndex(self.0.get()) }
+    pub fn generic_param_index(self) -> c_int {
+        unsafe { ffi::spReflectionTypeLayout_getGenericParamIndex(self.0.as_ptr()) }
     }
 
-    pub fn pending_data_type_layout(&self) -> Option<&mut TypeLayout> {
-        let p = unsafe { ffi::spReflectionTypeLayout_getPendingDataTypeLayout(self.0.get()) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { TypeLayout::from_mut_ptr(p) })
-        }
+    pub fn pending_data_type_layout(self) -> Option<TypeLayout<'a>> {
+        let p = unsafe { ffi::spReflectionTypeLayout_getPendingDataTypeLayout(self.0.as_ptr()) };
+        unsafe { TypeLayout::from_nullable(p) }
     }
 
-    pub fn specialized_type_pending_data_var_layout(&self) -> Option<&mut VariableLayout> {
+    pub fn specialized_type_pending_data_var_layout(self) -> Option<VariableLayout<'a>> {
         let p = unsafe {
-            ffi::spReflectionTypeLayout_getSpecializedTypePendingDataVarLayout(self.0.get())
+            ffi::spReflectionTypeLayout_getSpecializedTypePendingDataVarLayout(self.0.as_ptr())
         };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { VariableLayout::from_mut_ptr(p) })
-        }
+        unsafe { VariableLayout::from_nullable(p) }
     }
 
     #[inline]
-    pub fn binding_range_count(&self) -> SlangInt {
-        unsafe { ffi::spReflectionTypeLayout_getBindingRangeCount(self.0.get()) }
+    pub fn binding_range_count(self) -> SlangInt {
+        unsafe { ffi::spReflectionTypeLayout_getBindingRangeCount(self.0.as_ptr()) }
     }
 
     #[inline]
-    pub fn binding_range_type(&self, index: SlangInt) -> BindingType {
+    pub fn binding_range_type(self, index: SlangInt) -> BindingType {
         unsafe {
             core::mem::transmute(ffi::spReflectionTypeLayout_getBindingRangeType(
-                self.0.get(),
+                self.0.as_ptr(),
                 index,
             ))
         }
     }
 
     #[inline]
-    pub fn is_binding_range_specializable(&self, index: SlangInt) -> bool {
-        unsafe { ffi::spReflectionTypeLayout_isBindingRangeSpecializable(self.0.get(), index) != 0 }
+    pub fn is_binding_range_specializable(self, index: SlangInt) -> bool {
+        unsafe {
+            ffi::spReflectionTypeLayout_isBindingRangeSpecializable(self.0.as_ptr(), index) != 0
+        }
     }
 
     #[inline]
-    pub fn binding_range_binding_count(&self, index: SlangInt) -> SlangInt {
-        unsafe { ffi::spReflectionTypeLayout_getBindingRangeBindingCount(self.0.get(), index) }
+    pub fn binding_range_binding_count(self, index: SlangInt) -> SlangInt {
+        unsafe { ffi::spReflectionTypeLayout_getBindingRangeBindingCount(self.0.as_ptr(), index) }
     }
 
     #[inline]
-    pub fn field_binding_range_offset(&self, field_index: SlangInt) -> SlangInt {
-        unsafe { ffi::spReflectionTypeLayout_getFieldBindingRangeOffset(self.0.get(), field_index) }
+    pub fn field_binding_range_offset(self, field_index: SlangInt) -> SlangInt {
+        unsafe {
+            ffi::spReflectionTypeLayout_getFieldBindingRangeOffset(self.0.as_ptr(), field_index)
+        }
     }
 
     #[inline]
-    pub fn explicit_counter_binding_range_offset(&self) -> SlangInt {
-        unsafe { ffi::spReflectionTypeLayout_getExplicitCounterBindingRangeOffset(self.0.get()) }
+    pub fn explicit_counter_binding_range_offset(self) -> SlangInt {
+        unsafe { ffi::spReflectionTypeLayout_getExplicitCounterBindingRangeOffset(self.0.as_ptr()) }
     }
 
-    pub fn binding_range_leaf_type_layout(&self, index: SlangInt) -> Option<&mut TypeLayout> {
+    pub fn binding_range_leaf_type_layout(self, index: SlangInt) -> Option<TypeLayout<'a>> {
         let p = unsafe {
-            ffi::spReflectionTypeLayout_getBindingRangeLeafTypeLayout(self.0.get(), index)
+            ffi::spReflectionTypeLayout_getBindingRangeLeafTypeLayout(self.0.as_ptr(), index)
         };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { TypeLayout::from_mut_ptr(p) })
-        }
+        unsafe { TypeLayout::from_nullable(p) }
     }
 
-    pub fn binding_range_leaf_variable(&self, index: SlangInt) -> Option<&mut Variable> {
-        let p =
-            unsafe { ffi::spReflectionTypeLayout_getBindingRangeLeafVariable(self.0.get(), index) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { Variable::from_mut_ptr(p) })
-        }
+    pub fn binding_range_leaf_variable(self, index: SlangInt) -> Option<Variable<'a>> {
+        let p = unsafe {
+            ffi::spReflectionTypeLayout_getBindingRangeLeafVariable(self.0.as_ptr(), index)
+        };
+        unsafe { Variable::from_nullable(p) }
     }
 
     #[inline]
-    pub fn binding_range_image_format(&self, index: SlangInt) -> crate::ImageFormat {
-        unsafe { ffi::spReflectionTypeLayout_getBindingRAngeImageFormat(self.0.get(), index) }
+    pub fn binding_range_image_format(self, index: SlangInt) -> crate::ImageFormat {
+        unsafe { ffi::spReflectionTypeLayout_getBindingRAngeImageFormat(self.0.as_ptr(), index) }
     }
 
     #[inline]
-    pub fn binding_range_descriptor_set_index(&self, index: SlangInt) -> SlangInt {
+    pub fn binding_range_descriptor_set_index(self, index: SlangInt) -> SlangInt {
         unsafe {
-            ffi::spReflectionTypeLayout_getBindingRAngeDescriptorSetIndex(self.0.get(), index)
+            ffi::spReflectionTypeLayout_getBindingRAngeDescriptorSetIndex(self.0.as_ptr(), index)
         }
     }
 
     #[inline]
-    pub fn binding_range_first_descriptor_range_index(&self, index: SlangInt) -> SlangInt {
+    pub fn binding_range_first_descriptor_range_index(self, index: SlangInt) -> SlangInt {
         unsafe {
             ffi::spReflectionTypeLayout_getBindingRangeFirstDescriptorRangeIndex(
-                self.0.get(),
+                self.0.as_ptr(),
                 index,
             )
         }
     }
 
     #[inline]
-    pub fn binding_range_descriptor_range_count(&self, index: SlangInt) -> SlangInt {
+    pub fn binding_range_descriptor_range_count(self, index: SlangInt) -> SlangInt {
         unsafe {
-            ffi::spReflectionTypeLayout_getBindingRangeDescriptorRangeCount(self.0.get(), index)
+            ffi::spReflectionTypeLayout_getBindingRangeDescriptorRangeCount(self.0.as_ptr(), index)
         }
     }
 
     #[inline(always)]
-    pub fn iter_binding_range<'x>(&'x self) -> TypeLayoutBindingRangeIterator<'x> {
+    pub fn iter_binding_range(self) -> TypeLayoutBindingRangeIterator<'a> {
         TypeLayoutBindingRangeIterator {
             refl: self,
             current: 0,
@@ -676,20 +677,22 @@ impl TypeLayout {
     }
 
     #[inline]
-    pub fn descriptor_set_count(&self) -> SlangInt {
-        unsafe { ffi::spReflectionTypeLayout_getDescriptorSetCount(self.0.get()) }
+    pub fn descriptor_set_count(self) -> SlangInt {
+        unsafe { ffi::spReflectionTypeLayout_getDescriptorSetCount(self.0.as_ptr()) }
     }
 
     #[inline]
-    pub fn descriptor_set_space_offset(&self, set_index: SlangInt) -> SlangInt {
-        unsafe { ffi::spReflectionTypeLayout_getDescriptorSetSpaceOffset(self.0.get(), set_index) }
+    pub fn descriptor_set_space_offset(self, set_index: SlangInt) -> SlangInt {
+        unsafe {
+            ffi::spReflectionTypeLayout_getDescriptorSetSpaceOffset(self.0.as_ptr(), set_index)
+        }
     }
 
     #[inline]
-    pub fn descriptor_set_descriptor_range_count(&self, set_index: SlangInt) -> SlangInt {
+    pub fn descriptor_set_descriptor_range_count(self, set_index: SlangInt) -> SlangInt {
         unsafe {
             ffi::spReflectionTypeLayout_getDescriptorSetDescriptorRangeCount(
-                self.0.get(),
+                self.0.as_ptr(),
                 set_index,
             )
         }
@@ -697,13 +700,13 @@ impl TypeLayout {
 
     #[inline]
     pub fn descriptor_set_descriptor_range_index_offset(
-        &self,
+        self,
         set_index: SlangInt,
         range_index: SlangInt,
     ) -> SlangInt {
         unsafe {
             ffi::spReflectionTypeLayout_getDescriptorSetDescriptorRangeIndexOffset(
-                self.0.get(),
+                self.0.as_ptr(),
                 set_index,
                 range_index,
             )
@@ -712,13 +715,13 @@ impl TypeLayout {
 
     #[inline]
     pub fn descriptor_set_descriptor_range_descriptor_count(
-        &self,
+        self,
         set_index: SlangInt,
         range_index: SlangInt,
     ) -> SlangInt {
         unsafe {
             ffi::spReflectionTypeLayout_getDescriptorSetDescriptorRangeDescriptorCount(
-                self.0.get(),
+                self.0.as_ptr(),
                 set_index,
                 range_index,
             )
@@ -727,14 +730,14 @@ impl TypeLayout {
 
     #[inline]
     pub fn descriptor_set_descriptor_range_type(
-        &self,
+        self,
         set_index: SlangInt,
         range_index: SlangInt,
     ) -> BindingType {
         unsafe {
             core::mem::transmute(
                 ffi::spReflectionTypeLayout_getDescriptorSetDescriptorRangeType(
-                    self.0.get(),
+                    self.0.as_ptr(),
                     set_index,
                     range_index,
                 ),
@@ -744,14 +747,14 @@ impl TypeLayout {
 
     #[inline]
     pub fn descriptor_set_descriptor_range_category(
-        &self,
+        self,
         set_index: SlangInt,
         range_index: SlangInt,
     ) -> ParameterCategory {
         unsafe {
             core::mem::transmute(
                 ffi::spReflectionTypeLayout_getDescriptorSetDescriptorRangeCategory(
-                    self.0.get(),
+                    self.0.as_ptr(),
                     set_index,
                     range_index,
                 ),
@@ -760,7 +763,7 @@ impl TypeLayout {
     }
 
     #[inline(always)]
-    pub fn iter_descriptor_set<'x>(&'x self) -> TypeLayoutDescriptorSetIterator<'x> {
+    pub fn iter_descriptor_set(self) -> TypeLayoutDescriptorSetIterator<'a> {
         TypeLayoutDescriptorSetIterator {
             refl: self,
             current: 0,
@@ -769,52 +772,48 @@ impl TypeLayout {
     }
 
     #[inline]
-    pub fn sub_object_range_count(&self) -> SlangInt {
-        unsafe { ffi::spReflectionTypeLayout_getSubObjectRangeCount(self.0.get()) }
+    pub fn sub_object_range_count(self) -> SlangInt {
+        unsafe { ffi::spReflectionTypeLayout_getSubObjectRangeCount(self.0.as_ptr()) }
     }
 
     #[inline]
     pub fn sub_object_range_binding_range_index(
-        &self,
+        self,
         sub_object_range_index: SlangInt,
     ) -> SlangInt {
         unsafe {
             ffi::spReflectionTypeLayout_getSubObjectRangeBindingRangeIndex(
-                self.0.get(),
+                self.0.as_ptr(),
                 sub_object_range_index,
             )
         }
     }
 
     #[inline]
-    pub fn sub_object_range_space_offset(&self, sub_object_range_index: SlangInt) -> SlangInt {
+    pub fn sub_object_range_space_offset(self, sub_object_range_index: SlangInt) -> SlangInt {
         unsafe {
             ffi::spReflectionTypeLayout_getSubObjectRangeSpaceOffset(
-                self.0.get(),
+                self.0.as_ptr(),
                 sub_object_range_index,
             )
         }
     }
 
     pub fn sub_object_range_offset(
-        &self,
+        self,
         sub_object_range_index: SlangInt,
-    ) -> Option<&mut VariableLayout> {
+    ) -> Option<VariableLayout<'a>> {
         let p = unsafe {
             ffi::spReflectionTypeLayout_getSubObjectRangeOffset(
-                self.0.get(),
+                self.0.as_ptr(),
                 sub_object_range_index,
             )
         };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { VariableLayout::from_mut_ptr(p) })
-        }
+        unsafe { VariableLayout::from_nullable(p) }
     }
 
     #[inline(always)]
-    pub fn iter_sub_object_range<'x>(&'x self) -> TypeLayoutSubObjectRangeIterator<'x> {
+    pub fn iter_sub_object_range(self) -> TypeLayoutSubObjectRangeIterator<'a> {
         TypeLayoutSubObjectRangeIterator {
             refl: self,
             current: 0,
@@ -832,7 +831,7 @@ iter_something!(
 );
 
 pub struct TypeLayoutCategoryIterator<'x> {
-    refl: &'x TypeLayout,
+    refl: TypeLayout<'x>,
     current: c_uint,
     count: c_uint,
 }
@@ -846,7 +845,7 @@ impl<'x> Iterator for TypeLayoutCategoryIterator<'x> {
 
         let r = unsafe {
             core::mem::transmute(ffi::spReflectionTypeLayout_GetCategoryByIndex(
-                self.refl.0.get(),
+                self.refl.0.as_ptr(),
                 self.current,
             ))
         };
@@ -855,60 +854,61 @@ impl<'x> Iterator for TypeLayoutCategoryIterator<'x> {
     }
 }
 
+#[derive(Clone, Copy)]
 pub struct TypeLayoutBindingRange<'x> {
-    refl: &'x TypeLayout,
+    refl: TypeLayout<'x>,
     index: SlangInt,
 }
 impl<'x> TypeLayoutBindingRange<'x> {
     #[inline(always)]
-    pub fn r#type(&self) -> BindingType {
+    pub fn r#type(self) -> BindingType {
         self.refl.binding_range_type(self.index)
     }
 
     #[inline(always)]
-    pub fn is_specializable(&self) -> bool {
+    pub fn is_specializable(self) -> bool {
         self.refl.is_binding_range_specializable(self.index)
     }
 
     #[inline(always)]
-    pub fn binding_count(&self) -> SlangInt {
+    pub fn binding_count(self) -> SlangInt {
         self.refl.binding_range_binding_count(self.index)
     }
 
     #[inline(always)]
-    pub fn leaf_type_layout(&self) -> Option<&mut TypeLayout> {
+    pub fn leaf_type_layout(self) -> Option<TypeLayout<'x>> {
         self.refl.binding_range_leaf_type_layout(self.index)
     }
 
     #[inline(always)]
-    pub fn leaf_variable(&self) -> Option<&mut Variable> {
+    pub fn leaf_variable(self) -> Option<Variable<'x>> {
         self.refl.binding_range_leaf_variable(self.index)
     }
 
     #[inline(always)]
-    pub fn image_format(&self) -> crate::ImageFormat {
+    pub fn image_format(self) -> crate::ImageFormat {
         self.refl.binding_range_image_format(self.index)
     }
 
     #[inline(always)]
-    pub fn descriptor_set_index(&self) -> SlangInt {
+    pub fn descriptor_set_index(self) -> SlangInt {
         self.refl.binding_range_descriptor_set_index(self.index)
     }
 
     #[inline(always)]
-    pub fn first_descriptor_range_index(&self) -> SlangInt {
+    pub fn first_descriptor_range_index(self) -> SlangInt {
         self.refl
             .binding_range_first_descriptor_range_index(self.index)
     }
 
     #[inline(always)]
-    pub fn descriptor_range_count(&self) -> SlangInt {
+    pub fn descriptor_range_count(self) -> SlangInt {
         self.refl.binding_range_descriptor_range_count(self.index)
     }
 }
 
 pub struct TypeLayoutBindingRangeIterator<'x> {
-    refl: &'x TypeLayout,
+    refl: TypeLayout<'x>,
     current: SlangInt,
     count: SlangInt,
 }
@@ -929,47 +929,48 @@ impl<'x> Iterator for TypeLayoutBindingRangeIterator<'x> {
     }
 }
 
+#[derive(Clone, Copy)]
 pub struct TypeLayoutDescriptorSet<'x> {
-    refl: &'x TypeLayout,
+    refl: TypeLayout<'x>,
     index: SlangInt,
 }
 impl<'x> TypeLayoutDescriptorSet<'x> {
     #[inline(always)]
-    pub fn space_offset(&self) -> SlangInt {
+    pub fn space_offset(self) -> SlangInt {
         self.refl.descriptor_set_space_offset(self.index)
     }
 
     #[inline(always)]
-    pub fn descriptor_range_count(&self) -> SlangInt {
+    pub fn descriptor_range_count(self) -> SlangInt {
         self.refl.descriptor_set_descriptor_range_count(self.index)
     }
 
     #[inline(always)]
-    pub fn descriptor_range_index_offset(&self, range_index: SlangInt) -> SlangInt {
+    pub fn descriptor_range_index_offset(self, range_index: SlangInt) -> SlangInt {
         self.refl
             .descriptor_set_descriptor_range_index_offset(self.index, range_index)
     }
 
     #[inline(always)]
-    pub fn descriptor_range_descriptor_count(&self, range_index: SlangInt) -> SlangInt {
+    pub fn descriptor_range_descriptor_count(self, range_index: SlangInt) -> SlangInt {
         self.refl
             .descriptor_set_descriptor_range_descriptor_count(self.index, range_index)
     }
 
     #[inline(always)]
-    pub fn descriptor_range_type(&self, range_index: SlangInt) -> BindingType {
+    pub fn descriptor_range_type(self, range_index: SlangInt) -> BindingType {
         self.refl
             .descriptor_set_descriptor_range_type(self.index, range_index)
     }
 
     #[inline(always)]
-    pub fn descriptor_range_category(&self, range_index: SlangInt) -> ParameterCategory {
+    pub fn descriptor_range_category(self, range_index: SlangInt) -> ParameterCategory {
         self.refl
             .descriptor_set_descriptor_range_category(self.index, range_index)
     }
 
     #[inline(always)]
-    pub fn iter_descriptor_range(&self) -> TypeLayoutDescriptorSetDescriptorRangeIterator<'x> {
+    pub fn iter_descriptor_range(self) -> TypeLayoutDescriptorSetDescriptorRangeIterator<'x> {
         TypeLayoutDescriptorSetDescriptorRangeIterator {
             refl: self.refl,
             set_index: self.index,
@@ -980,7 +981,7 @@ impl<'x> TypeLayoutDescriptorSet<'x> {
 }
 
 pub struct TypeLayoutDescriptorSetIterator<'x> {
-    refl: &'x TypeLayout,
+    refl: TypeLayout<'x>,
     current: SlangInt,
     count: SlangInt,
 }
@@ -1001,39 +1002,40 @@ impl<'x> Iterator for TypeLayoutDescriptorSetIterator<'x> {
     }
 }
 
+#[derive(Clone, Copy)]
 pub struct TypeLayoutDescriptorSetDescriptorRange<'x> {
-    refl: &'x TypeLayout,
+    refl: TypeLayout<'x>,
     set_index: SlangInt,
     range_index: SlangInt,
 }
 impl<'x> TypeLayoutDescriptorSetDescriptorRange<'x> {
     #[inline(always)]
-    pub fn index_offset(&self) -> SlangInt {
+    pub fn index_offset(self) -> SlangInt {
         self.refl
             .descriptor_set_descriptor_range_index_offset(self.set_index, self.range_index)
     }
 
     #[inline(always)]
-    pub fn descriptor_count(&self) -> SlangInt {
+    pub fn descriptor_count(self) -> SlangInt {
         self.refl
             .descriptor_set_descriptor_range_descriptor_count(self.set_index, self.range_index)
     }
 
     #[inline(always)]
-    pub fn r#type(&self) -> BindingType {
+    pub fn r#type(self) -> BindingType {
         self.refl
             .descriptor_set_descriptor_range_type(self.set_index, self.range_index)
     }
 
     #[inline(always)]
-    pub fn category(&self) -> ParameterCategory {
+    pub fn category(self) -> ParameterCategory {
         self.refl
             .descriptor_set_descriptor_range_category(self.set_index, self.range_index)
     }
 }
 
 pub struct TypeLayoutDescriptorSetDescriptorRangeIterator<'x> {
-    refl: &'x TypeLayout,
+    refl: TypeLayout<'x>,
     set_index: SlangInt,
     current: SlangInt,
     count: SlangInt,
@@ -1056,29 +1058,30 @@ impl<'x> Iterator for TypeLayoutDescriptorSetDescriptorRangeIterator<'x> {
     }
 }
 
+#[derive(Clone, Copy)]
 pub struct TypeLayoutSubObjectRange<'x> {
-    refl: &'x TypeLayout,
+    refl: TypeLayout<'x>,
     index: SlangInt,
 }
 impl<'x> TypeLayoutSubObjectRange<'x> {
     #[inline(always)]
-    pub fn binding_range_index(&self) -> SlangInt {
+    pub fn binding_range_index(self) -> SlangInt {
         self.refl.sub_object_range_binding_range_index(self.index)
     }
 
     #[inline(always)]
-    pub fn space_offset(&self) -> SlangInt {
+    pub fn space_offset(self) -> SlangInt {
         self.refl.sub_object_range_space_offset(self.index)
     }
 
     #[inline(always)]
-    pub fn offset(&self) -> Option<&mut VariableLayout> {
+    pub fn offset(self) -> Option<VariableLayout<'x>> {
         self.refl.sub_object_range_offset(self.index)
     }
 }
 
 pub struct TypeLayoutSubObjectRangeIterator<'x> {
-    refl: &'x TypeLayout,
+    refl: TypeLayout<'x>,
     current: SlangInt,
     count: SlangInt,
 }
@@ -1099,48 +1102,35 @@ impl<'x> Iterator for TypeLayoutSubObjectRangeIterator<'x> {
     }
 }
 
-#[repr(transparent)]
-pub struct Variable(UnsafeCell<ffi::SlangReflectionVariable>);
-impl Variable {
-    pub const unsafe fn from_mut_ptr<'a>(ptr: *mut ffi::SlangReflectionVariable) -> &'a mut Self {
-        unsafe { core::mem::transmute(UnsafeCell::from_mut(&mut *ptr)) }
+reflection_handle!(Variable, ffi::SlangReflectionVariable);
+impl<'a> Variable<'a> {
+    pub fn name(self) -> Option<&'a CStr> {
+        let p = unsafe { ffi::spReflectionVariable_GetName(self.0.as_ptr()) };
+        unsafe { nullable_str(p) }
     }
 
-    #[inline]
-    pub fn name(&self) -> &CStr {
-        unsafe { CStr::from_ptr(ffi::spReflectionVariable_GetName(self.0.get())) }
+    pub fn r#type(self) -> Option<Type<'a>> {
+        let p = unsafe { ffi::spReflectionVariable_GetType(self.0.as_ptr()) };
+        unsafe { Type::from_nullable(p) }
     }
 
-    #[inline]
-    pub fn r#type(&self) -> &mut Type {
-        unsafe { Type::from_mut_ptr(ffi::spReflectionVariable_GetType(self.0.get())) }
-    }
-
-    pub fn find_modifier(&self, id: crate::ModifierID) -> Option<&mut Modifier> {
-        let p = unsafe { ffi::spReflectionVariable_FindModifier(self.0.get(), id) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { Modifier::from_mut_ptr(p) })
-        }
+    pub fn find_modifier(self, id: crate::ModifierID) -> Option<Modifier<'a>> {
+        let p = unsafe { ffi::spReflectionVariable_FindModifier(self.0.as_ptr(), id) };
+        unsafe { Modifier::from_nullable(p) }
     }
 
     #[inline]
-    pub fn user_attribute_count(&self) -> c_uint {
-        unsafe { ffi::spReflectionVariable_GetUserAttributeCount(self.0.get()) }
+    pub fn user_attribute_count(self) -> c_uint {
+        unsafe { ffi::spReflectionVariable_GetUserAttributeCount(self.0.as_ptr()) }
     }
 
-    pub fn user_attribute(&self, index: c_uint) -> Option<&mut Attribute> {
-        let p = unsafe { ffi::spReflectionVariable_GetUserAttribute(self.0.get(), index) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { Attribute::from_mut_ptr(p) })
-        }
+    pub fn user_attribute(self, index: c_uint) -> Option<Attribute<'a>> {
+        let p = unsafe { ffi::spReflectionVariable_GetUserAttribute(self.0.as_ptr(), index) };
+        unsafe { Attribute::from_nullable(p) }
     }
 
     #[inline(always)]
-    pub fn iter_user_attribute<'x>(&'x self) -> VariableUserAttributeIterator<'x> {
+    pub fn iter_user_attribute(self) -> VariableUserAttributeIterator<'a> {
         VariableUserAttributeIterator {
             refl: self,
             current: 0,
@@ -1149,55 +1139,44 @@ impl Variable {
     }
 
     pub fn find_user_attribute_by_name(
-        &self,
+        self,
         global_session: &impl IGlobalSession,
         name: &CStr,
-    ) -> Option<&mut Attribute> {
+    ) -> Option<Attribute<'a>> {
         let p = unsafe {
             ffi::spReflectionVariable_FindUserAttributeByName(
-                self.0.get(),
+                self.0.as_ptr(),
                 global_session.thisptr(),
                 name.as_ptr(),
             )
         };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { Attribute::from_mut_ptr(p) })
-        }
+        unsafe { Attribute::from_nullable(p) }
     }
 
     #[inline]
-    pub fn has_default_value(&self) -> bool {
-        unsafe { ffi::spReflectionVariable_HasDefaultValue(self.0.get()) }
+    pub fn has_default_value(self) -> bool {
+        unsafe { ffi::spReflectionVariable_HasDefaultValue(self.0.as_ptr()) }
     }
 
-    pub fn default_value_int(&self) -> crate::Result<i64> {
+    pub fn default_value_int(self) -> crate::Result<i64> {
         let mut o = MaybeUninit::uninit();
         crate::rw(unsafe {
-            ffi::spReflectionVariable_GetDefaultValueInt(self.0.get(), o.as_mut_ptr())
+            ffi::spReflectionVariable_GetDefaultValueInt(self.0.as_ptr(), o.as_mut_ptr())
         })?;
 
         Ok(unsafe { o.assume_init() })
     }
 
-    pub fn generic_container(&self) -> Option<&mut Generic> {
-        let p = unsafe { ffi::spReflectionVariable_GetGenericContainer(self.0.get()) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { Generic::from_mut_ptr(p) })
-        }
+    pub fn generic_container(self) -> Option<Generic<'a>> {
+        let p = unsafe { ffi::spReflectionVariable_GetGenericContainer(self.0.as_ptr()) };
+        unsafe { Generic::from_nullable(p) }
     }
 
-    #[inline]
-    pub fn apply_specializations(&self, generic: &Generic) -> &mut Variable {
-        unsafe {
-            Variable::from_mut_ptr(ffi::spReflectionVariable_applySpecializations(
-                self.0.get(),
-                generic.0.get(),
-            ))
-        }
+    pub fn apply_specializations(self, generic: Generic<'_>) -> Option<Variable<'a>> {
+        let p = unsafe {
+            ffi::spReflectionVariable_applySpecializations(self.0.as_ptr(), generic.0.as_ptr())
+        };
+        unsafe { Variable::from_nullable(p) }
     }
 }
 
@@ -1209,149 +1188,128 @@ iter_something!(
     c_uint
 );
 
-#[repr(transparent)]
-pub struct VariableLayout(UnsafeCell<ffi::SlangReflectionVariableLayout>);
-impl VariableLayout {
-    pub const unsafe fn from_mut_ptr<'a>(
-        ptr: *mut ffi::SlangReflectionVariableLayout,
-    ) -> &'a mut Self {
-        unsafe { core::mem::transmute(UnsafeCell::from_mut(&mut *ptr)) }
-    }
-
-    #[inline]
-    pub fn variable(&self) -> &mut Variable {
-        unsafe { Variable::from_mut_ptr(ffi::spReflectionVariableLayout_GetVariable(self.0.get())) }
+reflection_handle!(VariableLayout, ffi::SlangReflectionVariableLayout);
+impl<'a> VariableLayout<'a> {
+    /// The variable this layout was computed for; `None` for synthesized layouts such as an entry point result.
+    pub fn variable(self) -> Option<Variable<'a>> {
+        let p = unsafe { ffi::spReflectionVariableLayout_GetVariable(self.0.as_ptr()) };
+        unsafe { Variable::from_nullable(p) }
     }
 
     #[inline(always)]
-    pub fn name(&self) -> &CStr {
-        self.variable().name()
+    pub fn name(self) -> Option<&'a CStr> {
+        self.variable()?.name()
     }
 
     #[inline(always)]
-    pub fn find_modifier(&self, id: crate::ModifierID) -> Option<&mut Modifier> {
-        self.variable().find_modifier(id)
+    pub fn find_modifier(self, id: crate::ModifierID) -> Option<Modifier<'a>> {
+        self.variable()?.find_modifier(id)
     }
 
     #[inline]
-    pub fn type_layout(&self) -> &mut TypeLayout {
+    pub fn type_layout(self) -> TypeLayout<'a> {
         unsafe {
-            TypeLayout::from_mut_ptr(ffi::spReflectionVariableLayout_GetTypeLayout(self.0.get()))
+            TypeLayout::from_raw(ffi::spReflectionVariableLayout_GetTypeLayout(
+                self.0.as_ptr(),
+            ))
         }
     }
 
     #[inline(always)]
-    pub fn category(&self) -> ParameterCategory {
+    pub fn category(self) -> ParameterCategory {
         self.type_layout().parameter_category()
     }
 
     #[inline(always)]
-    pub fn category_count(&self) -> c_uint {
+    pub fn category_count(self) -> c_uint {
         self.type_layout().category_count()
     }
 
     #[inline(always)]
-    pub fn category_by_index(&self, index: c_uint) -> ParameterCategory {
+    pub fn category_by_index(self, index: c_uint) -> ParameterCategory {
         self.type_layout().category(index)
     }
 
     #[inline(always)]
-    pub fn iter_category<'x>(&'x self) -> TypeLayoutCategoryIterator<'x> {
+    pub fn iter_category(self) -> TypeLayoutCategoryIterator<'a> {
         self.type_layout().iter_category()
     }
 
     #[inline]
-    pub fn offset(&self, category: ParameterCategory) -> usize {
-        unsafe { ffi::spReflectionVariableLayout_GetOffset(self.0.get(), category as _) }
+    pub fn offset(self, category: ParameterCategory) -> usize {
+        unsafe { ffi::spReflectionVariableLayout_GetOffset(self.0.as_ptr(), category as _) }
     }
 
     #[inline(always)]
-    pub fn r#type(&self) -> &mut Type {
-        self.variable().r#type()
+    pub fn r#type(self) -> Option<Type<'a>> {
+        self.variable()?.r#type()
     }
 
     #[inline]
-    pub fn binding_index(&self) -> c_uint {
-        unsafe { ffi::spReflectionParameter_GetBindingIndex(self.0.get()) }
+    pub fn binding_index(self) -> c_uint {
+        unsafe { ffi::spReflectionParameter_GetBindingIndex(self.0.as_ptr()) }
     }
 
     #[inline]
-    pub fn binding_space(&self) -> c_uint {
-        unsafe { ffi::spReflectionParameter_GetBindingSpace(self.0.get()) }
+    pub fn binding_space(self) -> c_uint {
+        unsafe { ffi::spReflectionParameter_GetBindingSpace(self.0.as_ptr()) }
     }
 
     #[inline]
-    pub fn space(&self, category: ParameterCategory) -> usize {
-        unsafe { ffi::spReflectionVariableLayout_GetSpace(self.0.get(), category as _) }
+    pub fn space(self, category: ParameterCategory) -> usize {
+        unsafe { ffi::spReflectionVariableLayout_GetSpace(self.0.as_ptr(), category as _) }
     }
 
     #[inline]
-    pub fn image_format(&self) -> crate::ImageFormat {
-        unsafe { ffi::spReflectionVariableLayout_GetImageFormat(self.0.get()) }
+    pub fn image_format(self) -> crate::ImageFormat {
+        unsafe { ffi::spReflectionVariableLayout_GetImageFormat(self.0.as_ptr()) }
     }
 
-    pub fn semantic_name(&self) -> Option<&CStr> {
-        let p = unsafe { ffi::spReflectionVariableLayout_GetSemanticName(self.0.get()) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { CStr::from_ptr(p) })
-        }
+    pub fn semantic_name(self) -> Option<&'a CStr> {
+        let p = unsafe { ffi::spReflectionVariableLayout_GetSemanticName(self.0.as_ptr()) };
+        unsafe { nullable_str(p) }
     }
 
     #[inline]
-    pub fn semantic_index(&self) -> usize {
-        unsafe { ffi::spReflectionVariableLayout_GetSemanticIndex(self.0.get()) }
+    pub fn semantic_index(self) -> usize {
+        unsafe { ffi::spReflectionVariableLayout_GetSemanticIndex(self.0.as_ptr()) }
     }
 
     #[inline]
-    pub fn stage(&self) -> crate::Stage {
-        unsafe { ffi::spReflectionVariableLayout_getStage(self.0.get()) }
+    pub fn stage(self) -> crate::Stage {
+        unsafe { ffi::spReflectionVariableLayout_getStage(self.0.as_ptr()) }
     }
 
-    pub fn pending_data_layout(&self) -> Option<&mut VariableLayout> {
-        let p = unsafe { ffi::spReflectionVariableLayout_getPendingDataLayout(self.0.get()) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { VariableLayout::from_mut_ptr(p) })
-        }
+    pub fn pending_data_layout(self) -> Option<VariableLayout<'a>> {
+        let p = unsafe { ffi::spReflectionVariableLayout_getPendingDataLayout(self.0.as_ptr()) };
+        unsafe { VariableLayout::from_nullable(p) }
     }
 }
 
-#[repr(transparent)]
-pub struct Function(UnsafeCell<ffi::SlangReflectionFunction>);
-impl Function {
-    pub const unsafe fn from_mut_ptr<'a>(ptr: *mut ffi::SlangReflectionFunction) -> &'a mut Self {
-        unsafe { core::mem::transmute(UnsafeCell::from_mut(&mut *ptr)) }
-    }
-
-    #[inline]
-    pub fn name(&self) -> &CStr {
-        unsafe { CStr::from_ptr(ffi::spReflectionFunction_GetName(self.0.get())) }
+reflection_handle!(Function, ffi::SlangReflectionFunction);
+impl<'a> Function<'a> {
+    pub fn name(self) -> Option<&'a CStr> {
+        let p = unsafe { ffi::spReflectionFunction_GetName(self.0.as_ptr()) };
+        unsafe { nullable_str(p) }
     }
 
-    #[inline]
-    pub fn result_type(&self) -> &mut Type {
-        unsafe { Type::from_mut_ptr(ffi::spReflectionFunction_GetResultType(self.0.get())) }
+    pub fn result_type(self) -> Option<Type<'a>> {
+        let p = unsafe { ffi::spReflectionFunction_GetResultType(self.0.as_ptr()) };
+        unsafe { Type::from_nullable(p) }
     }
 
     #[inline]
-    pub fn parameter_count(&self) -> c_uint {
-        unsafe { ffi::spReflectionFunction_GetParameterCount(self.0.get()) }
+    pub fn parameter_count(self) -> c_uint {
+        unsafe { ffi::spReflectionFunction_GetParameterCount(self.0.as_ptr()) }
     }
 
-    pub fn parameter(&self, index: c_uint) -> Option<&mut Variable> {
-        let p = unsafe { ffi::spReflectionFunction_GetParameter(self.0.get(), index) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { Variable::from_mut_ptr(p) })
-        }
+    pub fn parameter(self, index: c_uint) -> Option<Variable<'a>> {
+        let p = unsafe { ffi::spReflectionFunction_GetParameter(self.0.as_ptr(), index) };
+        unsafe { Variable::from_nullable(p) }
     }
 
     #[inline(always)]
-    pub fn iter_parameter<'x>(&'x self) -> FunctionParameterIterator<'x> {
+    pub fn iter_parameter(self) -> FunctionParameterIterator<'a> {
         FunctionParameterIterator {
             refl: self,
             current: 0,
@@ -1360,21 +1318,17 @@ impl Function {
     }
 
     #[inline]
-    pub fn user_attribute_count(&self) -> c_uint {
-        unsafe { ffi::spReflectionFunction_GetUserAttributeCount(self.0.get()) }
+    pub fn user_attribute_count(self) -> c_uint {
+        unsafe { ffi::spReflectionFunction_GetUserAttributeCount(self.0.as_ptr()) }
     }
 
-    pub fn user_attribute(&self, index: c_uint) -> Option<&mut Attribute> {
-        let p = unsafe { ffi::spReflectionFunction_GetUserAttribute(self.0.get(), index) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { Attribute::from_mut_ptr(p) })
-        }
+    pub fn user_attribute(self, index: c_uint) -> Option<Attribute<'a>> {
+        let p = unsafe { ffi::spReflectionFunction_GetUserAttribute(self.0.as_ptr(), index) };
+        unsafe { Attribute::from_nullable(p) }
     }
 
     #[inline(always)]
-    pub fn iter_user_attribute<'x>(&'x self) -> FunctionUserAttributeIterator<'x> {
+    pub fn iter_user_attribute(self) -> FunctionUserAttributeIterator<'a> {
         FunctionUserAttributeIterator {
             refl: self,
             current: 0,
@@ -1383,84 +1337,65 @@ impl Function {
     }
 
     pub fn find_user_attribute_by_name(
-        &self,
+        self,
         global_session: &impl IGlobalSession,
         name: &CStr,
-    ) -> Option<&mut Attribute> {
+    ) -> Option<Attribute<'a>> {
         let p = unsafe {
             ffi::spReflectionFunction_FindUserAttributeByName(
-                self.0.get(),
+                self.0.as_ptr(),
                 global_session.thisptr(),
                 name.as_ptr(),
             )
         };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { Attribute::from_mut_ptr(p) })
-        }
+        unsafe { Attribute::from_nullable(p) }
     }
 
-    pub fn find_modifier(&self, id: crate::ModifierID) -> Option<&mut Modifier> {
-        let p = unsafe { ffi::spReflectionFunction_FindModifier(self.0.get(), id) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { Modifier::from_mut_ptr(p) })
-        }
+    pub fn find_modifier(self, id: crate::ModifierID) -> Option<Modifier<'a>> {
+        let p = unsafe { ffi::spReflectionFunction_FindModifier(self.0.as_ptr(), id) };
+        unsafe { Modifier::from_nullable(p) }
     }
 
-    pub fn generic_container(&self) -> Option<&mut Generic> {
-        let p = unsafe { ffi::spReflectionFunction_GetGenericContainer(self.0.get()) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { Generic::from_mut_ptr(p) })
-        }
+    pub fn generic_container(self) -> Option<Generic<'a>> {
+        let p = unsafe { ffi::spReflectionFunction_GetGenericContainer(self.0.as_ptr()) };
+        unsafe { Generic::from_nullable(p) }
     }
 
-    #[inline]
-    pub fn apply_specializations(&self, generic: &Generic) -> &mut Function {
-        unsafe {
-            Function::from_mut_ptr(ffi::spReflectionFunction_applySpecializations(
-                self.0.get(),
-                generic.0.get(),
-            ))
-        }
+    pub fn apply_specializations(self, generic: Generic<'_>) -> Option<Function<'a>> {
+        let p = unsafe {
+            ffi::spReflectionFunction_applySpecializations(self.0.as_ptr(), generic.0.as_ptr())
+        };
+        unsafe { Function::from_nullable(p) }
     }
 
-    #[inline]
-    pub fn specialize_with_arg_types(&self, arg_types: &[&Type]) -> &mut Function {
-        unsafe {
-            Function::from_mut_ptr(ffi::spReflectionFunction_specializeWithArgTypes(
-                self.0.get(),
+    pub fn specialize_with_arg_types(self, arg_types: &[Type<'_>]) -> Option<Function<'a>> {
+        let p = unsafe {
+            ffi::spReflectionFunction_specializeWithArgTypes(
+                self.0.as_ptr(),
                 arg_types.len() as _,
                 arg_types.as_ptr() as _,
-            ))
-        }
+            )
+        };
+        unsafe { Function::from_nullable(p) }
     }
 
     #[inline]
-    pub fn is_overloaded(&self) -> bool {
-        unsafe { ffi::spReflectionFunction_isOverloaded(self.0.get()) }
+    pub fn is_overloaded(self) -> bool {
+        unsafe { ffi::spReflectionFunction_isOverloaded(self.0.as_ptr()) }
     }
 
     #[inline]
-    pub fn overload_count(&self) -> c_uint {
-        unsafe { ffi::spReflectionFunction_getOverloadCount(self.0.get()) }
+    pub fn overload_count(self) -> c_uint {
+        unsafe { ffi::spReflectionFunction_getOverloadCount(self.0.as_ptr()) }
     }
 
-    pub fn overload(&self, index: c_uint) -> Option<&mut Function> {
-        let p = unsafe { ffi::spReflectionFunction_getOverload(self.0.get(), index) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { Function::from_mut_ptr(p) })
-        }
+    pub fn overload(self, index: c_uint) -> Option<Function<'a>> {
+        let p = unsafe { ffi::spReflectionFunction_getOverload(self.0.as_ptr(), index) };
+        unsafe { Function::from_nullable(p) }
     }
 
     #[inline(always)]
-    pub fn iter_overload<'x>(&'x self) -> FunctionOverloadIterator<'x> {
+    pub fn iter_overload(self) -> FunctionOverloadIterator<'a> {
         FunctionOverloadIterator {
             refl: self,
             current: 0,
@@ -1491,39 +1426,30 @@ iter_something!(
     c_uint
 );
 
-#[repr(transparent)]
-pub struct Generic(UnsafeCell<ffi::SlangReflectionGeneric>);
-impl Generic {
-    pub const unsafe fn from_mut_ptr<'a>(ptr: *mut ffi::SlangReflectionGeneric) -> &'a mut Self {
-        unsafe { core::mem::transmute(UnsafeCell::from_mut(&mut *ptr)) }
-    }
-
-    #[inline]
-    pub fn as_decl(&self) -> &mut Decl {
-        unsafe { Decl::from_mut_ptr(ffi::spReflectionGeneric_asDecl(self.0.get())) }
+reflection_handle!(Generic, ffi::SlangReflectionGeneric);
+impl<'a> Generic<'a> {
+    pub fn as_decl(self) -> Option<Decl<'a>> {
+        let p = unsafe { ffi::spReflectionGeneric_asDecl(self.0.as_ptr()) };
+        unsafe { Decl::from_nullable(p) }
     }
 
-    #[inline]
-    pub fn name(&self) -> &CStr {
-        unsafe { CStr::from_ptr(ffi::spReflectionGeneric_GetName(self.0.get())) }
+    pub fn name(self) -> Option<&'a CStr> {
+        let p = unsafe { ffi::spReflectionGeneric_GetName(self.0.as_ptr()) };
+        unsafe { nullable_str(p) }
     }
 
     #[inline]
-    pub fn type_parameter_count(&self) -> c_uint {
-        unsafe { ffi::spReflectionGeneric_GetTypeParameterCount(self.0.get()) }
+    pub fn type_parameter_count(self) -> c_uint {
+        unsafe { ffi::spReflectionGeneric_GetTypeParameterCount(self.0.as_ptr()) }
     }
 
-    pub fn type_parameter(&self, index: c_uint) -> Option<&mut Variable> {
-        let p = unsafe { ffi::spReflectionGeneric_GetTypeParameter(self.0.get(), index) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { Variable::from_mut_ptr(p) })
-        }
+    pub fn type_parameter(self, index: c_uint) -> Option<Variable<'a>> {
+        let p = unsafe { ffi::spReflectionGeneric_GetTypeParameter(self.0.as_ptr(), index) };
+        unsafe { Variable::from_nullable(p) }
     }
 
     #[inline(always)]
-    pub fn iter_type_parameter<'x>(&'x self) -> GenericTypeParameterIterator<'x> {
+    pub fn iter_type_parameter(self) -> GenericTypeParameterIterator<'a> {
         GenericTypeParameterIterator {
             refl: self,
             current: 0,
@@ -1532,21 +1458,17 @@ impl Generic {
     }
 
     #[inline]
-    pub fn value_parameter_count(&self) -> c_uint {
-        unsafe { ffi::spReflectionGeneric_GetValueParameterCount(self.0.get()) }
+    pub fn value_parameter_count(self) -> c_uint {
+        unsafe { ffi::spReflectionGeneric_GetValueParameterCount(self.0.as_ptr()) }
     }
 
-    pub fn value_parameter(&self, index: c_uint) -> Option<&mut Variable> {
-        let p = unsafe { ffi::spReflectionGeneric_GetValueParameter(self.0.get(), index) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { Variable::from_mut_ptr(p) })
-        }
+    pub fn value_parameter(self, index: c_uint) -> Option<Variable<'a>> {
+        let p = unsafe { ffi::spReflectionGeneric_GetValueParameter(self.0.as_ptr(), index) };
+        unsafe { Variable::from_nullable(p) }
     }
 
     #[inline(always)]
-    pub fn iter_value_parameter<'x>(&'x self) -> GenericValueParameterIterator<'x> {
+    pub fn iter_value_parameter(self) -> GenericValueParameterIterator<'a> {
         GenericValueParameterIterator {
             refl: self,
             current: 0,
@@ -1555,81 +1477,65 @@ impl Generic {
     }
 
     #[inline]
-    pub fn type_parameter_constraint_count(&self, type_param: &Variable) -> c_uint {
+    pub fn type_parameter_constraint_count(self, type_param: Variable<'_>) -> c_uint {
         unsafe {
             ffi::spReflectionGeneric_GetTypeParameterConstraintCount(
-                self.0.get(),
-                type_param.0.get(),
+                self.0.as_ptr(),
+                type_param.0.as_ptr(),
             )
         }
     }
 
     pub fn type_parameter_constraint_type(
-        &self,
-        type_param: &Variable,
+        self,
+        type_param: Variable<'_>,
         index: c_uint,
-    ) -> Option<&mut Type> {
+    ) -> Option<Type<'a>> {
         let p = unsafe {
             ffi::spReflectionGeneric_GetTypeParameterConstraintType(
-                self.0.get(),
-                type_param.0.get(),
+                self.0.as_ptr(),
+                type_param.0.as_ptr(),
                 index,
             )
         };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { Type::from_mut_ptr(p) })
-        }
+        unsafe { Type::from_nullable(p) }
     }
 
-    pub fn inner_decl(&self) -> Option<&mut Decl> {
-        let p = unsafe { ffi::spReflectionGeneric_GetInnerDecl(self.0.get()) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { Decl::from_mut_ptr(p) })
-        }
+    pub fn inner_decl(self) -> Option<Decl<'a>> {
+        let p = unsafe { ffi::spReflectionGeneric_GetInnerDecl(self.0.as_ptr()) };
+        unsafe { Decl::from_nullable(p) }
     }
 
     #[inline]
-    pub fn inner_kind(&self) -> crate::DeclKind {
-        unsafe { ffi::spReflectionGeneric_GetInnerKind(self.0.get()) }
+    pub fn inner_kind(self) -> crate::DeclKind {
+        unsafe { ffi::spReflectionGeneric_GetInnerKind(self.0.as_ptr()) }
     }
 
-    pub fn outer_generic_container(&self) -> Option<&mut Generic> {
-        let p = unsafe { ffi::spReflectionGeneric_GetOuterGenericContainer(self.0.get()) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { Generic::from_mut_ptr(p) })
-        }
+    pub fn outer_generic_container(self) -> Option<Generic<'a>> {
+        let p = unsafe { ffi::spReflectionGeneric_GetOuterGenericContainer(self.0.as_ptr()) };
+        unsafe { Generic::from_nullable(p) }
     }
 
-    pub fn concrete_type(&self, type_param: &Variable) -> Option<&mut Type> {
-        let p =
-            unsafe { ffi::spReflectionGeneric_GetConcreteType(self.0.get(), type_param.0.get()) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { Type::from_mut_ptr(p) })
-        }
-    }
-
-    #[inline]
-    pub fn concrete_int_val(&self, value_param: &Variable) -> i64 {
-        unsafe { ffi::spReflectionGeneric_GetConcreteIntVal(self.0.get(), value_param.0.get()) }
+    pub fn concrete_type(self, type_param: Variable<'_>) -> Option<Type<'a>> {
+        let p = unsafe {
+            ffi::spReflectionGeneric_GetConcreteType(self.0.as_ptr(), type_param.0.as_ptr())
+        };
+        unsafe { Type::from_nullable(p) }
     }
 
     #[inline]
-    pub fn apply_specializations(&self, generic: &Generic) -> &mut Generic {
+    pub fn concrete_int_val(self, value_param: Variable<'_>) -> i64 {
         unsafe {
-            Generic::from_mut_ptr(ffi::spReflectionGeneric_applySpecializations(
-                self.0.get(),
-                generic.0.get(),
-            ))
+            ffi::spReflectionGeneric_GetConcreteIntVal(self.0.as_ptr(), value_param.0.as_ptr())
         }
     }
+
+    pub fn apply_specializations(self, generic: Generic<'_>) -> Option<Generic<'a>> {
+        let p = unsafe {
+            ffi::spReflectionGeneric_applySpecializations(self.0.as_ptr(), generic.0.as_ptr())
+        };
+        unsafe { Generic::from_nullable(p) }
+    }
 }
 
 iter_something!(
@@ -1647,48 +1553,35 @@ iter_something!(
     c_uint
 );
 
-#[repr(transparent)]
-pub struct EntryPoint(UnsafeCell<ffi::SlangReflectionEntryPoint>);
-impl EntryPoint {
-    pub const unsafe fn from_mut_ptr<'a>(ptr: *mut ffi::SlangReflectionEntryPoint) -> &'a mut Self {
-        unsafe { core::mem::transmute(UnsafeCell::from_mut(&mut *ptr)) }
+reflection_handle!(EntryPoint, ffi::SlangReflectionEntryPoint);
+impl<'a> EntryPoint<'a> {
+    pub fn name(self) -> Option<&'a CStr> {
+        let p = unsafe { ffi::spReflectionEntryPoint_getName(self.0.as_ptr()) };
+        unsafe { nullable_str(p) }
     }
 
-    #[inline]
-    pub fn name(&self) -> &CStr {
-        unsafe { CStr::from_ptr(ffi::spReflectionEntryPoint_getName(self.0.get())) }
-    }
-
-    pub fn name_override(&self) -> Option<&CStr> {
-        let p = unsafe { ffi::spReflectionEntryPoint_getNameOverride(self.0.get()) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { CStr::from_ptr(p) })
-        }
+    pub fn name_override(self) -> Option<&'a CStr> {
+        let p = unsafe { ffi::spReflectionEntryPoint_getNameOverride(self.0.as_ptr()) };
+        unsafe { nullable_str(p) }
     }
 
     #[inline]
-    pub fn parameter_count(&self) -> c_uint {
-        unsafe { ffi::spReflectionEntryPoint_getParameterCount(self.0.get()) }
+    pub fn parameter_count(self) -> c_uint {
+        unsafe { ffi::spReflectionEntryPoint_getParameterCount(self.0.as_ptr()) }
     }
 
-    #[inline]
-    pub fn function(&self) -> &mut Function {
-        unsafe { Function::from_mut_ptr(ffi::spReflectionEntryPoint_getFunction(self.0.get())) }
+    pub fn function(self) -> Option<Function<'a>> {
+        let p = unsafe { ffi::spReflectionEntryPoint_getFunction(self.0.as_ptr()) };
+        unsafe { Function::from_nullable(p) }
     }
 
-    pub fn parameter(&self, index: c_uint) -> Option<&mut VariableLayout> {
-        let p = unsafe { ffi::spReflectionEntryPoint_getParameterByIndex(self.0.get(), index) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { VariableLayout::from_mut_ptr(p) })
-        }
+    pub fn parameter(self, index: c_uint) -> Option<VariableLayout<'a>> {
+        let p = unsafe { ffi::spReflectionEntryPoint_getParameterByIndex(self.0.as_ptr(), index) };
+        unsafe { VariableLayout::from_nullable(p) }
     }
 
     #[inline(always)]
-    pub fn iter_parameter<'x>(&'x self) -> EntryPointParameterIterator<'x> {
+    pub fn iter_parameter(self) -> EntryPointParameterIterator<'a> {
         EntryPointParameterIterator {
             refl: self,
             current: 0,
@@ -1697,15 +1590,15 @@ impl EntryPoint {
     }
 
     #[inline]
-    pub fn stage(&self) -> crate::Stage {
-        unsafe { ffi::spReflectionEntryPoint_getStage(self.0.get()) }
+    pub fn stage(self) -> crate::Stage {
+        unsafe { ffi::spReflectionEntryPoint_getStage(self.0.as_ptr()) }
     }
 
     #[inline]
-    pub fn compute_thread_group_size(&self, out_size_along_axis: &mut [MaybeUninit<SlangUInt>]) {
+    pub fn compute_thread_group_size(self, out_size_along_axis: &mut [MaybeUninit<SlangUInt>]) {
         unsafe {
             ffi::spReflectionEntryPoint_getComputeThreadGroupSize(
-                self.0.get(),
+                self.0.as_ptr(),
                 out_size_along_axis.len() as _,
                 out_size_along_axis.as_mut_ptr() as _,
             )
@@ -1713,44 +1606,39 @@ impl EntryPoint {
     }
 
     #[inline]
-    pub fn compute_wave_size(&self) -> SlangUInt {
+    pub fn compute_wave_size(self) -> SlangUInt {
         let mut o = MaybeUninit::uninit();
         unsafe {
-            ffi::spReflectionEntryPoint_getComputeWaveSize(self.0.get(), o.as_mut_ptr());
+            ffi::spReflectionEntryPoint_getComputeWaveSize(self.0.as_ptr(), o.as_mut_ptr());
         }
 
         unsafe { o.assume_init() }
     }
 
     #[inline]
-    pub fn uses_any_sample_rate_input(&self) -> bool {
-        unsafe { ffi::spReflectionEntryPoint_usesAnySampleRateInput(self.0.get()) != 0 }
+    pub fn uses_any_sample_rate_input(self) -> bool {
+        unsafe { ffi::spReflectionEntryPoint_usesAnySampleRateInput(self.0.as_ptr()) != 0 }
     }
 
-    #[inline]
-    pub fn var_layout(&self) -> &mut VariableLayout {
-        unsafe {
-            VariableLayout::from_mut_ptr(ffi::spReflectionEntryPoint_getVarLayout(self.0.get()))
-        }
+    pub fn var_layout(self) -> Option<VariableLayout<'a>> {
+        let p = unsafe { ffi::spReflectionEntryPoint_getVarLayout(self.0.as_ptr()) };
+        unsafe { VariableLayout::from_nullable(p) }
     }
 
     #[inline(always)]
-    pub fn type_layout(&self) -> &mut TypeLayout {
-        self.var_layout().type_layout()
+    pub fn type_layout(self) -> Option<TypeLayout<'a>> {
+        self.var_layout().map(VariableLayout::type_layout)
     }
 
-    #[inline]
-    pub fn result_var_layout(&self) -> &mut VariableLayout {
-        unsafe {
-            VariableLayout::from_mut_ptr(ffi::spReflectionEntryPoint_getResultVarLayout(
-                self.0.get(),
-            ))
-        }
+    /// Layout of the return value; `None` for an entry point returning `void`.
+    pub fn result_var_layout(self) -> Option<VariableLayout<'a>> {
+        let p = unsafe { ffi::spReflectionEntryPoint_getResultVarLayout(self.0.as_ptr()) };
+        unsafe { VariableLayout::from_nullable(p) }
     }
 
     #[inline]
-    pub fn has_default_constant_buffer(&self) -> bool {
-        unsafe { ffi::spReflectionEntryPoint_hasDefaultConstantBuffer(self.0.get()) != 0 }
+    pub fn has_default_constant_buffer(self) -> bool {
+        unsafe { ffi::spReflectionEntryPoint_hasDefaultConstantBuffer(self.0.as_ptr()) != 0 }
     }
 }
 
@@ -1762,41 +1650,31 @@ iter_something!(
     c_uint
 );
 
-#[repr(transparent)]
-pub struct TypeParameter(UnsafeCell<ffi::SlangReflectionTypeParameter>);
-impl TypeParameter {
-    pub const unsafe fn from_mut_ptr<'a>(
-        ptr: *mut ffi::SlangReflectionTypeParameter,
-    ) -> &'a mut Self {
-        unsafe { core::mem::transmute(UnsafeCell::from_mut(&mut *ptr)) }
-    }
-
-    #[inline]
-    pub fn name(&self) -> &CStr {
-        unsafe { CStr::from_ptr(ffi::spReflectionTypeParameter_GetName(self.0.get())) }
+reflection_handle!(TypeParameter, ffi::SlangReflectionTypeParameter);
+impl<'a> TypeParameter<'a> {
+    pub fn name(self) -> Option<&'a CStr> {
+        let p = unsafe { ffi::spReflectionTypeParameter_GetName(self.0.as_ptr()) };
+        unsafe { nullable_str(p) }
     }
 
     #[inline]
-    pub fn index(&self) -> c_uint {
-        unsafe { ffi::spReflectionTypeParameter_GetIndex(self.0.get()) }
+    pub fn index(self) -> c_uint {
+        unsafe { ffi::spReflectionTypeParameter_GetIndex(self.0.as_ptr()) }
     }
 
     #[inline]
-    pub fn constraint_count(&self) -> c_uint {
-        unsafe { ffi::spReflectionTypeParameter_GetConstraintCount(self.0.get()) }
+    pub fn constraint_count(self) -> c_uint {
+        unsafe { ffi::spReflectionTypeParameter_GetConstraintCount(self.0.as_ptr()) }
     }
 
-    pub fn constraint(&self, index: c_uint) -> Option<&mut Type> {
-        let p = unsafe { ffi::spReflectionTypeParameter_GetConstraintByIndex(self.0.get(), index) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { Type::from_mut_ptr(p) })
-        }
+    pub fn constraint(self, index: c_uint) -> Option<Type<'a>> {
+        let p =
+            unsafe { ffi::spReflectionTypeParameter_GetConstraintByIndex(self.0.as_ptr(), index) };
+        unsafe { Type::from_nullable(p) }
     }
 
     #[inline(always)]
-    pub fn iter_constraint<'x>(&'x self) -> TypeParameterConstraintIterator<'x> {
+    pub fn iter_constraint(self) -> TypeParameterConstraintIterator<'a> {
         TypeParameterConstraintIterator {
             refl: self,
             current: 0,
@@ -1813,52 +1691,43 @@ iter_something!(
     c_uint
 );
 
-#[repr(transparent)]
-pub struct Shader(UnsafeCell<ffi::SlangReflection>);
-impl Shader {
-    pub const unsafe fn from_mut_ptr<'a>(ptr: *mut ffi::SlangReflection) -> &'a mut Self {
-        unsafe { core::mem::transmute(UnsafeCell::from_mut(&mut *ptr)) }
-    }
-
+reflection_handle!(Shader, ffi::SlangReflection);
+impl<'a> Shader<'a> {
     #[inline]
-    pub fn parameter_count(&self) -> c_uint {
-        unsafe { ffi::spReflection_GetParameterCount(self.0.get()) }
+    pub fn parameter_count(self) -> c_uint {
+        unsafe { ffi::spReflection_GetParameterCount(self.0.as_ptr()) }
     }
 
     #[inline]
-    pub fn type_parameter_count(&self) -> c_uint {
-        unsafe { ffi::spReflection_GetTypeParameterCount(self.0.get()) }
+    pub fn type_parameter_count(self) -> c_uint {
+        unsafe { ffi::spReflection_GetTypeParameterCount(self.0.as_ptr()) }
     }
 
     #[inline]
-    pub fn session(&self) -> crate::ISessionPtr {
-        unsafe {
+    pub fn session(self) -> crate::ISessionPtr {
+        let session = unsafe {
             crate::ISessionPtr(NonNull::new_unchecked(ffi::spReflection_GetSession(
-                self.0.get(),
+                self.0.as_ptr(),
             )))
-        }
+        };
+        // returned without a reference of our own
+        crate::IUnknown::add_ref(&session);
+
+        session
     }
 
-    pub fn type_parameter(&self, index: c_uint) -> Option<&mut TypeParameter> {
-        let p = unsafe { ffi::spReflection_GetTypeParameterByIndex(self.0.get(), index) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { TypeParameter::from_mut_ptr(p) })
-        }
+    pub fn type_parameter(self, index: c_uint) -> Option<TypeParameter<'a>> {
+        let p = unsafe { ffi::spReflection_GetTypeParameterByIndex(self.0.as_ptr(), index) };
+        unsafe { TypeParameter::from_nullable(p) }
     }
 
-    pub fn find_type_parameter(&self, name: &CStr) -> Option<&mut TypeParameter> {
-        let p = unsafe { ffi::spReflection_FindTypeParameter(self.0.get(), name.as_ptr()) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { TypeParameter::from_mut_ptr(p) })
-        }
+    pub fn find_type_parameter(self, name: &CStr) -> Option<TypeParameter<'a>> {
+        let p = unsafe { ffi::spReflection_FindTypeParameter(self.0.as_ptr(), name.as_ptr()) };
+        unsafe { TypeParameter::from_nullable(p) }
     }
 
     #[inline(always)]
-    pub fn iter_type_parameter<'x>(&'x self) -> ShaderTypeParameterIterator<'x> {
+    pub fn iter_type_parameter(self) -> ShaderTypeParameterIterator<'a> {
         ShaderTypeParameterIterator {
             refl: self,
             current: 0,
@@ -1866,17 +1735,13 @@ impl Shader {
         }
     }
 
-    pub fn parameter(&self, index: c_uint) -> Option<&mut VariableLayout> {
-        let p = unsafe { ffi::spReflection_GetParameterByIndex(self.0.get(), index) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { VariableLayout::from_mut_ptr(p) })
-        }
+    pub fn parameter(self, index: c_uint) -> Option<VariableLayout<'a>> {
+        let p = unsafe { ffi::spReflection_GetParameterByIndex(self.0.as_ptr(), index) };
+        unsafe { VariableLayout::from_nullable(p) }
     }
 
     #[inline(always)]
-    pub fn iter_parameter<'x>(&'x self) -> ShaderParameterIterator<'x> {
+    pub fn iter_parameter(self) -> ShaderParameterIterator<'a> {
         ShaderParameterIterator {
             refl: self,
             current: 0,
@@ -1885,21 +1750,17 @@ impl Shader {
     }
 
     #[inline]
-    pub fn entry_point_count(&self) -> SlangUInt {
-        unsafe { ffi::spReflection_getEntryPointCount(self.0.get()) }
+    pub fn entry_point_count(self) -> SlangUInt {
+        unsafe { ffi::spReflection_getEntryPointCount(self.0.as_ptr()) }
     }
 
-    pub fn entry_point(&self, index: SlangUInt) -> Option<&mut EntryPoint> {
-        let p = unsafe { ffi::spReflection_getEntryPointByIndex(self.0.get(), index) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { EntryPoint::from_mut_ptr(p) })
-        }
+    pub fn entry_point(self, index: SlangUInt) -> Option<EntryPoint<'a>> {
+        let p = unsafe { ffi::spReflection_getEntryPointByIndex(self.0.as_ptr(), index) };
+        unsafe { EntryPoint::from_nullable(p) }
     }
 
     #[inline(always)]
-    pub fn iter_entry_point<'x>(&'x self) -> ShaderEntryPointIterator<'x> {
+    pub fn iter_entry_point(self) -> ShaderEntryPointIterator<'a> {
         ShaderEntryPointIterator {
             refl: self,
             current: 0,
@@ -1908,106 +1769,83 @@ impl Shader {
     }
 
     #[inline]
-    pub fn global_constant_buffer_binding(&self) -> SlangUInt {
-        unsafe { ffi::spReflection_getGlobalConstantBufferBinding(self.0.get()) }
+    pub fn global_constant_buffer_binding(self) -> SlangUInt {
+        unsafe { ffi::spReflection_getGlobalConstantBufferBinding(self.0.as_ptr()) }
     }
 
     #[inline]
-    pub fn global_constant_buffer_size(&self) -> usize {
-        unsafe { ffi::spReflection_getGlobalConstantBufferSize(self.0.get()) }
+    pub fn global_constant_buffer_size(self) -> usize {
+        unsafe { ffi::spReflection_getGlobalConstantBufferSize(self.0.as_ptr()) }
     }
 
-    pub fn find_type_by_name(&self, name: &CStr) -> Option<&mut Type> {
-        let p = unsafe { ffi::spReflection_FindTypeByName(self.0.get(), name.as_ptr()) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { Type::from_mut_ptr(p) })
-        }
+    pub fn find_type_by_name(self, name: &CStr) -> Option<Type<'a>> {
+        let p = unsafe { ffi::spReflection_FindTypeByName(self.0.as_ptr(), name.as_ptr()) };
+        unsafe { Type::from_nullable(p) }
     }
 
-    pub fn find_function_by_name(&self, name: &CStr) -> Option<&mut Function> {
-        let p = unsafe { ffi::spReflection_FindFunctionByName(self.0.get(), name.as_ptr()) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { Function::from_mut_ptr(p) })
-        }
+    pub fn find_function_by_name(self, name: &CStr) -> Option<Function<'a>> {
+        let p = unsafe { ffi::spReflection_FindFunctionByName(self.0.as_ptr(), name.as_ptr()) };
+        unsafe { Function::from_nullable(p) }
     }
 
     pub fn find_function_by_name_in_type(
-        &self,
-        r#type: &Type,
+        self,
+        r#type: Type<'_>,
         name: &CStr,
-    ) -> Option<&mut Function> {
+    ) -> Option<Function<'a>> {
         let p = unsafe {
-            ffi::spReflection_FindFunctionByNameInType(self.0.get(), r#type.0.get(), name.as_ptr())
+            ffi::spReflection_FindFunctionByNameInType(
+                self.0.as_ptr(),
+                r#type.0.as_ptr(),
+                name.as_ptr(),
+            )
         };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { Function::from_mut_ptr(p) })
-        }
+        unsafe { Function::from_nullable(p) }
     }
 
-    pub fn find_var_by_name_in_type(&self, r#type: &Type, name: &CStr) -> Option<&mut Variable> {
+    pub fn find_var_by_name_in_type(self, r#type: Type<'_>, name: &CStr) -> Option<Variable<'a>> {
         let p = unsafe {
-            ffi::spReflection_FindVarByNameInType(self.0.get(), r#type.0.get(), name.as_ptr())
+            ffi::spReflection_FindVarByNameInType(self.0.as_ptr(), r#type.0.as_ptr(), name.as_ptr())
         };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { Variable::from_mut_ptr(p) })
-        }
+        unsafe { Variable::from_nullable(p) }
     }
 
-    pub fn type_layout(&self, r#type: &Type, rules: LayoutRules) -> Option<&mut TypeLayout> {
-        let p = unsafe { ffi::spReflection_GetTypeLayout(self.0.get(), r#type.0.get(), rules) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { TypeLayout::from_mut_ptr(p) })
-        }
+    pub fn type_layout(self, r#type: Type<'_>, rules: LayoutRules) -> Option<TypeLayout<'a>> {
+        let p =
+            unsafe { ffi::spReflection_GetTypeLayout(self.0.as_ptr(), r#type.0.as_ptr(), rules) };
+        unsafe { TypeLayout::from_nullable(p) }
     }
 
-    pub fn find_entry_point_by_name(&self, name: &CStr) -> Option<&mut EntryPoint> {
-        let p = unsafe { ffi::spReflection_findEntryPointByName(self.0.get(), name.as_ptr()) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { EntryPoint::from_mut_ptr(p) })
-        }
+    pub fn find_entry_point_by_name(self, name: &CStr) -> Option<EntryPoint<'a>> {
+        let p = unsafe { ffi::spReflection_findEntryPointByName(self.0.as_ptr(), name.as_ptr()) };
+        unsafe { EntryPoint::from_nullable(p) }
     }
 
     pub fn specialize_type(
-        &self,
-        r#type: &Type,
-        specialization_args: &[&Type],
+        self,
+        r#type: Type<'_>,
+        specialization_args: &[Type<'_>],
         out_diagnostics: Option<&mut MaybeUninit<Option<crate::IBlobPtr>>>,
-    ) -> Option<&mut Type> {
+    ) -> Option<Type<'a>> {
         let p = unsafe {
             ffi::spReflection_specializeType(
-                self.0.get(),
-                r#type.0.get(),
+                self.0.as_ptr(),
+                r#type.0.as_ptr(),
                 specialization_args.len() as _,
                 specialization_args.as_ptr() as _,
                 out_diagnostics.map_or_else(core::ptr::null_mut, MaybeUninit::as_mut_ptr) as _,
             )
         };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { Type::from_mut_ptr(p) })
-        }
+        unsafe { Type::from_nullable(p) }
     }
 
     pub fn specialize_generic(
-        &self,
-        generic: &Generic,
+        self,
+        generic: Generic<'_>,
         specialization_arg_types: &[GenericArgType],
         specialization_arg_vals: &[GenericArg],
         out_diagnostics: Option<&mut MaybeUninit<Option<crate::IBlobPtr>>>,
-    ) -> Option<&mut Generic> {
+    ) -> Option<Generic<'a>> {
         assert_eq!(
             specialization_arg_types.len(),
             specialization_arg_vals.len()
@@ -2015,68 +1853,54 @@ impl Shader {
 
         let p = unsafe {
             ffi::spReflection_specializeGeneric(
-                self.0.get(),
-                generic.0.get(),
+                self.0.as_ptr(),
+                generic.0.as_ptr(),
                 specialization_arg_types.len() as _,
                 specialization_arg_types.as_ptr(),
                 specialization_arg_vals.as_ptr(),
                 out_diagnostics.map_or_else(core::ptr::null_mut, MaybeUninit::as_mut_ptr) as _,
             )
         };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { Generic::from_mut_ptr(p) })
-        }
+        unsafe { Generic::from_nullable(p) }
     }
 
     #[inline]
-    pub fn is_sub_type(&self, sub_type: &Type, super_type: &Type) -> bool {
-        unsafe { ffi::spReflection_isSubType(self.0.get(), sub_type.0.get(), super_type.0.get()) }
+    pub fn is_sub_type(self, sub_type: Type<'_>, super_type: Type<'_>) -> bool {
+        unsafe {
+            ffi::spReflection_isSubType(self.0.as_ptr(), sub_type.0.as_ptr(), super_type.0.as_ptr())
+        }
     }
 
     #[inline]
-    pub fn hashed_string_count(&self) -> SlangUInt {
-        unsafe { ffi::spReflection_getHashedStringCount(self.0.get()) }
+    pub fn hashed_string_count(self) -> SlangUInt {
+        unsafe { ffi::spReflection_getHashedStringCount(self.0.as_ptr()) }
     }
 
     pub fn hashed_string(
-        &self,
+        self,
         index: SlangUInt,
         out_count: &mut MaybeUninit<usize>,
-    ) -> Option<&CStr> {
+    ) -> Option<&'a CStr> {
         let p = unsafe {
-            ffi::spReflection_getHashedString(self.0.get(), index, out_count.as_mut_ptr())
+            ffi::spReflection_getHashedString(self.0.as_ptr(), index, out_count.as_mut_ptr())
         };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { CStr::from_ptr(p) })
-        }
+        unsafe { nullable_str(p) }
     }
 
-    pub fn global_params_type_layout(&self) -> Option<&mut TypeLayout> {
-        let p = unsafe { ffi::spReflection_getGlobalParamsTypeLayout(self.0.get()) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { TypeLayout::from_mut_ptr(p) })
-        }
+    pub fn global_params_type_layout(self) -> Option<TypeLayout<'a>> {
+        let p = unsafe { ffi::spReflection_getGlobalParamsTypeLayout(self.0.as_ptr()) };
+        unsafe { TypeLayout::from_nullable(p) }
     }
 
-    pub fn global_params_var_layout(&self) -> Option<&mut VariableLayout> {
-        let p = unsafe { ffi::spReflection_getGlobalParamsVarLayout(self.0.get()) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { VariableLayout::from_mut_ptr(p) })
-        }
+    pub fn global_params_var_layout(self) -> Option<VariableLayout<'a>> {
+        let p = unsafe { ffi::spReflection_getGlobalParamsVarLayout(self.0.as_ptr()) };
+        unsafe { VariableLayout::from_nullable(p) }
     }
 
-    pub fn to_json(&self) -> crate::Result<crate::IBlobPtr> {
+    pub fn to_json(self) -> crate::Result<crate::IBlobPtr> {
         let mut o = MaybeUninit::uninit();
         crate::rw(unsafe {
-            ffi::spReflection_ToJson(self.0.get(), core::ptr::null_mut(), o.as_mut_ptr())
+            ffi::spReflection_ToJson(self.0.as_ptr(), core::ptr::null_mut(), o.as_mut_ptr())
         })?;
 
         Ok(crate::IBlobPtr(unsafe {
@@ -2107,39 +1931,30 @@ iter_something!(
     SlangUInt
 );
 
-#[repr(transparent)]
-pub struct Decl(UnsafeCell<ffi::SlangReflectionDecl>);
-impl Decl {
-    pub const unsafe fn from_mut_ptr<'a>(ptr: *mut ffi::SlangReflectionDecl) -> &'a mut Self {
-        unsafe { core::mem::transmute(UnsafeCell::from_mut(&mut *ptr)) }
+reflection_handle!(Decl, ffi::SlangReflectionDecl);
+impl<'a> Decl<'a> {
+    pub fn name(self) -> Option<&'a CStr> {
+        let p = unsafe { ffi::spReflectionDecl_getName(self.0.as_ptr()) };
+        unsafe { nullable_str(p) }
     }
 
     #[inline]
-    pub fn name(&self) -> &CStr {
-        unsafe { CStr::from_ptr(ffi::spReflectionDecl_getName(self.0.get())) }
+    pub fn kind(self) -> crate::DeclKind {
+        unsafe { ffi::spReflectionDecl_getKind(self.0.as_ptr()) }
     }
 
     #[inline]
-    pub fn kind(&self) -> crate::DeclKind {
-        unsafe { ffi::spReflectionDecl_getKind(self.0.get()) }
+    pub fn children_count(self) -> c_uint {
+        unsafe { ffi::spReflectionDecl_getChildrenCount(self.0.as_ptr()) }
     }
 
-    #[inline]
-    pub fn children_count(&self) -> c_uint {
-        unsafe { ffi::spReflectionDecl_getChildrenCount(self.0.get()) }
-    }
-
-    pub fn child(&self, index: c_uint) -> Option<&mut Decl> {
-        let p = unsafe { ffi::spReflectionDecl_getChild(self.0.get(), index) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { Decl::from_mut_ptr(p) })
-        }
+    pub fn child(self, index: c_uint) -> Option<Decl<'a>> {
+        let p = unsafe { ffi::spReflectionDecl_getChild(self.0.as_ptr(), index) };
+        unsafe { Decl::from_nullable(p) }
     }
 
     #[inline(always)]
-    pub fn iter_child<'d>(&'d self) -> DeclChildrenIterator<'d> {
+    pub fn iter_child(self) -> DeclChildrenIterator<'a> {
         DeclChildrenIterator {
             refl: self,
             current: 0,
@@ -2147,49 +1962,29 @@ impl Decl {
         }
     }
 
-    pub fn r#type(&self) -> Option<&mut Type> {
-        let p = unsafe { ffi::spReflection_getTypeFromDecl(self.0.get()) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { Type::from_mut_ptr(p) })
-        }
+    pub fn r#type(self) -> Option<Type<'a>> {
+        let p = unsafe { ffi::spReflection_getTypeFromDecl(self.0.as_ptr()) };
+        unsafe { Type::from_nullable(p) }
     }
 
-    pub fn as_variable(&self) -> Option<&mut Variable> {
-        let p = unsafe { ffi::spReflectionDecl_castToVariable(self.0.get()) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { Variable::from_mut_ptr(p) })
-        }
+    pub fn as_variable(self) -> Option<Variable<'a>> {
+        let p = unsafe { ffi::spReflectionDecl_castToVariable(self.0.as_ptr()) };
+        unsafe { Variable::from_nullable(p) }
     }
 
-    pub fn as_function(&self) -> Option<&mut Function> {
-        let p = unsafe { ffi::spReflectionDecl_castToFunction(self.0.get()) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { Function::from_mut_ptr(p) })
-        }
+    pub fn as_function(self) -> Option<Function<'a>> {
+        let p = unsafe { ffi::spReflectionDecl_castToFunction(self.0.as_ptr()) };
+        unsafe { Function::from_nullable(p) }
     }
 
-    pub fn as_generic(&self) -> Option<&mut Generic> {
-        let p = unsafe { ffi::spReflectionDecl_castToGeneric(self.0.get()) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { Generic::from_mut_ptr(p) })
-        }
+    pub fn as_generic(self) -> Option<Generic<'a>> {
+        let p = unsafe { ffi::spReflectionDecl_castToGeneric(self.0.as_ptr()) };
+        unsafe { Generic::from_nullable(p) }
     }
 
-    pub fn parent(&self) -> Option<&mut Decl> {
-        let p = unsafe { ffi::spReflectionDecl_getParent(self.0.get()) };
-        if p.is_null() {
-            None
-        } else {
-            Some(unsafe { Decl::from_mut_ptr(p) })
-        }
+    pub fn parent(self) -> Option<Decl<'a>> {
+        let p = unsafe { ffi::spReflectionDecl_getParent(self.0.as_ptr()) };
+        unsafe { Decl::from_nullable(p) }
     }
 }
 
@@ -2201,10 +1996,4 @@ iter_something!(
     c_uint
 );
 
-#[repr(transparent)]
-pub struct Modifier(UnsafeCell<ffi::SlangReflectionModifier>);
-impl Modifier {
-    pub const unsafe fn from_mut_ptr<'a>(ptr: *mut ffi::SlangReflectionModifier) -> &'a mut Self {
-        unsafe { core::mem::transmute(UnsafeCell::from_mut(&mut *ptr)) }
-    }
-}
+reflection_handle!(Modifier, ffi::SlangReflectionModifier);
@@ -0,0 +1,868 @@
+//! Owned copy of the reflection data of a program.
+//!
+//! The reflection objects in the parent module point into the layout owned by the component type they were obtained
+//! from. [`ProgramReflection`] copies everything needed for binding resources into plain Rust values, so it can be
+//! kept (and cloned, or sent to other threads) after the component type is released.
+//!
+//! With the `serde` feature the structures implement `Serialize`/`Deserialize`, e.g. to store the reflection data
+//! next to the compiled code. Unlike [`Shader::to_json`], the format is defined by this crate and versioned with
+//! [`ProgramReflection::SCHEMA_VERSION`]. Slang's enumerations (stages, image formats, resource shapes, ...) are written
+//! by their `snake_case` name rather than by their numbering, which changes between Slang releases. Values this crate
+//! has no name for (e.g. a stage added by a newer Slang release) are written as `raw:` followed by their number.
+
+use super::{
+    Attribute, BindingType, EntryPoint, ParameterCategory, ScalarType, Shader, TypeKind,
+    TypeLayout, VariableLayout, owned_name,
+};
+use crate::{IBlob, ImageFormat, MatrixLayoutMode, ResourceAccess, ResourceShape, Stage};
+
+/// Reflection data of a whole program.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProgramReflection {
+    /// [`ProgramReflection::SCHEMA_VERSION`] of the crate that created the value.
+    pub schema_version: u32,
+    /// Global shader parameters.
+    pub parameters: Vec<VariableLayoutReflection>,
+    pub entry_points: Vec<EntryPointReflection>,
+    /// Layout of the scope holding all global parameters (including the implicit global constant buffer, if any).
+    pub global_params: Option<VariableLayoutReflection>,
+    pub global_constant_buffer_binding: u64,
+    pub global_constant_buffer_size: usize,
+}
+impl ProgramReflection {
+    /// Version of the layout of these structures, incremented on every change that breaks deserializing
+    /// previously serialized values (renamed/removed fields, changed meanings).
+    pub const SCHEMA_VERSION: u32 = 4;
+
+    /// Copies the reflection data out of `shader`.
+    pub fn new(shader: Shader<'_>) -> Self {
+        let ctx = Snapshot { shader };
+
+        Self {
+            schema_version: Self::SCHEMA_VERSION,
+            parameters: shader
+                .iter_parameter()
+                .map(|p| ctx.variable_layout(p))
+                .collect(),
+            entry_points: shader
+                .iter_entry_point()
+                .map(|e| ctx.entry_point(e))
+                .collect(),
+            global_params: shader
+                .global_params_var_layout()
+                .map(|v| ctx.variable_layout(v)),
+            global_constant_buffer_binding: shader.global_constant_buffer_binding() as _,
+            global_constant_buffer_size: shader.global_constant_buffer_size(),
+        }
+    }
+
+    pub fn find_entry_point(&self, name: &str) -> Option<&EntryPointReflection> {
+        self.entry_points.iter().find(|e| e.name == name)
+    }
+
+    pub fn find_parameter(&self, name: &str) -> Option<&VariableLayoutReflection> {
+        self.parameters
+            .iter()
+            .find(|p| p.name.as_deref() == Some(name))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EntryPointReflection {
+    pub name: String,
+    /// Name the entry point was renamed to (see [`IComponentType::rename_entry_point`](crate::IComponentType::rename_entry_point)).
+    pub name_override: Option<String>,
+    #[cfg_attr(feature = "serde", serde(with = "names::stage"))]
+    pub stage: Stage,
+    pub parameters: Vec<VariableLayoutReflection>,
+    /// Layout of the scope holding the entry point parameters.
+    pub var_layout: Option<VariableLayoutReflection>,
+    /// Layout of the return value (absent for `void` entry points).
+    pub result: Option<VariableLayoutReflection>,
+    /// Thread group size along each axis (compute-like stages only; zeros otherwise).
+    pub thread_group_size: [u64; 3],
+    pub wave_size: u64,
+    pub uses_any_sample_rate_input: bool,
+    pub has_default_constant_buffer: bool,
+    /// User attributes on the entry point function.
+    pub attributes: Vec<AttributeReflection>,
+}
+
+/// Placement of a variable for one parameter category.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BindingOffset {
+    pub category: ParameterCategory,
+    /// Offset in units of the category (bytes for [`ParameterCategory::Uniform`], registers/bindings otherwise).
+    pub offset: usize,
+    /// Register space/descriptor set.
+    pub space: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VariableLayoutReflection {
+    pub name: Option<String>,
+    pub semantic_name: Option<String>,
+    pub semantic_index: usize,
+    #[cfg_attr(feature = "serde", serde(with = "names::stage"))]
+    pub stage: Stage,
+    #[cfg_attr(feature = "serde", serde(with = "names::image_format"))]
+    pub image_format: ImageFormat,
+    /// Offsets for every category the variable's type consumes.
+    pub offsets: Vec<BindingOffset>,
+    pub type_layout: TypeLayoutReflection,
+    pub attributes: Vec<AttributeReflection>,
+}
+impl VariableLayoutReflection {
+    /// The offset for `category`, if the variable consumes resources of that category.
+    pub fn offset(&self, category: ParameterCategory) -> Option<&BindingOffset> {
+        self.offsets.iter().find(|o| o.category == category)
+    }
+}
+
+/// Resource usage of a type for one parameter category.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CategorySize {
+    pub category: ParameterCategory,
+    /// `usize::MAX` for unbounded sizes (e.g. unsized arrays).
+    pub size: usize,
+    pub stride: usize,
+    pub alignment: i32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TypeLayoutReflection {
+    pub kind: TypeKind,
+    pub name: Option<String>,
+    pub full_name: Option<String>,
+    pub parameter_category: ParameterCategory,
+    pub sizes: Vec<CategorySize>,
+    pub scalar_type: ScalarType,
+    pub row_count: u32,
+    pub column_count: u32,
+    pub element_count: usize,
+    #[cfg_attr(feature = "serde", serde(with = "names::matrix_layout_mode"))]
+    pub matrix_layout_mode: MatrixLayoutMode,
+    #[cfg_attr(feature = "serde", serde(with = "names::resource_shape"))]
+    pub resource_shape: ResourceShape,
+    #[cfg_attr(feature = "serde", serde(with = "names::resource_access"))]
+    pub resource_access: ResourceAccess,
+    pub fields: Vec<VariableLayoutReflection>,
+    /// Layout of the element type of arrays, resources and buffers.
+    ///
+    /// Not filled in for pointer types, whose pointee may refer back to the type itself.
+    pub element: Option<Box<TypeLayoutReflection>>,
+    /// Offsets of the element within a buffer-like type (constant buffers, parameter blocks, ...).
+    pub element_offsets: Vec<BindingOffset>,
+    /// Offsets of the buffer itself (the "container") within a buffer-like type.
+    pub container_offsets: Vec<BindingOffset>,
+    pub binding_ranges: Vec<BindingRangeReflection>,
+    pub descriptor_sets: Vec<DescriptorSetReflection>,
+    pub sub_object_ranges: Vec<SubObjectRangeReflection>,
+    pub attributes: Vec<AttributeReflection>,
+}
+impl TypeLayoutReflection {
+    /// Resource usage for `category`, if the type consumes resources of that category.
+    pub fn size(&self, category: ParameterCategory) -> Option<&CategorySize> {
+        self.sizes.iter().find(|s| s.category == category)
+    }
+
+    pub fn find_field(&self, name: &str) -> Option<&VariableLayoutReflection> {
+        self.fields.iter().find(|f| f.name.as_deref() == Some(name))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BindingRangeReflection {
+    pub binding_type: BindingType,
+    pub binding_count: i64,
+    pub is_specializable: bool,
+    #[cfg_attr(feature = "serde", serde(with = "names::image_format"))]
+    pub image_format: ImageFormat,
+    pub descriptor_set_index: i64,
+    pub first_descriptor_range_index: i64,
+    pub descriptor_range_count: i64,
+    /// Name of the variable the range was created for.
+    pub leaf_variable_name: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DescriptorSetReflection {
+    pub space_offset: i64,
+    pub descriptor_ranges: Vec<DescriptorRangeReflection>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DescriptorRangeReflection {
+    pub index_offset: i64,
+    pub descriptor_count: i64,
+    pub binding_type: BindingType,
+    pub category: ParameterCategory,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SubObjectRangeReflection {
+    pub binding_range_index: i64,
+    pub space_offset: i64,
+    pub offsets: Vec<BindingOffset>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AttributeReflection {
+    pub name: String,
+    pub arguments: Vec<AttributeArgument>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AttributeArgument {
+    Int(i32),
+    Float(f32),
+    String(String),
+    /// An argument whose value is not a literal of the above types.
+    Unknown,
+}
+
+impl Shader<'_> {
+    /// Copies the reflection data into a [`ProgramReflection`].
+    #[inline]
+    pub fn snapshot(self) -> ProgramReflection {
+        ProgramReflection::new(self)
+    }
+}
+
+struct Snapshot<'s> {
+    shader: Shader<'s>,
+}
+impl Snapshot<'_> {
+    fn entry_point(&self, e: EntryPoint<'_>) -> EntryPointReflection {
+        let mut thread_group_size = [core::mem::MaybeUninit::new(0); 3];
+        e.compute_thread_group_size(&mut thread_group_size);
+
+        EntryPointReflection {
+            name: owned_name(e.name()).unwrap_or_default(),
+            name_override: owned_name(e.name_override()),
+            stage: e.stage(),
+            parameters: e
+                .iter_parameter()
+                .map(|p| self.variable_layout(p))
+                .collect(),
+            var_layout: e.var_layout().map(|v| self.variable_layout(v)),
+            result: e
+                .result_var_layout()
+                .map(|r| self.variable_layout(r))
+                .filter(|r| r.type_layout.scalar_type != ScalarType::Void),
+            thread_group_size: thread_group_size.map(|x| unsafe { x.assume_init() } as _),
+            wave_size: e.compute_wave_size() as _,
+            uses_any_sample_rate_input: e.uses_any_sample_rate_input(),
+            has_default_constant_buffer: e.has_default_constant_buffer(),
+            attributes: e.function().map_or_else(Vec::new, |f| {
+                f.iter_user_attribute().map(|a| self.attribute(a)).collect()
+            }),
+        }
+    }
+
+    fn offsets(&self, v: VariableLayout<'_>) -> Vec<BindingOffset> {
+        v.iter_category()
+            .map(|category| BindingOffset {
+                category,
+                offset: v.offset(category),
+                space: v.space(category),
+            })
+            .collect()
+    }
+
+    fn variable_layout(&self, v: VariableLayout<'_>) -> VariableLayoutReflection {
+        let (name, attributes) = match v.variable() {
+            None => (None, Vec::new()),
+            Some(variable) => (
+                owned_name(variable.name()),
+                variable
+                    .iter_user_attribute()
+                    .map(|a| self.attribute(a))
+                    .collect(),
+            ),
+        };
+
+        VariableLayoutReflection {
+            name,
+            semantic_name: owned_name(v.semantic_name()),
+            semantic_index: v.semantic_index(),
+            stage: v.stage(),
+            image_format: v.image_format(),
+            offsets: self.offsets(v),
+            type_layout: self.type_layout(v.type_layout()),
+            attributes,
+        }
+    }
+
+    fn type_layout(&self, t: TypeLayout<'_>) -> TypeLayoutReflection {
+        let kind = t.kind();
+        let ty = t.r#type();
+
+        let buffer_like = matches!(
+            kind,
+            TypeKind::ConstantBuffer
+                | TypeKind::ParameterBlock
+                | TypeKind::TextureBuffer
+                | TypeKind::ShaderStorageBuffer
+        );
+        let (element, element_offsets, container_offsets) = if kind == TypeKind::Pointer {
+            (None, Vec::new(), Vec::new())
+        } else if buffer_like && let Some(ev) = t.element_var_layout() {
+            (
+                Some(Box::new(self.type_layout(ev.type_layout()))),
+                self.offsets(ev),
+                t.container_var_layout()
+                    .map_or_else(Vec::new, |c| self.offsets(c)),
+            )
+        } else {
+            (
+                t.element_type_layout()
+                    .map(|e| Box::new(self.type_layout(e))),
+                Vec::new(),
+                Vec::new(),
+            )
+        };
+
+        TypeLayoutReflection {
+            kind,
+            name: owned_name(t.name()),
+            full_name: ty.and_then(|ty| {
+                ty.full_name()
+                    .ok()
+                    .and_then(|b| b.as_str().ok().map(str::to_owned))
+            }),
+            parameter_category: t.parameter_category(),
+            sizes: t
+                .iter_category()
+                .map(|category| CategorySize {
+                    category,
+                    size: t.size(category),
+                    stride: t.stride(category),
+                    alignment: t.alignment(category),
+                })
+                .collect(),
+            scalar_type: ty.map_or(ScalarType::None, |ty| ty.scalar_type()),
+            row_count: ty.map_or(0, |ty| ty.row_count()),
+            column_count: ty.map_or(0, |ty| ty.column_count()),
+            element_count: ty.map_or(0, |ty| ty.element_count(Some(self.shader))),
+            matrix_layout_mode: t.matrix_layout_mode(),
+            resource_shape: ty.map_or(0, |ty| ty.resource_shape()),
+            resource_access: ty.map_or(0, |ty| ty.resource_access()),
+            fields: t.iter_field().map(|f| self.variable_layout(f)).collect(),
+            element,
+            element_offsets,
+            container_offsets,
+            binding_ranges: t
+                .iter_binding_range()
+                .map(|r| BindingRangeReflection {
+                    binding_type: r.r#type(),
+                    binding_count: r.binding_count() as _,
+                    is_specializable: r.is_specializable(),
+                    image_format: r.image_format(),
+                    descriptor_set_index: r.descriptor_set_index() as _,
+                    first_descriptor_range_index: r.first_descriptor_range_index() as _,
+                    descriptor_range_count: r.descriptor_range_count() as _,
+                    leaf_variable_name: r.leaf_variable().and_then(|v| owned_name(v.name())),
+                })
+                .collect(),
+            descriptor_sets: t
+                .iter_descriptor_set()
+                .map(|s| DescriptorSetReflection {
+                    space_offset: s.space_offset() as _,
+                    descriptor_ranges: s
+                        .iter_descriptor_range()
+                        .map(|r| DescriptorRangeReflection {
+                            index_offset: r.index_offset() as _,
+                            descriptor_count: r.descriptor_count() as _,
+                            binding_type: r.r#type(),
+                            category: r.category(),
+                        })
+                        .collect(),
+                })
+                .collect(),
+            sub_object_ranges: t
+                .iter_sub_object_range()
+                .map(|r| SubObjectRangeReflection {
+                    binding_range_index: r.binding_range_index() as _,
+                    space_offset: r.space_offset() as _,
+                    offsets: r.offset().map_or_else(Vec::new, |o| self.offsets(o)),
+                })
+                .collect(),
+            attributes: ty.map_or_else(Vec::new, |ty| {
+                ty.iter_user_attribute()
+                    .map(|a| self.attribute(a))
+                    .collect()
+            }),
+        }
+    }
+
+    fn attribute(&self, a: Attribute<'_>) -> AttributeReflection {
+        AttributeReflection {
+            name: owned_name(a.name()).unwrap_or_default(),
+            arguments: (0..a.argument_count())
+                .map(|n| {
+                    if let Ok(v) = a.argument_value_int(n) {
+                        AttributeArgument::Int(v)
+                    } else if let Ok(v) = a.argument_value_float(n) {
+                        AttributeArgument::Float(v)
+                    } else if let Some(v) = a.argument_value_string(n) {
+                        AttributeArgument::String(v.to_string_lossy().into_owned())
+                    } else {
+                        AttributeArgument::Unknown
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Names used for Slang's enumerations in the serialized form, so it does not depend on their numbering in a particular
+/// Slang release.
+#[cfg(feature = "serde")]
+mod names {
+    use serde::{Deserialize, Deserializer, Serializer, de};
+
+    use crate::ffi;
+
+    /// Prefix of the values that have no name, e.g. `raw:1000`. Every value is written as a string so formats that
+    /// are not self-describing (bincode, postcard, ...) can read it back.
+    const RAW: &str = "raw:";
+
+    fn serialize<S: Serializer>(
+        value: u32,
+        table: &[(u32, &str)],
+        s: S,
+    ) -> Result<S::Ok, S::Error> {
+        match table.iter().find(|&&(v, _)| v == value) {
+            Some((_, name)) => s.serialize_str(name),
+            None => s.serialize_str(&format!("{RAW}{value}")),
+        }
+    }
+
+    /// Reads a value written as `raw:<n>`.
+    fn raw<E: de::Error>(name: &str, what: &str) -> Option<Result<u32, E>> {
+        let raw = name.strip_prefix(RAW)?;
+        Some(
+            raw.parse()
+                .map_err(|_| E::custom(format!("invalid {what} {name:?}"))),
+        )
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(
+        table: &[(u32, &str)],
+        what: &str,
+        d: D,
+    ) -> Result<u32, D::Error> {
+        let name = String::deserialize(d)?;
+        if let Some(value) = raw(&name, what) {
+            return value;
+        }
+        table
+            .iter()
+            .find(|&&(_, n)| n == name)
+            .map(|&(v, _)| v)
+            .ok_or_else(|| de::Error::custom(format!("unknown {what} {name:?}")))
+    }
+
+    macro_rules! named {
+        ($module: ident, $what: literal, [$($value: expr => $name: literal),* $(,)?]) => {
+            pub mod $module {
+                use super::*;
+
+                const TABLE: &[(u32, &str)] = &[$(($value, $name)),*];
+
+                pub fn serialize<S: Serializer>(value: &u32, s: S) -> Result<S::Ok, S::Error> {
+                    super::serialize(*value, TABLE, s)
+                }
+
+                pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<u32, D::Error> {
+                    super::deserialize(TABLE, $what, d)
+                }
+            }
+        };
+    }
+
+    named!(stage, "stage", [
+        ffi::SLANG_STAGE_NONE => "none",
+        ffi::SLANG_STAGE_VERTEX => "vertex",
+        ffi::SLANG_STAGE_HULL => "hull",
+        ffi::SLANG_STAGE_DOMAIN => "domain",
+        ffi::SLANG_STAGE_GEOMETRY => "geometry",
+        ffi::SLANG_STAGE_FRAGMENT => "fragment",
+        ffi::SLANG_STAGE_COMPUTE => "compute",
+        ffi::SLANG_STAGE_RAY_GENERATION => "ray_generation",
+        ffi::SLANG_STAGE_INTERSECTION => "intersection",
+        ffi::SLANG_STAGE_ANY_HIT => "any_hit",
+        ffi::SLANG_STAGE_CLOSEST_HIT => "closest_hit",
+        ffi::SLANG_STAGE_MISS => "miss",
+        ffi::SLANG_STAGE_CALLABLE => "callable",
+        ffi::SLANG_STAGE_MESH => "mesh",
+        ffi::SLANG_STAGE_AMPLIFICATION => "amplification",
+        ffi::SLANG_STAGE_DISPATCH => "dispatch",
+    ]);
+    named!(image_format, "image format", [
+        ffi::SLANG_IMAGE_FORMAT_UNKNOWN => "unknown",
+        ffi::SLANG_IMAGE_FORMAT_RGBA32F => "rgba32f",
+        ffi::SLANG_IMAGE_FORMAT_RGBA16F => "rgba16f",
+        ffi::SLANG_IMAGE_FORMAT_RG32F => "rg32f",
+        ffi::SLANG_IMAGE_FORMAT_RG16F => "rg16f",
+        ffi::SLANG_IMAGE_FORMAT_R11F_G11F_B10F => "r11f_g11f_b10f",
+        ffi::SLANG_IMAGE_FORMAT_R32F => "r32f",
+        ffi::SLANG_IMAGE_FORMAT_R16F => "r16f",
+        ffi::SLANG_IMAGE_FORMAT_RGBA16 => "rgba16",
+        ffi::SLANG_IMAGE_FORMAT_RGB10_A2 => "rgb10_a2",
+        ffi::SLANG_IMAGE_FORMAT_RGBA8 => "rgba8",
+        ffi::SLANG_IMAGE_FORMAT_RG16 => "rg16",
+        ffi::SLANG_IMAGE_FORMAT_RG8 => "rg8",
+        ffi::SLANG_IMAGE_FORMAT_R16 => "r16",
+        ffi::SLANG_IMAGE_FORMAT_R8 => "r8",
+        ffi::SLANG_IMAGE_FORMAT_RGBA16_SNORM => "rgba16_snorm",
+        ffi::SLANG_IMAGE_FORMAT_RGBA8_SNORM => "rgba8_snorm",
+        ffi::SLANG_IMAGE_FORMAT_RG16_SNORM => "rg16_snorm",
+        ffi::SLANG_IMAGE_FORMAT_RG8_SNORM => "rg8_snorm",
+        ffi::SLANG_IMAGE_FORMAT_R16_SNORM => "r16_snorm",
+        ffi::SLANG_IMAGE_FORMAT_R8_SNORM => "r8_snorm",
+        ffi::SLANG_IMAGE_FORMAT_RGBA32I => "rgba32i",
+        ffi::SLANG_IMAGE_FORMAT_RGBA16I => "rgba16i",
+        ffi::SLANG_IMAGE_FORMAT_RGBA8I => "rgba8i",
+        ffi::SLANG_IMAGE_FORMAT_RG32I => "rg32i",
+        ffi::SLANG_IMAGE_FORMAT_RG16I => "rg16i",
+        ffi::SLANG_IMAGE_FORMAT_RG8I => "rg8i",
+        ffi::SLANG_IMAGE_FORMAT_R32I => "r32i",
+        ffi::SLANG_IMAGE_FORMAT_R16I => "r16i",
+        ffi::SLANG_IMAGE_FORMAT_R8I => "r8i",
+        ffi::SLANG_IMAGE_FORMAT_RGBA32UI => "rgba32ui",
+        ffi::SLANG_IMAGE_FORMAT_RGBA16UI => "rgba16ui",
+        ffi::SLANG_IMAGE_FORMAT_RGB10_A2UI => "rgb10_a2ui",
+        ffi::SLANG_IMAGE_FORMAT_RGBA8UI => "rgba8ui",
+        ffi::SLANG_IMAGE_FORMAT_RG32UI => "rg32ui",
+        ffi::SLANG_IMAGE_FORMAT_RG16UI => "rg16ui",
+        ffi::SLANG_IMAGE_FORMAT_RG8UI => "rg8ui",
+        ffi::SLANG_IMAGE_FORMAT_R32UI => "r32ui",
+        ffi::SLANG_IMAGE_FORMAT_R16UI => "r16ui",
+        ffi::SLANG_IMAGE_FORMAT_R8UI => "r8ui",
+        ffi::SLANG_IMAGE_FORMAT_R64UI => "r64ui",
+        ffi::SLANG_IMAGE_FORMAT_R64I => "r64i",
+        ffi::SLANG_IMAGE_FORMAT_BGRA8 => "bgra8",
+    ]);
+    named!(matrix_layout_mode, "matrix layout mode", [
+        ffi::SLANG_MATRIX_LAYOUT_MODE_UNKNOWN => "unknown",
+        ffi::SLANG_MATRIX_LAYOUT_ROW_MAJOR => "row_major",
+        ffi::SLANG_MATRIX_LAYOUT_COLUMN_MAJOR => "column_major",
+    ]);
+    named!(resource_access, "resource access", [
+        ffi::SLANG_RESOURCE_ACCESS_NONE => "none",
+        ffi::SLANG_RESOURCE_ACCESS_READ => "read",
+        ffi::SLANG_RESOURCE_ACCESS_READ_WRITE => "read_write",
+        ffi::SLANG_RESOURCE_ACCESS_RASTER_ORDERED => "raster_ordered",
+        ffi::SLANG_RESOURCE_ACCESS_APPEND => "append",
+        ffi::SLANG_RESOURCE_ACCESS_CONSUME => "consume",
+        ffi::SLANG_RESOURCE_ACCESS_WRITE => "write",
+        ffi::SLANG_RESOURCE_ACCESS_FEEDBACK => "feedback",
+        ffi::SLANG_RESOURCE_ACCESS_UNKNOWN => "unknown",
+    ]);
+
+    /// Resource shapes are written as the base shape followed by its flags, e.g. `texture_2d|array|multisample`.
+    pub mod resource_shape {
+        use super::*;
+
+        const BASE: &[(u32, &str)] = &[
+            (ffi::SLANG_RESOURCE_NONE, "none"),
+            (ffi::SLANG_TEXTURE_1D, "texture_1d"),
+            (ffi::SLANG_TEXTURE_2D, "texture_2d"),
+            (ffi::SLANG_TEXTURE_3D, "texture_3d"),
+            (ffi::SLANG_TEXTURE_CUBE, "texture_cube"),
+            (ffi::SLANG_TEXTURE_BUFFER, "texture_buffer"),
+            (ffi::SLANG_STRUCTURED_BUFFER, "structured_buffer"),
+            (ffi::SLANG_BYTE_ADDRESS_BUFFER, "byte_address_buffer"),
+            (ffi::SLANG_RESOURCE_UNKNOWN, "unknown"),
+            (ffi::SLANG_ACCELERATION_STRUCTURE, "acceleration_structure"),
+            (ffi::SLANG_TEXTURE_SUBPASS, "texture_subpass"),
+        ];
+        const FLAGS: &[(u32, &str)] = &[
+            (ffi::SLANG_TEXTURE_FEEDBACK_FLAG, "feedback"),
+            (ffi::SLANG_TEXTURE_SHADOW_FLAG, "shadow"),
+            (ffi::SLANG_TEXTURE_ARRAY_FLAG, "array"),
+            (ffi::SLANG_TEXTURE_MULTISAMPLE_FLAG, "multisample"),
+            (ffi::SLANG_TEXTURE_COMBINED_FLAG, "combined"),
+        ];
+        const KNOWN: u32 = ffi::SLANG_RESOURCE_BASE_SHAPE_MASK | ffi::SLANG_RESOURCE_EXT_SHAPE_MASK;
+
+        pub fn serialize<S: Serializer>(value: &u32, s: S) -> Result<S::Ok, S::Error> {
+            let base = value & ffi::SLANG_RESOURCE_BASE_SHAPE_MASK;
+            let flags = value & !ffi::SLANG_RESOURCE_BASE_SHAPE_MASK;
+            let base_name = BASE.iter().find(|&&(v, _)| v == base);
+            let (Some(&(_, base_name)), 0, 0) = (
+                base_name,
+                value & !KNOWN,
+                FLAGS.iter().fold(flags, |f, &(v, _)| f & !v),
+            ) else {
+                return s.serialize_str(&format!("{RAW}{value}"));
+            };
+
+            let mut name = base_name.to_owned();
+            for &(v, n) in FLAGS {
+                if value & v != 0 {
+                    name.push('|');
+                    name.push_str(n);
+                }
+            }
+
+            s.serialize_str(&name)
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<u32, D::Error> {
+            let name = String::deserialize(d)?;
+            if let Some(value) = raw(&name, "resource shape") {
+                return value;
+            }
+            let mut parts = name.split('|');
+            let unknown = || de::Error::custom(format!("unknown resource shape {name:?}"));
+
+            let base = parts.next().unwrap_or_default();
+            let mut value = BASE
+                .iter()
+                .find(|&&(_, n)| n == base)
+                .ok_or_else(unknown)?
+                .0;
+            for part in parts {
+                value |= FLAGS
+                    .iter()
+                    .find(|&&(_, n)| n == part)
+                    .ok_or_else(unknown)?
+                    .0;
+            }
+
+            Ok(value)
+        }
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+    use crate::ffi;
+
+    fn type_layout(kind: TypeKind) -> TypeLayoutReflection {
+        TypeLayoutReflection {
+            kind,
+            name: None,
+            full_name: None,
+            parameter_category: ParameterCategory::None,
+            sizes: Vec::new(),
+            scalar_type: ScalarType::None,
+            row_count: 0,
+            column_count: 0,
+            element_count: 0,
+            matrix_layout_mode: ffi::SLANG_MATRIX_LAYOUT_MODE_UNKNOWN,
+            resource_shape: ffi::SLANG_RESOURCE_NONE,
+            resource_access: ffi::SLANG_RESOURCE_ACCESS_NONE,
+            fields: Vec::new(),
+            element: None,
+            element_offsets: Vec::new(),
+            container_offsets: Vec::new(),
+            binding_ranges: Vec::new(),
+            descriptor_sets: Vec::new(),
+            sub_object_ranges: Vec::new(),
+            attributes: Vec::new(),
+        }
+    }
+
+    fn variable(name: &str, type_layout: TypeLayoutReflection) -> VariableLayoutReflection {
+        VariableLayoutReflection {
+            name: Some(name.to_owned()),
+            semantic_name: None,
+            semantic_index: 0,
+            stage: ffi::SLANG_STAGE_NONE,
+            image_format: ffi::SLANG_IMAGE_FORMAT_UNKNOWN,
+            offsets: Vec::new(),
+            type_layout,
+            attributes: Vec::new(),
+        }
+    }
+
+    fn program() -> ProgramReflection {
+        let texture = TypeLayoutReflection {
+            name: Some("RWTexture2DArray".to_owned()),
+            parameter_category: ParameterCategory::DescriptorTableSlot,
+            sizes: vec![CategorySize {
+                category: ParameterCategory::DescriptorTableSlot,
+                size: 1,
+                stride: 1,
+                alignment: 1,
+            }],
+            resource_shape: ffi::SLANG_TEXTURE_2D_ARRAY,
+            resource_access: ffi::SLANG_RESOURCE_ACCESS_READ_WRITE,
+            binding_ranges: vec![BindingRangeReflection {
+                binding_type: BindingType::MutableTexture,
+                binding_count: 1,
+                is_specializable: false,
+                image_format: ffi::SLANG_IMAGE_FORMAT_RGBA8,
+                descriptor_set_index: 0,
+                first_descriptor_range_index: 0,
+                descriptor_range_count: 1,
+                leaf_variable_name: Some("output".to_owned()),
+            }],
+            ..type_layout(TypeKind::Resource)
+        };
+        let matrix = TypeLayoutReflection {
+            scalar_type: ScalarType::Float32,
+            row_count: 4,
+            column_count: 4,
+            matrix_layout_mode: ffi::SLANG_MATRIX_LAYOUT_COLUMN_MAJOR,
+            ..type_layout(TypeKind::Matrix)
+        };
+
+        ProgramReflection {
+            schema_version: ProgramReflection::SCHEMA_VERSION,
+            parameters: vec![
+                VariableLayoutReflection {
+                    image_format: ffi::SLANG_IMAGE_FORMAT_RGBA8,
+                    offsets: vec![BindingOffset {
+                        category: ParameterCategory::DescriptorTableSlot,
+                        offset: 2,
+                        space: 1,
+                    }],
+                    attributes: vec![AttributeReflection {
+                        name: "Tag".to_owned(),
+                        arguments: vec![
+                            AttributeArgument::Int(3),
+                            AttributeArgument::Float(0.5),
+                            AttributeArgument::String("x".to_owned()),
+                            AttributeArgument::Unknown,
+                        ],
+                    }],
+                    ..variable("output", texture)
+                },
+                variable("transform", matrix),
+            ],
+            entry_points: vec![EntryPointReflection {
+                name: "main".to_owned(),
+                name_override: None,
+                stage: ffi::SLANG_STAGE_COMPUTE,
+                parameters: Vec::new(),
+                var_layout: None,
+                result: None,
+                thread_group_size: [8, 8, 1],
+                wave_size: 0,
+                uses_any_sample_rate_input: false,
+                has_default_constant_buffer: false,
+                attributes: Vec::new(),
+            }],
+            global_params: None,
+            global_constant_buffer_binding: 0,
+            global_constant_buffer_size: 0,
+        }
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let program = program();
+        let json = serde_json::to_string(&program).unwrap();
+
+        assert_eq!(
+            serde_json::from_str::<ProgramReflection>(&json).unwrap(),
+            program
+        );
+    }
+
+    #[test]
+    fn bincode_round_trip() {
+        let mut program = program();
+        let bytes = bincode::serialize(&program).unwrap();
+        assert_eq!(
+            bincode::deserialize::<ProgramReflection>(&bytes).unwrap(),
+            program
+        );
+
+        program.entry_points[0].stage = 1000;
+        program.parameters[0].type_layout.resource_shape = ffi::SLANG_TEXTURE_2D | 0x8000;
+        let bytes = bincode::serialize(&program).unwrap();
+        assert_eq!(
+            bincode::deserialize::<ProgramReflection>(&bytes).unwrap(),
+            program
+        );
+    }
+
+    #[test]
+    fn enumerations_are_named() {
+        let json = serde_json::to_value(program()).unwrap();
+        let output = &json["parameters"][0];
+
+        assert_eq!(json["entry_points"][0]["stage"], "compute");
+        assert_eq!(output["stage"], "none");
+        assert_eq!(output["image_format"], "rgba8");
+        assert_eq!(output["type_layout"]["kind"], "resource");
+        assert_eq!(
+            output["type_layout"]["parameter_category"],
+            "descriptor_table_slot"
+        );
+        assert_eq!(
+            output["type_layout"]["binding_ranges"][0]["binding_type"],
+            "mutable_texture"
+        );
+        assert_eq!(
+            json["parameters"][1]["type_layout"]["scalar_type"],
+            "float32"
+        );
+        assert_eq!(output["type_layout"]["resource_shape"], "texture_2d|array");
+        assert_eq!(output["type_layout"]["resource_access"], "read_write");
+        assert_eq!(
+            output["type_layout"]["binding_ranges"][0]["image_format"],
+            "rgba8"
+        );
+        assert_eq!(
+            json["parameters"][1]["type_layout"]["matrix_layout_mode"],
+            "column_major"
+        );
+    }
+
+    #[test]
+    fn unknown_names_are_rejected() {
+        let mut json = serde_json::to_value(program()).unwrap();
+        json["entry_points"][0]["stage"] = "tessellation".into();
+        assert!(serde_json::from_value::<ProgramReflection>(json).is_err());
+
+        let mut json = serde_json::to_value(program()).unwrap();
+        json["parameters"][0]["type_layout"]["resource_shape"] = "texture_2d|sparse".into();
+        assert!(serde_json::from_value::<ProgramReflection>(json).is_err());
+
+        let mut json = serde_json::to_value(program()).unwrap();
+        json["parameters"][0]["image_format"] = "raw:rgba8".into();
+        assert!(serde_json::from_value::<ProgramReflection>(json).is_err());
+    }
+
+    #[test]
+    fn unnamed_values_are_written_as_raw_numbers() {
+        // e.g. values added by a newer Slang release
+        let mut program = program();
+        program.entry_points[0].stage = 1000;
+        program.parameters[0].image_format = 2000;
+        program.parameters[0].type_layout.resource_shape = ffi::SLANG_TEXTURE_2D | 0x8000;
+        program.parameters[0].type_layout.resource_access = 3000;
+
+        let json = serde_json::to_value(&program).unwrap();
+        let output = &json["parameters"][0];
+        assert_eq!(json["entry_points"][0]["stage"], "raw:1000");
+        assert_eq!(output["image_format"], "raw:2000");
+        assert_eq!(output["type_layout"]["resource_shape"], "raw:32770");
+        assert_eq!(output["type_layout"]["resource_access"], "raw:3000");
+
+        assert_eq!(
+            serde_json::from_value::<ProgramReflection>(json).unwrap(),
+            program
+        );
+    }
+}
@@ -0,0 +1,791 @@
+//! Stage input/output reflection, for creating pipeline vertex layouts and checking render target formats.
+//!
+//! [`vertex_attributes`] flattens the varying inputs of a vertex entry point (including nested structs, arrays and
+//! matrices) into one [`VertexAttribute`] per location. System values (`SV_*` semantics) take no vertex buffer data
+//! and are skipped, as are entry points of other stages.
+//!
+//! [`color_targets`] lists the color attachments a fragment entry point writes, from its result and its `out`
+//! parameters, as one [`ColorTarget`] per `SV_Target` index or output location. Other system values
+//! (`SV_Depth`, `SV_Coverage`, ...) are skipped, as are entry points of other stages.
+
+use crate::{
+    ffi,
+    reflection::{
+        EntryPoint, ParameterCategory, ScalarType, TypeKind, TypeLayout, VariableLayout, owned_name,
+    },
+};
+
+/// One vertex input location.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VertexAttribute {
+    /// Path of the input in the entry point signature, e.g. `input.normal` or `input.weights[1]`.
+    pub name: String,
+    pub location: u32,
+    pub semantic_name: Option<String>,
+    pub semantic_index: u32,
+    pub scalar_type: ScalarType,
+    /// 1 for scalars, the vector size for vectors and matrix rows/columns.
+    pub component_count: u32,
+}
+
+/// One color attachment written by a fragment shader.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColorTarget {
+    /// Path of the output in the entry point signature: the field path for a returned struct (e.g. `albedo`), the
+    /// parameter name for an `out` parameter, or empty for a result that is not a struct.
+    pub name: String,
+    /// Attachment index: the `SV_Target` index, or the output location for outputs without `SV_Target`.
+    pub index: u32,
+    /// Output location (`layout(location = N)` on Vulkan).
+    pub location: u32,
+    pub scalar_type: ScalarType,
+    pub component_count: u32,
+}
+
+/// Returns the vertex attributes of a vertex entry point, ordered by location.
+///
+/// Entry points of other stages read their inputs from the previous stage rather than from vertex buffers, so they
+/// have no vertex attributes (an empty list is returned).
+pub fn vertex_attributes(entry_point: EntryPoint<'_>) -> Vec<VertexAttribute> {
+    let category = ParameterCategory::VaryingInput;
+    let inputs = entry_point
+        .iter_parameter()
+        .map(|p| Variable::of(p, category, owned_name(p.name()).unwrap_or_default()));
+    vertex_attributes_of(entry_point.stage(), inputs)
+}
+
+fn vertex_attributes_of<L: VaryingLayout>(
+    stage: crate::Stage,
+    inputs: impl IntoIterator<Item = Variable<L>>,
+) -> Vec<VertexAttribute> {
+    if stage != ffi::SLANG_STAGE_VERTEX {
+        return Vec::new();
+    }
+
+    let mut walker = Walker::new(ParameterCategory::VaryingInput);
+    for v in inputs {
+        walker.add(v);
+    }
+
+    let mut out: Vec<_> = walker
+        .out
+        .into_iter()
+        .map(|v| {
+            let (semantic_name, semantic_index) =
+                v.semantic.map_or((None, 0), |s| (Some(s.name), s.index));
+            VertexAttribute {
+                name: v.name,
+                location: v.location as _,
+                semantic_name,
+                semantic_index,
+                scalar_type: v.scalar_type,
+                component_count: v.component_count,
+            }
+        })
+        .collect();
+    out.sort_by_key(|a| a.location);
+    out
+}
+
+/// Returns the color targets of a fragment entry point, ordered by attachment index.
+///
+/// An entry point returning `void` (which has no result layout) only writes through its `out` parameters. The outputs
+/// of other stages go to the next stage rather than to attachments, so they have no color targets (an empty list is
+/// returned).
+pub fn color_targets(entry_point: EntryPoint<'_>) -> Vec<ColorTarget> {
+    let category = ParameterCategory::VaryingOutput;
+    let result = entry_point
+        .result_var_layout()
+        .map(|r| Variable::of(r, category, String::new()));
+    let outputs = entry_point
+        .iter_parameter()
+        .map(|p| Variable::of(p, category, owned_name(p.name()).unwrap_or_default()));
+    color_targets_of(entry_point.stage(), result.into_iter().chain(outputs))
+}
+
+fn color_targets_of<L: VaryingLayout>(
+    stage: crate::Stage,
+    outputs: impl IntoIterator<Item = Variable<L>>,
+) -> Vec<ColorTarget> {
+    if stage != ffi::SLANG_STAGE_FRAGMENT {
+        return Vec::new();
+    }
+
+    let mut walker = Walker::new(ParameterCategory::VaryingOutput);
+    for v in outputs {
+        walker.add(v);
+    }
+
+    let mut out: Vec<_> = walker
+        .out
+        .into_iter()
+        .map(|v| ColorTarget {
+            index: target_index(v.semantic.as_ref(), v.location),
+            name: v.name,
+            location: v.location as _,
+            scalar_type: v.scalar_type,
+            component_count: v.component_count,
+        })
+        .collect();
+    out.sort_by_key(|t| t.index);
+    out
+}
+
+/// Attachment index of an output: its `SV_Target` index, or its location.
+fn target_index(semantic: Option<&Semantic>, location: usize) -> u32 {
+    semantic
+        .filter(|s| s.is_target())
+        .map_or(location as _, |s| s.index)
+}
+
+impl EntryPoint<'_> {
+    /// See [`vertex_attributes`].
+    #[inline(always)]
+    pub fn vertex_attributes(self) -> Vec<VertexAttribute> {
+        vertex_attributes(self)
+    }
+
+    /// See [`color_targets`].
+    #[inline(always)]
+    pub fn color_targets(self) -> Vec<ColorTarget> {
+        color_targets(self)
+    }
+}
+
+#[derive(Clone)]
+struct Semantic {
+    name: String,
+    index: u32,
+}
+impl Semantic {
+    fn of(v: VariableLayout<'_>) -> Option<Self> {
+        Some(Self {
+            name: owned_name(v.semantic_name())?,
+            index: v.semantic_index() as _,
+        })
+    }
+
+    fn is_system_value(&self) -> bool {
+        self.name
+            .get(..3)
+            .is_some_and(|p| p.eq_ignore_ascii_case("sv_"))
+    }
+
+    fn is_target(&self) -> bool {
+        self.name.eq_ignore_ascii_case("sv_target")
+    }
+
+    fn offset(&self, by: u32) -> Self {
+        Self {
+            name: self.name.clone(),
+            index: self.index + by,
+        }
+    }
+}
+
+/// A parameter or field: its path (or name, for a field), the location it starts at (relative to the enclosing
+/// struct, for a field), its semantic and its layout.
+struct Variable<L> {
+    name: String,
+    location: usize,
+    semantic: Option<Semantic>,
+    layout: L,
+}
+impl<'a> Variable<TypeLayout<'a>> {
+    fn of(v: VariableLayout<'a>, category: ParameterCategory, name: String) -> Self {
+        Self {
+            name,
+            location: v.offset(category),
+            semantic: Semantic::of(v),
+            layout: v.type_layout(),
+        }
+    }
+}
+
+/// The parts of a type layout the walker reads.
+///
+/// Sizes and offsets are counted in locations of the walked category; a type that takes none (e.g. a uniform
+/// parameter of an entry point) is not part of the stage interface.
+trait VaryingLayout: Copy {
+    fn kind(self) -> TypeKind;
+    /// Number of locations of `category` the type takes.
+    fn size(self, category: ParameterCategory) -> usize;
+    fn scalar_type(self) -> ScalarType;
+    fn row_count(self) -> u32;
+    fn column_count(self) -> u32;
+    fn column_major(self) -> bool;
+    fn element_count(self) -> usize;
+    fn element(self) -> Option<Self>;
+    /// Every field, with its location relative to the struct.
+    fn fields(self, category: ParameterCategory) -> impl Iterator<Item = Variable<Self>>;
+}
+impl VaryingLayout for TypeLayout<'_> {
+    #[inline]
+    fn kind(self) -> TypeKind {
+        TypeLayout::kind(self)
+    }
+
+    #[inline]
+    fn size(self, category: ParameterCategory) -> usize {
+        TypeLayout::size(self, category)
+    }
+
+    #[inline]
+    fn scalar_type(self) -> ScalarType {
+        TypeLayout::scalar_type(self)
+    }
+
+    #[inline]
+    fn row_count(self) -> u32 {
+        TypeLayout::row_count(self)
+    }
+
+    #[inline]
+    fn column_count(self) -> u32 {
+        TypeLayout::column_count(self)
+    }
+
+    #[inline]
+    fn column_major(self) -> bool {
+        self.matrix_layout_mode() == ffi::SLANG_MATRIX_LAYOUT_COLUMN_MAJOR
+    }
+
+    #[inline]
+    fn element_count(self) -> usize {
+        TypeLayout::element_count(self, None)
+    }
+
+    #[inline]
+    fn element(self) -> Option<Self> {
+        self.element_type_layout()
+    }
+
+    fn fields(self, category: ParameterCategory) -> impl Iterator<Item = Variable<Self>> {
+        self.iter_field()
+            .map(move |f| Variable::of(f, category, owned_name(f.name()).unwrap_or_default()))
+    }
+}
+
+/// A single flattened varying.
+struct Varying {
+    name: String,
+    location: usize,
+    semantic: Option<Semantic>,
+    scalar_type: ScalarType,
+    component_count: u32,
+}
+
+struct Walker {
+    category: ParameterCategory,
+    out: Vec<Varying>,
+}
+impl Walker {
+    fn new(category: ParameterCategory) -> Self {
+        Self {
+            category,
+            out: Vec::new(),
+        }
+    }
+
+    fn add<L: VaryingLayout>(&mut self, v: Variable<L>) {
+        let Variable {
+            name: path,
+            location,
+            semantic,
+            layout: t,
+        } = v;
+        match t.kind() {
+            TypeKind::Struct => {
+                for f in t.fields(self.category) {
+                    self.add(Variable {
+                        name: if path.is_empty() {
+                            f.name
+                        } else {
+                            format!("{path}.{}", f.name)
+                        },
+                        location: location + f.location,
+                        ..f
+                    });
+                }
+            }
+            TypeKind::Array => {
+                let Some(element) = t.element() else {
+                    return;
+                };
+                let stride = element.size(self.category).max(1);
+                for i in 0..t.element_count() {
+                    self.add(Variable {
+                        name: format!("{path}[{i}]"),
+                        location: location + i * stride,
+                        semantic: semantic.as_ref().map(|s| s.offset((i * stride) as _)),
+                        layout: element,
+                    });
+                }
+            }
+            TypeKind::Matrix => {
+                // every row (or column, for column-major storage) takes a location of its own
+                let (slots, components) = if t.column_major() {
+                    (t.column_count(), t.row_count())
+                } else {
+                    (t.row_count(), t.column_count())
+                };
+                for i in 0..slots {
+                    self.push(
+                        t,
+                        location + i as usize,
+                        semantic.as_ref().map(|s| s.offset(i)),
+                        components,
+                        format!("{path}[{i}]"),
+                    );
+                }
+            }
+            TypeKind::Vector => {
+                let components = t.column_count();
+                self.push(t, location, semantic, components, path);
+            }
+            TypeKind::Scalar => self.push(t, location, semantic, 1, path),
+            _ => (),
+        }
+    }
+
+    fn push(
+        &mut self,
+        t: impl VaryingLayout,
+        location: usize,
+        semantic: Option<Semantic>,
+        component_count: u32,
+        name: String,
+    ) {
+        match &semantic {
+            // render targets are the only system values that are part of the stage interface
+            Some(s) if s.is_system_value() => {
+                if !(self.category == ParameterCategory::VaryingOutput && s.is_target()) {
+                    return;
+                }
+            }
+            // uniform parameters take no locations
+            _ => {
+                if t.size(self.category) == 0 {
+                    return;
+                }
+            }
+        }
+
+        self.out.push(Varying {
+            name,
+            location,
+            semantic,
+            scalar_type: t.scalar_type(),
+            component_count,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn semantic(name: &str, index: u32) -> Semantic {
+        Semantic {
+            name: name.to_owned(),
+            index,
+        }
+    }
+
+    /// A hand-built varying type; sizes count locations.
+    #[derive(Clone)]
+    struct Type {
+        kind: TypeKind,
+        size: usize,
+        scalar_type: ScalarType,
+        rows: u32,
+        columns: u32,
+        column_major: bool,
+        count: usize,
+        element: Option<Box<Type>>,
+        fields: Vec<(&'static str, usize, Option<Semantic>, Type)>,
+    }
+    impl VaryingLayout for &Type {
+        fn kind(self) -> TypeKind {
+            self.kind
+        }
+
+        fn size(self, _: ParameterCategory) -> usize {
+            self.size
+        }
+
+        fn scalar_type(self) -> ScalarType {
+            self.scalar_type
+        }
+
+        fn row_count(self) -> u32 {
+            self.rows
+        }
+
+        fn column_count(self) -> u32 {
+            self.columns
+        }
+
+        fn column_major(self) -> bool {
+            self.column_major
+        }
+
+        fn element_count(self) -> usize {
+            self.count
+        }
+
+        fn element(self) -> Option<Self> {
+            self.element.as_deref()
+        }
+
+        fn fields(self, _: ParameterCategory) -> impl Iterator<Item = Variable<Self>> {
+            self.fields
+                .iter()
+                .map(|(name, location, semantic, layout)| Variable {
+                    name: (*name).to_owned(),
+                    location: *location,
+                    semantic: semantic.clone(),
+                    layout,
+                })
+        }
+    }
+
+    fn scalar(scalar_type: ScalarType) -> Type {
+        Type {
+            kind: TypeKind::Scalar,
+            size: 1,
+            scalar_type,
+            rows: 1,
+            columns: 1,
+            column_major: false,
+            count: 0,
+            element: None,
+            fields: Vec::new(),
+        }
+    }
+
+    fn vector(scalar_type: ScalarType, columns: u32) -> Type {
+        Type {
+            kind: TypeKind::Vector,
+            columns,
+            ..scalar(scalar_type)
+        }
+    }
+
+    fn matrix(rows: u32, columns: u32, column_major: bool) -> Type {
+        Type {
+            kind: TypeKind::Matrix,
+            size: if column_major { columns } else { rows } as _,
+            rows,
+            columns,
+            column_major,
+            ..scalar(ScalarType::Float32)
+        }
+    }
+
+    fn array(element: Type, count: usize) -> Type {
+        Type {
+            kind: TypeKind::Array,
+            size: element.size * count,
+            count,
+            element: Some(Box::new(element)),
+            ..scalar(ScalarType::None)
+        }
+    }
+
+    fn structure(fields: Vec<(&'static str, usize, Option<Semantic>, Type)>) -> Type {
+        Type {
+            kind: TypeKind::Struct,
+            size: fields.iter().map(|(_, _, _, t)| t.size).sum(),
+            fields,
+            ..scalar(ScalarType::None)
+        }
+    }
+
+    fn variable<'a>(
+        name: &str,
+        location: usize,
+        semantic: Option<Semantic>,
+        layout: &'a Type,
+    ) -> Variable<&'a Type> {
+        Variable {
+            name: name.to_owned(),
+            location,
+            semantic,
+            layout,
+        }
+    }
+
+    fn attribute(
+        name: &str,
+        location: u32,
+        semantic: Option<(&str, u32)>,
+        scalar_type: ScalarType,
+        component_count: u32,
+    ) -> VertexAttribute {
+        VertexAttribute {
+            name: name.to_owned(),
+            location,
+            semantic_name: semantic.map(|(n, _)| n.to_owned()),
+            semantic_index: semantic.map_or(0, |(_, i)| i),
+            scalar_type,
+            component_count,
+        }
+    }
+
+    #[test]
+    fn system_values() {
+        assert!(semantic("SV_Position", 0).is_system_value());
+        assert!(semantic("sv_vertexid", 0).is_system_value());
+        assert!(semantic("SV_Target", 1).is_system_value());
+        assert!(!semantic("POSITION", 0).is_system_value());
+        assert!(!semantic("SV", 0).is_system_value());
+        // the prefix ends inside a multi-byte character
+        assert!(!semantic("SVé", 0).is_system_value());
+    }
+
+    #[test]
+    fn targets() {
+        assert!(semantic("SV_Target", 0).is_target());
+        assert!(semantic("sv_target", 3).is_target());
+        assert!(!semantic("SV_Target1", 0).is_target());
+        assert!(!semantic("SV_Depth", 0).is_target());
+        assert!(!semantic("COLOR", 0).is_target());
+    }
+
+    #[test]
+    fn target_indices() {
+        // `SV_TargetN` picks the attachment regardless of the location
+        assert_eq!(target_index(Some(&semantic("SV_Target", 2)), 0), 2);
+        // outputs without `SV_Target` (e.g. `[[vk::location(1)]]` or a user semantic) use their location
+        assert_eq!(target_index(Some(&semantic("COLOR", 0)), 1), 1);
+        assert_eq!(target_index(None, 3), 3);
+    }
+
+    #[test]
+    fn semantic_offsets() {
+        let s = semantic("TEXCOORD", 2).offset(3);
+        assert_eq!(s.name, "TEXCOORD");
+        assert_eq!(s.index, 5);
+    }
+
+    #[test]
+    fn nested_structs() {
+        let material = structure(vec![
+            (
+                "uv",
+                0,
+                Some(semantic("TEXCOORD", 0)),
+                vector(ScalarType::Float32, 2),
+            ),
+            (
+                "id",
+                1,
+                Some(semantic("MATERIAL", 0)),
+                scalar(ScalarType::UInt32),
+            ),
+        ]);
+        let input = structure(vec![
+            (
+                "position",
+                0,
+                Some(semantic("POSITION", 0)),
+                vector(ScalarType::Float32, 3),
+            ),
+            // takes no location
+            (
+                "vertex_id",
+                0,
+                Some(semantic("SV_VertexID", 0)),
+                scalar(ScalarType::UInt32),
+            ),
+            ("material", 1, None, material),
+        ]);
+
+        assert_eq!(
+            vertex_attributes_of(
+                ffi::SLANG_STAGE_VERTEX,
+                [variable("input", 2, None, &input)]
+            ),
+            [
+                attribute(
+                    "input.position",
+                    2,
+                    Some(("POSITION", 0)),
+                    ScalarType::Float32,
+                    3
+                ),
+                attribute(
+                    "input.material.uv",
+                    3,
+                    Some(("TEXCOORD", 0)),
+                    ScalarType::Float32,
+                    2
+                ),
+                attribute(
+                    "input.material.id",
+                    4,
+                    Some(("MATERIAL", 0)),
+                    ScalarType::UInt32,
+                    1
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn arrays() {
+        let weights = array(vector(ScalarType::Float32, 4), 2);
+        // every element takes as many locations as its matrix has rows
+        let bones = array(matrix(3, 4, false), 2);
+
+        assert_eq!(
+            vertex_attributes_of(
+                ffi::SLANG_STAGE_VERTEX,
+                [
+                    variable("weights", 0, Some(semantic("WEIGHT", 1)), &weights),
+                    variable("bones", 2, Some(semantic("BONE", 0)), &bones),
+                ]
+            ),
+            [
+                attribute("weights[0]", 0, Some(("WEIGHT", 1)), ScalarType::Float32, 4),
+                attribute("weights[1]", 1, Some(("WEIGHT", 2)), ScalarType::Float32, 4),
+                attribute("bones[0][0]", 2, Some(("BONE", 0)), ScalarType::Float32, 4),
+                attribute("bones[0][1]", 3, Some(("BONE", 1)), ScalarType::Float32, 4),
+                attribute("bones[0][2]", 4, Some(("BONE", 2)), ScalarType::Float32, 4),
+                attribute("bones[1][0]", 5, Some(("BONE", 3)), ScalarType::Float32, 4),
+                attribute("bones[1][1]", 6, Some(("BONE", 4)), ScalarType::Float32, 4),
+                attribute("bones[1][2]", 7, Some(("BONE", 5)), ScalarType::Float32, 4),
+            ]
+        );
+    }
+
+    #[test]
+    fn matrices() {
+        let row_major = matrix(2, 3, false);
+        let column_major = matrix(2, 3, true);
+
+        assert_eq!(
+            vertex_attributes_of(
+                ffi::SLANG_STAGE_VERTEX,
+                [
+                    variable("a", 0, Some(semantic("A", 0)), &row_major),
+                    variable("b", 2, None, &column_major),
+                ]
+            ),
+            [
+                attribute("a[0]", 0, Some(("A", 0)), ScalarType::Float32, 3),
+                attribute("a[1]", 1, Some(("A", 1)), ScalarType::Float32, 3),
+                attribute("b[0]", 2, None, ScalarType::Float32, 2),
+                attribute("b[1]", 3, None, ScalarType::Float32, 2),
+                attribute("b[2]", 4, None, ScalarType::Float32, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn uniform_parameters_and_system_values_are_skipped() {
+        let position = vector(ScalarType::Float32, 4);
+        let uniform = Type {
+            size: 0,
+            ..scalar(ScalarType::Float32)
+        };
+
+        assert_eq!(
+            vertex_attributes_of(
+                ffi::SLANG_STAGE_VERTEX,
+                [
+                    variable("scale", 0, None, &uniform),
+                    variable("instance", 0, Some(semantic("SV_InstanceID", 0)), &uniform),
+                    variable("position", 0, None, &position),
+                ]
+            ),
+            [attribute("position", 0, None, ScalarType::Float32, 4)]
+        );
+    }
+
+    #[test]
+    fn other_stages_have_no_vertex_attributes() {
+        let position = vector(ScalarType::Float32, 4);
+        for stage in [ffi::SLANG_STAGE_FRAGMENT, ffi::SLANG_STAGE_COMPUTE] {
+            assert_eq!(
+                vertex_attributes_of(stage, [variable("position", 0, None, &position)]),
+                []
+            );
+        }
+    }
+
+    #[test]
+    fn outputs() {
+        let output = structure(vec![
+            (
+                "albedo",
+                0,
+                Some(semantic("SV_Target", 1)),
+                vector(ScalarType::Float32, 4),
+            ),
+            (
+                "normal",
+                1,
+                Some(semantic("SV_Target", 0)),
+                vector(ScalarType::Float16, 2),
+            ),
+            (
+                "depth",
+                0,
+                Some(semantic("SV_Depth", 0)),
+                scalar(ScalarType::Float32),
+            ),
+        ]);
+        let id = scalar(ScalarType::UInt32);
+
+        assert_eq!(
+            color_targets_of(
+                ffi::SLANG_STAGE_FRAGMENT,
+                [variable("", 0, None, &output), variable("id", 2, None, &id)]
+            ),
+            [
+                ColorTarget {
+                    name: "normal".to_owned(),
+                    index: 0,
+                    location: 1,
+                    scalar_type: ScalarType::Float16,
+                    component_count: 2,
+                },
+                ColorTarget {
+                    name: "albedo".to_owned(),
+                    index: 1,
+                    location: 0,
+                    scalar_type: ScalarType::Float32,
+                    component_count: 4,
+                },
+                ColorTarget {
+                    name: "id".to_owned(),
+                    index: 2,
+                    location: 2,
+                    scalar_type: ScalarType::UInt32,
+                    component_count: 1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn other_stages_have_no_color_targets() {
+        // e.g. the interpolants a vertex shader passes to the fragment shader
+        let uv = vector(ScalarType::Float32, 2);
+        let color = vector(ScalarType::Float32, 4);
+        for stage in [ffi::SLANG_STAGE_VERTEX, ffi::SLANG_STAGE_COMPUTE] {
+            assert_eq!(
+                color_targets_of(
+                    stage,
+                    [
+                        variable("uv", 0, Some(semantic("TEXCOORD", 0)), &uv),
+                        variable("color", 1, Some(semantic("SV_Target", 0)), &color),
+                    ]
+                ),
+                []
+            );
+        }
+    }
+}
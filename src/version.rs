@@ -0,0 +1,230 @@
+//! Version of the Slang library, as reported by its build tag.
+
+use core::ops::Range;
+
+use crate::{IGlobalSession, SlangError};
+
+/// A Slang release version (`year.release.patch`, e.g. `2025.10.4`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SlangVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+impl SlangVersion {
+    #[inline]
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Parses a build tag as produced by `git describe --tags` (e.g. `v2025.10.4`, `v2025.10.4-12-gdeadbeef`).
+    ///
+    /// Returns `None` for tags that do not start with a version number, such as the `unknown` tag of libraries
+    /// built outside the release scripts.
+    pub fn parse(tag: &str) -> Option<Self> {
+        let tag = tag.trim();
+        let tag = tag.strip_prefix('v').unwrap_or(tag);
+        let version = tag.split(['-', '+']).next()?;
+
+        let mut parts = version.split('.').map(str::parse::<u32>);
+        let major = parts.next()?.ok()?;
+        let minor = parts.next()?.ok()?;
+        let patch = parts.next().unwrap_or(Ok(0)).ok()?;
+        if parts.next().is_some() {
+            return None;
+        }
+
+        Some(Self::new(major, minor, patch))
+    }
+
+    /// The version of the library behind `global_session`.
+    ///
+    /// Returns the raw build tag if it cannot be parsed.
+    pub fn of(global_session: &impl IGlobalSession) -> core::result::Result<Self, String> {
+        let tag = global_session.get_build_tag_string().to_string_lossy();
+
+        Self::parse(&tag).ok_or_else(|| tag.into_owned())
+    }
+}
+impl core::fmt::Display for SlangVersion {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Library versions whose ABI matches the declarations of this crate (struct layouts, enum values such as
+/// [`CompilerOptionName`](crate::CompilerOptionName)).
+///
+/// Starts at the first release with all the declarations this crate uses and ends after the last release series it
+/// was tested with: Slang does not promise a stable ABI (enum values have been inserted and renumbered between
+/// releases), so newer libraries are rejected until they have been checked and the range is extended.
+pub const SUPPORTED_VERSIONS: Range<SlangVersion> =
+    SlangVersion::new(2025, 10, 0)..SlangVersion::new(2026, 0, 0);
+
+/// Failure of [`create_global_session_checked`](crate::create_global_session_checked) and [`check_version`].
+#[derive(Debug, Clone)]
+pub enum VersionError {
+    /// Creating the global session failed.
+    Slang(SlangError),
+    /// The build tag of the library does not contain a version (e.g. `unknown` for a custom build).
+    UnknownVersion(String),
+    /// The library is outside [`SUPPORTED_VERSIONS`].
+    Incompatible {
+        version: SlangVersion,
+        supported: Range<SlangVersion>,
+    },
+}
+impl core::fmt::Display for VersionError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Slang(e) => core::fmt::Display::fmt(e, f),
+            Self::UnknownVersion(tag) => {
+                write!(f, "unknown Slang library version (build tag {tag:?})")
+            }
+            Self::Incompatible { version, supported } => write!(
+                f,
+                "Slang library version {version} is not supported (supported: {} up to, but excluding, {})",
+                supported.start, supported.end
+            ),
+        }
+    }
+}
+impl std::error::Error for VersionError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Slang(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+impl From<SlangError> for VersionError {
+    #[inline]
+    fn from(e: SlangError) -> Self {
+        Self::Slang(e)
+    }
+}
+
+/// Checks that the library behind `global_session` is within [`SUPPORTED_VERSIONS`], returning its version.
+///
+/// Use this directly to relax the check (e.g. to accept [`VersionError::UnknownVersion`] for custom builds).
+pub fn check_version(
+    global_session: &impl IGlobalSession,
+) -> core::result::Result<SlangVersion, VersionError> {
+    let version = SlangVersion::of(global_session).map_err(VersionError::UnknownVersion)?;
+
+    check_supported(version)
+}
+
+fn check_supported(version: SlangVersion) -> core::result::Result<SlangVersion, VersionError> {
+    if !SUPPORTED_VERSIONS.contains(&version) {
+        return Err(VersionError::Incompatible {
+            version,
+            supported: SUPPORTED_VERSIONS,
+        });
+    }
+
+    Ok(version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            SlangVersion::parse("2025.10.4"),
+            Some(SlangVersion::new(2025, 10, 4))
+        );
+        assert_eq!(
+            SlangVersion::parse("v2025.10.4"),
+            Some(SlangVersion::new(2025, 10, 4))
+        );
+        assert_eq!(
+            SlangVersion::parse(" v2025.10.4\n"),
+            Some(SlangVersion::new(2025, 10, 4))
+        );
+    }
+
+    #[test]
+    fn parse_suffix() {
+        assert_eq!(
+            SlangVersion::parse("v2025.10.4-12-gdeadbeef"),
+            Some(SlangVersion::new(2025, 10, 4))
+        );
+        assert_eq!(
+            SlangVersion::parse("v2025.10.4+local"),
+            Some(SlangVersion::new(2025, 10, 4))
+        );
+    }
+
+    #[test]
+    fn parse_missing_patch() {
+        assert_eq!(
+            SlangVersion::parse("v2025.10"),
+            Some(SlangVersion::new(2025, 10, 0))
+        );
+        assert_eq!(
+            SlangVersion::parse("v2025.10-3-gdeadbeef"),
+            Some(SlangVersion::new(2025, 10, 0))
+        );
+    }
+
+    #[test]
+    fn parse_invalid() {
+        for tag in [
+            "",
+            "unknown",
+            "v",
+            "v2025",
+            "v2025.x.1",
+            "v2025.10.4.1",
+            "2025..4",
+        ] {
+            assert_eq!(SlangVersion::parse(tag), None, "{tag:?}");
+        }
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let v = SlangVersion::new(2026, 3, 1);
+        assert_eq!(v.to_string(), "2026.3.1");
+        assert_eq!(SlangVersion::parse(&v.to_string()), Some(v));
+    }
+
+    #[test]
+    fn supported_versions() {
+        let start = SUPPORTED_VERSIONS.start;
+        assert_eq!(check_supported(start).ok(), Some(start));
+        assert!(check_supported(SlangVersion::new(2025, 24, 3)).is_ok());
+    }
+
+    #[test]
+    fn too_old() {
+        let e = check_supported(SlangVersion::new(2025, 9, 9)).unwrap_err();
+
+        assert!(matches!(
+            e,
+            VersionError::Incompatible { version, .. } if version == SlangVersion::new(2025, 9, 9)
+        ));
+    }
+
+    #[test]
+    fn too_new() {
+        for v in [SUPPORTED_VERSIONS.end, SlangVersion::new(2031, 1, 0)] {
+            let e = check_supported(v).unwrap_err();
+            assert!(matches!(e, VersionError::Incompatible { version, .. } if version == v));
+        }
+
+        assert_eq!(
+            check_supported(SlangVersion::new(2026, 1, 0))
+                .unwrap_err()
+                .to_string(),
+            "Slang library version 2026.1.0 is not supported (supported: 2025.10.0 up to, but excluding, 2026.0.0)"
+        );
+    }
+}
@@ -0,0 +1,414 @@
+//! Vulkan pipeline layout extraction from reflection.
+//!
+//! [`PipelineLayout::new`] produces the data needed to fill the `VkDescriptorSetLayoutBinding`s and
+//! `VkPushConstantRange`s of a program, as plain values so that no particular Vulkan binding crate is required:
+//!
+//! * one [`DescriptorSetLayout`] per non-empty set, with the set and binding numbers Slang assigned
+//! * bindings of the global scope are visible to all stages, bindings of an entry point only to its stage
+//! * unbounded arrays have no descriptor count, to be declared as variable-count bindings
+//! * inline uniform blocks have their size in bytes as descriptor count, as Vulkan expects
+//! * `[vk::push_constant]` buffers are merged into one [`PushConstantRange`] per stage, or into a single range for
+//!   all stages if the global scope has one, as ranges may not share a stage
+
+use crate::{
+    bindings::ProgramBindings,
+    ffi,
+    reflection::{BindingType, Shader},
+};
+
+/// Mirror of `VkDescriptorType`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(i32)]
+pub enum DescriptorType {
+    Sampler = 0,
+    CombinedImageSampler = 1,
+    SampledImage = 2,
+    StorageImage = 3,
+    UniformTexelBuffer = 4,
+    StorageTexelBuffer = 5,
+    UniformBuffer = 6,
+    StorageBuffer = 7,
+    InputAttachment = 10,
+    InlineUniformBlock = 1000138000,
+    AccelerationStructure = 1000150000,
+}
+impl DescriptorType {
+    /// The `VkDescriptorType` value.
+    #[inline(always)]
+    pub const fn as_raw(self) -> i32 {
+        self as i32
+    }
+
+    /// Maps a Slang binding type to the Vulkan descriptor type used to bind it.
+    ///
+    /// Returns `None` for binding types that do not occupy a descriptor (push constants, parameter blocks,
+    /// varyings, ...).
+    pub const fn from_binding_type(binding_type: BindingType) -> Option<Self> {
+        Some(match binding_type {
+            BindingType::Sampler => Self::Sampler,
+            BindingType::CombinedTextureSampler => Self::CombinedImageSampler,
+            BindingType::Texture => Self::SampledImage,
+            BindingType::MutableTexture => Self::StorageImage,
+            BindingType::TypedBuffer => Self::UniformTexelBuffer,
+            BindingType::MutableTypedBuffer => Self::StorageTexelBuffer,
+            BindingType::ConstantBuffer => Self::UniformBuffer,
+            BindingType::RawBuffer | BindingType::MutableRawBuffer => Self::StorageBuffer,
+            BindingType::InputRenderTarget => Self::InputAttachment,
+            BindingType::InlineUniformData => Self::InlineUniformBlock,
+            BindingType::RayTracingAccelerationStructure => Self::AccelerationStructure,
+            _ => return None,
+        })
+    }
+}
+
+/// Mirror of `VkShaderStageFlags`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[repr(transparent)]
+pub struct ShaderStageFlags(pub u32);
+impl ShaderStageFlags {
+    pub const VERTEX: Self = Self(0x0000_0001);
+    pub const TESSELLATION_CONTROL: Self = Self(0x0000_0002);
+    pub const TESSELLATION_EVALUATION: Self = Self(0x0000_0004);
+    pub const GEOMETRY: Self = Self(0x0000_0008);
+    pub const FRAGMENT: Self = Self(0x0000_0010);
+    pub const COMPUTE: Self = Self(0x0000_0020);
+    pub const ALL_GRAPHICS: Self = Self(0x0000_001f);
+    pub const ALL: Self = Self(0x7fff_ffff);
+    pub const TASK: Self = Self(0x0000_0040);
+    pub const MESH: Self = Self(0x0000_0080);
+    pub const RAYGEN: Self = Self(0x0000_0100);
+    pub const ANY_HIT: Self = Self(0x0000_0200);
+    pub const CLOSEST_HIT: Self = Self(0x0000_0400);
+    pub const MISS: Self = Self(0x0000_0800);
+    pub const INTERSECTION: Self = Self(0x0000_1000);
+    pub const CALLABLE: Self = Self(0x0000_2000);
+
+    /// The flag for a Slang stage, or an empty set for stages Vulkan has no flag for.
+    pub const fn from_stage(stage: crate::Stage) -> Self {
+        match stage {
+            ffi::SLANG_STAGE_VERTEX => Self::VERTEX,
+            ffi::SLANG_STAGE_HULL => Self::TESSELLATION_CONTROL,
+            ffi::SLANG_STAGE_DOMAIN => Self::TESSELLATION_EVALUATION,
+            ffi::SLANG_STAGE_GEOMETRY => Self::GEOMETRY,
+            ffi::SLANG_STAGE_FRAGMENT => Self::FRAGMENT,
+            ffi::SLANG_STAGE_COMPUTE => Self::COMPUTE,
+            ffi::SLANG_STAGE_AMPLIFICATION => Self::TASK,
+            ffi::SLANG_STAGE_MESH => Self::MESH,
+            ffi::SLANG_STAGE_RAY_GENERATION => Self::RAYGEN,
+            ffi::SLANG_STAGE_ANY_HIT => Self::ANY_HIT,
+            ffi::SLANG_STAGE_CLOSEST_HIT => Self::CLOSEST_HIT,
+            ffi::SLANG_STAGE_MISS => Self::MISS,
+            ffi::SLANG_STAGE_INTERSECTION => Self::INTERSECTION,
+            ffi::SLANG_STAGE_CALLABLE => Self::CALLABLE,
+            _ => Self(0),
+        }
+    }
+
+    #[inline(always)]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    #[inline(always)]
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+}
+impl core::ops::BitOr for ShaderStageFlags {
+    type Output = Self;
+
+    #[inline(always)]
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+impl core::ops::BitOrAssign for ShaderStageFlags {
+    #[inline(always)]
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// Shape of a `VkDescriptorSetLayoutBinding`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DescriptorSetLayoutBinding {
+    pub binding: u32,
+    pub descriptor_type: DescriptorType,
+    /// Number of descriptors, or `None` for an unbounded array (to be declared with
+    /// `VK_DESCRIPTOR_BINDING_VARIABLE_DESCRIPTOR_COUNT_BIT`).
+    pub descriptor_count: Option<u32>,
+    pub stage_flags: ShaderStageFlags,
+}
+
+/// Bindings of one descriptor set.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DescriptorSetLayout {
+    /// Index of the set (`layout(set = N)`).
+    pub set: u32,
+    pub bindings: Vec<DescriptorSetLayoutBinding>,
+}
+
+/// Shape of a `VkPushConstantRange`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PushConstantRange {
+    pub stage_flags: ShaderStageFlags,
+    pub offset: u32,
+    pub size: u32,
+}
+
+/// Descriptor set layouts and push-constant ranges of a program.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PipelineLayout {
+    /// Non-empty sets, ordered by set index.
+    pub set_layouts: Vec<DescriptorSetLayout>,
+    /// At most one range per stage, all starting at offset 0.
+    pub push_constant_ranges: Vec<PushConstantRange>,
+}
+impl PipelineLayout {
+    pub fn new(shader: Shader<'_>) -> Self {
+        Self::from_bindings(ProgramBindings::new(shader))
+    }
+
+    fn from_bindings(bindings: ProgramBindings) -> Self {
+        let mut layout = Self::default();
+
+        for b in bindings.bindings {
+            let Some(descriptor_type) = DescriptorType::from_binding_type(b.binding_type) else {
+                continue;
+            };
+            if layout.set_layouts.last().is_none_or(|s| s.set != b.set) {
+                layout.set_layouts.push(DescriptorSetLayout {
+                    set: b.set,
+                    bindings: Vec::new(),
+                });
+            }
+            let set = layout.set_layouts.last_mut().unwrap();
+            set.bindings.push(DescriptorSetLayoutBinding {
+                binding: b.binding,
+                descriptor_type,
+                descriptor_count: if descriptor_type == DescriptorType::InlineUniformBlock {
+                    Some(b.uniform_size as _)
+                } else {
+                    b.count
+                },
+                stage_flags: stage_flags(b.stage),
+            });
+        }
+
+        // a block of the global scope is visible to every stage, so it takes all blocks in
+        let global = bindings.push_constants.iter().any(|p| p.stage.is_none());
+        for p in bindings.push_constants {
+            let stage_flags = if global {
+                ShaderStageFlags::ALL
+            } else {
+                stage_flags(p.stage)
+            };
+            if stage_flags.is_empty() {
+                continue;
+            }
+
+            match layout
+                .push_constant_ranges
+                .iter_mut()
+                .find(|r| r.stage_flags == stage_flags)
+            {
+                Some(r) => r.size = r.size.max(p.size as _),
+                None => layout.push_constant_ranges.push(PushConstantRange {
+                    stage_flags,
+                    offset: 0,
+                    size: p.size as _,
+                }),
+            }
+        }
+
+        layout
+    }
+
+    /// The layout of set `set`, if it has any bindings.
+    pub fn set_layout(&self, set: u32) -> Option<&DescriptorSetLayout> {
+        self.set_layouts.iter().find(|s| s.set == set)
+    }
+}
+
+impl Shader<'_> {
+    /// Extracts the Vulkan pipeline layout of this program. See [`PipelineLayout::new`].
+    #[inline(always)]
+    pub fn vulkan_pipeline_layout(self) -> PipelineLayout {
+        PipelineLayout::new(self)
+    }
+}
+
+fn stage_flags(stage: Option<crate::Stage>) -> ShaderStageFlags {
+    stage.map_or(ShaderStageFlags::ALL, ShaderStageFlags::from_stage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bindings::{PushConstantBlock, tests::binding};
+
+    #[test]
+    fn descriptor_types() {
+        assert_eq!(
+            DescriptorType::from_binding_type(BindingType::ConstantBuffer),
+            Some(DescriptorType::UniformBuffer)
+        );
+        assert_eq!(
+            DescriptorType::from_binding_type(BindingType::MutableRawBuffer),
+            Some(DescriptorType::StorageBuffer)
+        );
+        assert_eq!(
+            DescriptorType::from_binding_type(BindingType::MutableTexture),
+            Some(DescriptorType::StorageImage)
+        );
+        assert_eq!(
+            DescriptorType::from_binding_type(BindingType::CombinedTextureSampler),
+            Some(DescriptorType::CombinedImageSampler)
+        );
+        assert_eq!(
+            DescriptorType::from_binding_type(BindingType::InlineUniformData),
+            Some(DescriptorType::InlineUniformBlock)
+        );
+        assert_eq!(
+            DescriptorType::from_binding_type(BindingType::PushConstant),
+            None
+        );
+        assert_eq!(
+            DescriptorType::from_binding_type(BindingType::ParameterBlock),
+            None
+        );
+        assert_eq!(DescriptorType::InlineUniformBlock.as_raw(), 1000138000);
+    }
+
+    #[test]
+    fn stages() {
+        assert_eq!(
+            ShaderStageFlags::from_stage(ffi::SLANG_STAGE_FRAGMENT),
+            ShaderStageFlags::FRAGMENT
+        );
+        assert_eq!(
+            ShaderStageFlags::from_stage(ffi::SLANG_STAGE_HULL),
+            ShaderStageFlags::TESSELLATION_CONTROL
+        );
+        assert_eq!(
+            ShaderStageFlags::from_stage(ffi::SLANG_STAGE_AMPLIFICATION),
+            ShaderStageFlags::TASK
+        );
+        assert!(ShaderStageFlags::from_stage(ffi::SLANG_STAGE_NONE).is_empty());
+        assert!(ShaderStageFlags::ALL_GRAPHICS.contains(ShaderStageFlags::VERTEX));
+        assert!(!ShaderStageFlags::ALL_GRAPHICS.contains(ShaderStageFlags::COMPUTE));
+    }
+
+    #[test]
+    fn sets() {
+        let mut inline = binding(1, 2, BindingType::InlineUniformData, Some(1), None);
+        inline.uniform_size = 64;
+        let bindings = ProgramBindings {
+            bindings: vec![
+                binding(
+                    2,
+                    0,
+                    BindingType::Texture,
+                    None,
+                    Some(ffi::SLANG_STAGE_FRAGMENT),
+                ),
+                binding(0, 1, BindingType::Sampler, Some(1), None),
+                inline,
+                binding(0, 0, BindingType::ConstantBuffer, Some(1), None),
+                // no descriptor
+                binding(3, 0, BindingType::PushConstant, Some(1), None),
+            ],
+            push_constants: Vec::new(),
+        }
+        .sorted();
+        let layout = PipelineLayout::from_bindings(bindings);
+
+        let set = |set, bindings| DescriptorSetLayout { set, bindings };
+        let b =
+            |binding, descriptor_type, descriptor_count, stage_flags| DescriptorSetLayoutBinding {
+                binding,
+                descriptor_type,
+                descriptor_count,
+                stage_flags,
+            };
+        assert_eq!(
+            layout.set_layouts,
+            [
+                set(
+                    0,
+                    vec![
+                        b(
+                            0,
+                            DescriptorType::UniformBuffer,
+                            Some(1),
+                            ShaderStageFlags::ALL
+                        ),
+                        b(1, DescriptorType::Sampler, Some(1), ShaderStageFlags::ALL),
+                    ]
+                ),
+                set(
+                    1,
+                    vec![b(
+                        2,
+                        DescriptorType::InlineUniformBlock,
+                        Some(64),
+                        ShaderStageFlags::ALL
+                    )]
+                ),
+                set(
+                    2,
+                    vec![b(
+                        0,
+                        DescriptorType::SampledImage,
+                        None,
+                        ShaderStageFlags::FRAGMENT
+                    )]
+                ),
+            ]
+        );
+        assert_eq!(layout.set_layout(1).map(|s| s.bindings.len()), Some(1));
+        assert_eq!(layout.set_layout(3), None);
+    }
+
+    fn push_constant_ranges(blocks: &[(Option<crate::Stage>, usize)]) -> Vec<PushConstantRange> {
+        PipelineLayout::from_bindings(ProgramBindings {
+            bindings: Vec::new(),
+            push_constants: blocks
+                .iter()
+                .map(|&(stage, size)| PushConstantBlock { stage, size })
+                .collect(),
+        })
+        .push_constant_ranges
+    }
+
+    #[test]
+    fn push_constants_per_stage() {
+        let range = |stage_flags, size| PushConstantRange {
+            stage_flags,
+            offset: 0,
+            size,
+        };
+
+        assert_eq!(
+            push_constant_ranges(&[
+                (Some(ffi::SLANG_STAGE_VERTEX), 16),
+                (Some(ffi::SLANG_STAGE_FRAGMENT), 32),
+                (Some(ffi::SLANG_STAGE_VERTEX), 48),
+            ]),
+            [
+                range(ShaderStageFlags::VERTEX, 48),
+                range(ShaderStageFlags::FRAGMENT, 32)
+            ]
+        );
+        // the global block covers every stage, so it absorbs the entry point blocks before and after it
+        assert_eq!(
+            push_constant_ranges(&[
+                (Some(ffi::SLANG_STAGE_VERTEX), 64),
+                (None, 16),
+                (Some(ffi::SLANG_STAGE_FRAGMENT), 32),
+            ]),
+            [range(ShaderStageFlags::ALL, 64)]
+        );
+        assert_eq!(push_constant_ranges(&[]), []);
+    }
+}
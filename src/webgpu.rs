@@ -0,0 +1,859 @@
+//! WebGPU bind group layout extraction from reflection, for programs compiled to `SLANG_WGSL`.
+//!
+//! [`PipelineLayout::new`] produces one [`BindGroupLayout`] per `@group`, with entries shaped like
+//! `GPUBindGroupLayoutEntry`, without depending on any WebGPU crate. Group and binding numbers are the ones Slang
+//! assigned; the visibility of an entry is every stage for the global scope, or the stage of its entry point. Resources
+//! that WebGPU cannot bind (arrays, combined texture-samplers, ...) are reported as a [`WebGpuError`], and
+//! push-constant blocks are left out.
+//!
+//! With the `serde` feature the entries serialize to the JSON shape WebGPU expects, e.g.
+//! `{"binding":0,"visibility":3,"texture":{"sampleType":"float","viewDimension":"2d","multisampled":false}}`, so they
+//! can be handed to `createBindGroupLayout` directly from JavaScript.
+
+use crate::{
+    bindings::{ProgramBindings, ResourceBinding},
+    ffi,
+    reflection::{BindingType, ScalarType, Shader},
+};
+
+/// Mirror of `GPUShaderStageFlags`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[repr(transparent)]
+pub struct ShaderStages(pub u32);
+impl ShaderStages {
+    pub const VERTEX: Self = Self(0x1);
+    pub const FRAGMENT: Self = Self(0x2);
+    pub const COMPUTE: Self = Self(0x4);
+    pub const ALL: Self = Self(0x7);
+
+    /// The flag for a Slang stage, or an empty set for stages WebGPU does not have.
+    pub const fn from_stage(stage: crate::Stage) -> Self {
+        match stage {
+            ffi::SLANG_STAGE_VERTEX => Self::VERTEX,
+            ffi::SLANG_STAGE_FRAGMENT => Self::FRAGMENT,
+            ffi::SLANG_STAGE_COMPUTE => Self::COMPUTE,
+            _ => Self(0),
+        }
+    }
+
+    #[inline(always)]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    #[inline(always)]
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+}
+impl core::ops::BitOr for ShaderStages {
+    type Output = Self;
+
+    #[inline(always)]
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+impl core::ops::BitOrAssign for ShaderStages {
+    #[inline(always)]
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// Mirror of `GPUBufferBindingType`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum BufferBindingType {
+    Uniform,
+    Storage,
+    ReadOnlyStorage,
+}
+
+/// Mirror of `GPUSamplerBindingType`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum SamplerBindingType {
+    Filtering,
+    NonFiltering,
+    Comparison,
+}
+
+/// Mirror of `GPUTextureSampleType`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum TextureSampleType {
+    Float,
+    UnfilterableFloat,
+    Depth,
+    Sint,
+    Uint,
+}
+
+/// Mirror of `GPUTextureViewDimension`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TextureViewDimension {
+    #[cfg_attr(feature = "serde", serde(rename = "1d"))]
+    D1,
+    #[cfg_attr(feature = "serde", serde(rename = "2d"))]
+    D2,
+    #[cfg_attr(feature = "serde", serde(rename = "2d-array"))]
+    D2Array,
+    #[cfg_attr(feature = "serde", serde(rename = "cube"))]
+    Cube,
+    #[cfg_attr(feature = "serde", serde(rename = "cube-array"))]
+    CubeArray,
+    #[cfg_attr(feature = "serde", serde(rename = "3d"))]
+    D3,
+}
+
+/// Mirror of `GPUStorageTextureAccess`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum StorageTextureAccess {
+    WriteOnly,
+    ReadOnly,
+    ReadWrite,
+}
+
+/// The `GPUTextureFormat`s a storage texture can be declared with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum TextureFormat {
+    R8Unorm,
+    R8Snorm,
+    R8Uint,
+    R8Sint,
+    R16Unorm,
+    R16Snorm,
+    R16Uint,
+    R16Sint,
+    R16Float,
+    Rg8Unorm,
+    Rg8Snorm,
+    Rg8Uint,
+    Rg8Sint,
+    R32Uint,
+    R32Sint,
+    R32Float,
+    Rg16Unorm,
+    Rg16Snorm,
+    Rg16Uint,
+    Rg16Sint,
+    Rg16Float,
+    Rgba8Unorm,
+    Rgba8Snorm,
+    Rgba8Uint,
+    Rgba8Sint,
+    Bgra8Unorm,
+    Rgb10a2Uint,
+    Rgb10a2Unorm,
+    Rg11b10Ufloat,
+    Rg32Uint,
+    Rg32Sint,
+    Rg32Float,
+    Rgba16Unorm,
+    Rgba16Snorm,
+    Rgba16Uint,
+    Rgba16Sint,
+    Rgba16Float,
+    Rgba32Uint,
+    Rgba32Sint,
+    Rgba32Float,
+}
+impl TextureFormat {
+    /// Maps a Slang image format (`[format("...")]`) to the WebGPU format of the same layout.
+    pub const fn from_image_format(format: crate::ImageFormat) -> Option<Self> {
+        Some(match format {
+            ffi::SLANG_IMAGE_FORMAT_RGBA32F => Self::Rgba32Float,
+            ffi::SLANG_IMAGE_FORMAT_RGBA16F => Self::Rgba16Float,
+            ffi::SLANG_IMAGE_FORMAT_RG32F => Self::Rg32Float,
+            ffi::SLANG_IMAGE_FORMAT_RG16F => Self::Rg16Float,
+            ffi::SLANG_IMAGE_FORMAT_R11F_G11F_B10F => Self::Rg11b10Ufloat,
+            ffi::SLANG_IMAGE_FORMAT_R32F => Self::R32Float,
+            ffi::SLANG_IMAGE_FORMAT_R16F => Self::R16Float,
+            ffi::SLANG_IMAGE_FORMAT_RGBA16 => Self::Rgba16Unorm,
+            ffi::SLANG_IMAGE_FORMAT_RGB10_A2 => Self::Rgb10a2Unorm,
+            ffi::SLANG_IMAGE_FORMAT_RGBA8 => Self::Rgba8Unorm,
+            ffi::SLANG_IMAGE_FORMAT_RG16 => Self::Rg16Unorm,
+            ffi::SLANG_IMAGE_FORMAT_RG8 => Self::Rg8Unorm,
+            ffi::SLANG_IMAGE_FORMAT_R16 => Self::R16Unorm,
+            ffi::SLANG_IMAGE_FORMAT_R8 => Self::R8Unorm,
+            ffi::SLANG_IMAGE_FORMAT_RGBA16_SNORM => Self::Rgba16Snorm,
+            ffi::SLANG_IMAGE_FORMAT_RGBA8_SNORM => Self::Rgba8Snorm,
+            ffi::SLANG_IMAGE_FORMAT_RG16_SNORM => Self::Rg16Snorm,
+            ffi::SLANG_IMAGE_FORMAT_RG8_SNORM => Self::Rg8Snorm,
+            ffi::SLANG_IMAGE_FORMAT_R16_SNORM => Self::R16Snorm,
+            ffi::SLANG_IMAGE_FORMAT_R8_SNORM => Self::R8Snorm,
+            ffi::SLANG_IMAGE_FORMAT_RGBA32I => Self::Rgba32Sint,
+            ffi::SLANG_IMAGE_FORMAT_RGBA16I => Self::Rgba16Sint,
+            ffi::SLANG_IMAGE_FORMAT_RGBA8I => Self::Rgba8Sint,
+            ffi::SLANG_IMAGE_FORMAT_RG32I => Self::Rg32Sint,
+            ffi::SLANG_IMAGE_FORMAT_RG16I => Self::Rg16Sint,
+            ffi::SLANG_IMAGE_FORMAT_RG8I => Self::Rg8Sint,
+            ffi::SLANG_IMAGE_FORMAT_R32I => Self::R32Sint,
+            ffi::SLANG_IMAGE_FORMAT_R16I => Self::R16Sint,
+            ffi::SLANG_IMAGE_FORMAT_R8I => Self::R8Sint,
+            ffi::SLANG_IMAGE_FORMAT_RGBA32UI => Self::Rgba32Uint,
+            ffi::SLANG_IMAGE_FORMAT_RGBA16UI => Self::Rgba16Uint,
+            ffi::SLANG_IMAGE_FORMAT_RGB10_A2UI => Self::Rgb10a2Uint,
+            ffi::SLANG_IMAGE_FORMAT_RGBA8UI => Self::Rgba8Uint,
+            ffi::SLANG_IMAGE_FORMAT_RG32UI => Self::Rg32Uint,
+            ffi::SLANG_IMAGE_FORMAT_RG16UI => Self::Rg16Uint,
+            ffi::SLANG_IMAGE_FORMAT_RG8UI => Self::Rg8Uint,
+            ffi::SLANG_IMAGE_FORMAT_R32UI => Self::R32Uint,
+            ffi::SLANG_IMAGE_FORMAT_R16UI => Self::R16Uint,
+            ffi::SLANG_IMAGE_FORMAT_R8UI => Self::R8Uint,
+            ffi::SLANG_IMAGE_FORMAT_BGRA8 => Self::Bgra8Unorm,
+            _ => return None,
+        })
+    }
+
+    /// The 32-bit format with the given element type, which is what Slang picks for a storage texture without
+    /// an explicit `[format("...")]`.
+    const fn from_element(scalar: ScalarType, components: u32) -> Option<Self> {
+        Some(match (scalar, components) {
+            (ScalarType::Float32, 1) => Self::R32Float,
+            (ScalarType::Float32, 2) => Self::Rg32Float,
+            (ScalarType::Float32, 4) => Self::Rgba32Float,
+            (ScalarType::Int32, 1) => Self::R32Sint,
+            (ScalarType::Int32, 2) => Self::Rg32Sint,
+            (ScalarType::Int32, 4) => Self::Rgba32Sint,
+            (ScalarType::UInt32, 1) => Self::R32Uint,
+            (ScalarType::UInt32, 2) => Self::Rg32Uint,
+            (ScalarType::UInt32, 4) => Self::Rgba32Uint,
+            _ => return None,
+        })
+    }
+}
+
+/// The resource-specific part of a `GPUBindGroupLayoutEntry`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(rename_all = "camelCase", rename_all_fields = "camelCase")
+)]
+pub enum BindingLayout {
+    Buffer {
+        #[cfg_attr(feature = "serde", serde(rename = "type"))]
+        ty: BufferBindingType,
+    },
+    Sampler {
+        #[cfg_attr(feature = "serde", serde(rename = "type"))]
+        ty: SamplerBindingType,
+    },
+    /// `sample_type` is [`TextureSampleType::Float`] for float textures; whether the texture is actually
+    /// filterable depends on the format bound at runtime.
+    Texture {
+        sample_type: TextureSampleType,
+        view_dimension: TextureViewDimension,
+        multisampled: bool,
+    },
+    StorageTexture {
+        access: StorageTextureAccess,
+        format: TextureFormat,
+        view_dimension: TextureViewDimension,
+    },
+}
+
+/// Shape of a `GPUBindGroupLayoutEntry`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BindGroupLayoutEntry {
+    pub binding: u32,
+    pub visibility: ShaderStages,
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub layout: BindingLayout,
+}
+
+/// Entries of one bind group.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BindGroupLayout {
+    /// Index of the group (`@group(N)`).
+    pub group: u32,
+    pub entries: Vec<BindGroupLayoutEntry>,
+}
+
+/// Bind group layouts of a program.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PipelineLayout {
+    /// Non-empty groups, ordered by group index.
+    pub bind_group_layouts: Vec<BindGroupLayout>,
+}
+impl PipelineLayout {
+    /// Push-constant blocks have no WebGPU equivalent and are not reported.
+    pub fn new(shader: Shader<'_>) -> Result<Self, WebGpuError> {
+        let mut layout = Self::default();
+
+        for b in ProgramBindings::new(shader).bindings {
+            let entry = entry(&b)?;
+            if layout
+                .bind_group_layouts
+                .last()
+                .is_none_or(|g| g.group != b.set)
+            {
+                layout.bind_group_layouts.push(BindGroupLayout {
+                    group: b.set,
+                    entries: Vec::new(),
+                });
+            }
+            layout
+                .bind_group_layouts
+                .last_mut()
+                .unwrap()
+                .entries
+                .push(entry);
+        }
+
+        Ok(layout)
+    }
+
+    /// The layout of group `group`, if it has any entries.
+    pub fn bind_group_layout(&self, group: u32) -> Option<&BindGroupLayout> {
+        self.bind_group_layouts.iter().find(|g| g.group == group)
+    }
+}
+
+impl Shader<'_> {
+    /// Extracts the WebGPU bind group layouts of this program. See [`PipelineLayout::new`].
+    #[inline(always)]
+    pub fn webgpu_pipeline_layout(self) -> Result<PipelineLayout, WebGpuError> {
+        PipelineLayout::new(self)
+    }
+}
+
+/// A binding that cannot be expressed in WebGPU.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebGpuError {
+    pub group: u32,
+    pub binding: u32,
+    pub reason: &'static str,
+}
+impl core::fmt::Display for WebGpuError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "@group({}) @binding({}): {}",
+            self.group, self.binding, self.reason
+        )
+    }
+}
+impl std::error::Error for WebGpuError {}
+
+fn entry(b: &ResourceBinding) -> Result<BindGroupLayoutEntry, WebGpuError> {
+    let error = |reason| WebGpuError {
+        group: b.set,
+        binding: b.binding,
+        reason,
+    };
+    if b.count != Some(1) {
+        return Err(error("arrays of resources are not supported"));
+    }
+
+    let layout = match b.binding_type {
+        BindingType::ConstantBuffer => BindingLayout::Buffer {
+            ty: BufferBindingType::Uniform,
+        },
+        BindingType::RawBuffer | BindingType::TypedBuffer => BindingLayout::Buffer {
+            ty: BufferBindingType::ReadOnlyStorage,
+        },
+        BindingType::MutableRawBuffer | BindingType::MutableTypedBuffer => BindingLayout::Buffer {
+            ty: BufferBindingType::Storage,
+        },
+        BindingType::Sampler => BindingLayout::Sampler {
+            ty: if b.type_name.as_deref() == Some("SamplerComparisonState") {
+                SamplerBindingType::Comparison
+            } else {
+                SamplerBindingType::Filtering
+            },
+        },
+        BindingType::Texture => {
+            let shape = b.resource_shape;
+            BindingLayout::Texture {
+                sample_type: if shape & ffi::SLANG_TEXTURE_SHADOW_FLAG != 0 {
+                    TextureSampleType::Depth
+                } else {
+                    match b.texel.0 {
+                        ScalarType::Int8 | ScalarType::Int16 | ScalarType::Int32 => {
+                            TextureSampleType::Sint
+                        }
+                        ScalarType::UInt8 | ScalarType::UInt16 | ScalarType::UInt32 => {
+                            TextureSampleType::Uint
+                        }
+                        _ => TextureSampleType::Float,
+                    }
+                },
+                view_dimension: view_dimension(shape)
+                    .ok_or_else(|| error("unsupported texture shape"))?,
+                multisampled: shape & ffi::SLANG_TEXTURE_MULTISAMPLE_FLAG != 0,
+            }
+        }
+        BindingType::MutableTexture => {
+            let access = match b.resource_access {
+                ffi::SLANG_RESOURCE_ACCESS_READ => StorageTextureAccess::ReadOnly,
+                ffi::SLANG_RESOURCE_ACCESS_WRITE => StorageTextureAccess::WriteOnly,
+                _ => StorageTextureAccess::ReadWrite,
+            };
+            let (scalar, components) = b.texel;
+            BindingLayout::StorageTexture {
+                access,
+                format: TextureFormat::from_image_format(b.image_format)
+                    .or_else(|| TextureFormat::from_element(scalar, components))
+                    .ok_or_else(|| error("no WebGPU storage texture format for this texture"))?,
+                view_dimension: view_dimension(b.resource_shape)
+                    .ok_or_else(|| error("unsupported texture shape"))?,
+            }
+        }
+        BindingType::CombinedTextureSampler => {
+            return Err(error("combined texture-samplers are not supported"));
+        }
+        _ => return Err(error("binding type has no WebGPU equivalent")),
+    };
+
+    Ok(BindGroupLayoutEntry {
+        binding: b.binding,
+        visibility: visibility(b.stage, &layout),
+        layout,
+    })
+}
+
+/// Stages an entry is visible to: its entry point's stage, or every stage for the global scope.
+fn visibility(stage: Option<crate::Stage>, layout: &BindingLayout) -> ShaderStages {
+    match stage {
+        Some(stage) => ShaderStages::from_stage(stage),
+        // writable resources may not be visible to the vertex stage
+        None if matches!(
+            layout,
+            BindingLayout::Buffer {
+                ty: BufferBindingType::Storage
+            } | BindingLayout::StorageTexture {
+                access: StorageTextureAccess::WriteOnly | StorageTextureAccess::ReadWrite,
+                ..
+            }
+        ) =>
+        {
+            ShaderStages::FRAGMENT | ShaderStages::COMPUTE
+        }
+        None => ShaderStages::ALL,
+    }
+}
+
+fn view_dimension(shape: crate::ResourceShape) -> Option<TextureViewDimension> {
+    let array = shape & ffi::SLANG_TEXTURE_ARRAY_FLAG != 0;
+    Some(match (shape & ffi::SLANG_RESOURCE_BASE_SHAPE_MASK, array) {
+        (ffi::SLANG_TEXTURE_1D, false) => TextureViewDimension::D1,
+        (ffi::SLANG_TEXTURE_2D, false) => TextureViewDimension::D2,
+        (ffi::SLANG_TEXTURE_2D, true) => TextureViewDimension::D2Array,
+        (ffi::SLANG_TEXTURE_3D, false) => TextureViewDimension::D3,
+        (ffi::SLANG_TEXTURE_CUBE, false) => TextureViewDimension::Cube,
+        (ffi::SLANG_TEXTURE_CUBE, true) => TextureViewDimension::CubeArray,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bindings::tests::binding;
+
+    fn texture(shape: crate::ResourceShape, texel: (ScalarType, u32)) -> ResourceBinding {
+        ResourceBinding {
+            resource_shape: shape,
+            resource_access: ffi::SLANG_RESOURCE_ACCESS_READ,
+            texel,
+            ..binding(0, 0, BindingType::Texture, Some(1), None)
+        }
+    }
+
+    fn storage_texture(
+        access: crate::ResourceAccess,
+        image_format: crate::ImageFormat,
+        texel: (ScalarType, u32),
+    ) -> ResourceBinding {
+        ResourceBinding {
+            resource_shape: ffi::SLANG_TEXTURE_2D,
+            resource_access: access,
+            texel,
+            image_format,
+            ..binding(0, 0, BindingType::MutableTexture, Some(1), None)
+        }
+    }
+
+    fn error(reason: &'static str) -> WebGpuError {
+        WebGpuError {
+            group: 0,
+            binding: 0,
+            reason,
+        }
+    }
+
+    #[test]
+    fn view_dimensions() {
+        assert_eq!(
+            view_dimension(ffi::SLANG_TEXTURE_2D),
+            Some(TextureViewDimension::D2)
+        );
+        assert_eq!(
+            view_dimension(ffi::SLANG_TEXTURE_2D | ffi::SLANG_TEXTURE_ARRAY_FLAG),
+            Some(TextureViewDimension::D2Array)
+        );
+        assert_eq!(
+            view_dimension(ffi::SLANG_TEXTURE_2D | ffi::SLANG_TEXTURE_MULTISAMPLE_FLAG),
+            Some(TextureViewDimension::D2)
+        );
+        assert_eq!(
+            view_dimension(ffi::SLANG_TEXTURE_CUBE | ffi::SLANG_TEXTURE_ARRAY_FLAG),
+            Some(TextureViewDimension::CubeArray)
+        );
+        assert_eq!(
+            view_dimension(ffi::SLANG_TEXTURE_3D),
+            Some(TextureViewDimension::D3)
+        );
+        // no 1D or 3D arrays in WebGPU
+        assert_eq!(
+            view_dimension(ffi::SLANG_TEXTURE_1D | ffi::SLANG_TEXTURE_ARRAY_FLAG),
+            None
+        );
+        assert_eq!(view_dimension(ffi::SLANG_STRUCTURED_BUFFER), None);
+    }
+
+    #[test]
+    fn texture_formats() {
+        assert_eq!(
+            TextureFormat::from_image_format(ffi::SLANG_IMAGE_FORMAT_RGBA8),
+            Some(TextureFormat::Rgba8Unorm)
+        );
+        assert_eq!(
+            TextureFormat::from_image_format(ffi::SLANG_IMAGE_FORMAT_R11F_G11F_B10F),
+            Some(TextureFormat::Rg11b10Ufloat)
+        );
+        assert_eq!(
+            TextureFormat::from_image_format(ffi::SLANG_IMAGE_FORMAT_R32UI),
+            Some(TextureFormat::R32Uint)
+        );
+        assert_eq!(
+            TextureFormat::from_image_format(ffi::SLANG_IMAGE_FORMAT_UNKNOWN),
+            None
+        );
+
+        assert_eq!(
+            TextureFormat::from_element(ScalarType::Float32, 4),
+            Some(TextureFormat::Rgba32Float)
+        );
+        assert_eq!(
+            TextureFormat::from_element(ScalarType::Int32, 2),
+            Some(TextureFormat::Rg32Sint)
+        );
+        // no three-component formats
+        assert_eq!(TextureFormat::from_element(ScalarType::Float32, 3), None);
+        assert_eq!(TextureFormat::from_element(ScalarType::Float16, 4), None);
+    }
+
+    #[test]
+    fn writable_global_resources_skip_the_vertex_stage() {
+        let storage = BindingLayout::Buffer {
+            ty: BufferBindingType::Storage,
+        };
+        let read_only = BindingLayout::Buffer {
+            ty: BufferBindingType::ReadOnlyStorage,
+        };
+        let storage_texture = |access| BindingLayout::StorageTexture {
+            access,
+            format: TextureFormat::R32Float,
+            view_dimension: TextureViewDimension::D2,
+        };
+
+        assert_eq!(
+            visibility(None, &storage),
+            ShaderStages::FRAGMENT | ShaderStages::COMPUTE
+        );
+        assert_eq!(
+            visibility(None, &storage_texture(StorageTextureAccess::WriteOnly)),
+            ShaderStages::FRAGMENT | ShaderStages::COMPUTE
+        );
+        assert_eq!(
+            visibility(None, &storage_texture(StorageTextureAccess::ReadOnly)),
+            ShaderStages::ALL
+        );
+        assert_eq!(visibility(None, &read_only), ShaderStages::ALL);
+        // entry point parameters keep the stage of their entry point
+        assert_eq!(
+            visibility(Some(ffi::SLANG_STAGE_VERTEX), &storage),
+            ShaderStages::VERTEX
+        );
+    }
+
+    #[test]
+    fn buffers() {
+        let uniform = entry(&binding(0, 1, BindingType::ConstantBuffer, Some(1), None)).unwrap();
+        assert_eq!(
+            uniform,
+            BindGroupLayoutEntry {
+                binding: 1,
+                visibility: ShaderStages::ALL,
+                layout: BindingLayout::Buffer {
+                    ty: BufferBindingType::Uniform,
+                },
+            }
+        );
+
+        let storage = binding(0, 2, BindingType::MutableRawBuffer, Some(1), None);
+        assert_eq!(
+            entry(&storage).unwrap().layout,
+            BindingLayout::Buffer {
+                ty: BufferBindingType::Storage,
+            }
+        );
+        let read_only = binding(0, 3, BindingType::TypedBuffer, Some(1), None);
+        assert_eq!(
+            entry(&read_only).unwrap().layout,
+            BindingLayout::Buffer {
+                ty: BufferBindingType::ReadOnlyStorage,
+            }
+        );
+    }
+
+    #[test]
+    fn samplers() {
+        let sampler = |name: &str| ResourceBinding {
+            type_name: Some(name.to_owned()),
+            ..binding(
+                0,
+                0,
+                BindingType::Sampler,
+                Some(1),
+                Some(ffi::SLANG_STAGE_FRAGMENT),
+            )
+        };
+
+        assert_eq!(
+            entry(&sampler("SamplerComparisonState")).unwrap(),
+            BindGroupLayoutEntry {
+                binding: 0,
+                visibility: ShaderStages::FRAGMENT,
+                layout: BindingLayout::Sampler {
+                    ty: SamplerBindingType::Comparison,
+                },
+            }
+        );
+        assert_eq!(
+            entry(&sampler("SamplerState")).unwrap().layout,
+            BindingLayout::Sampler {
+                ty: SamplerBindingType::Filtering,
+            }
+        );
+    }
+
+    #[test]
+    fn texture_sample_types() {
+        let sample_type = |b: ResourceBinding| match entry(&b).unwrap().layout {
+            BindingLayout::Texture { sample_type, .. } => sample_type,
+            layout => panic!("not a texture: {layout:?}"),
+        };
+        let shape = ffi::SLANG_TEXTURE_2D;
+
+        assert_eq!(
+            sample_type(texture(shape, (ScalarType::Float32, 4))),
+            TextureSampleType::Float
+        );
+        assert_eq!(
+            sample_type(texture(shape, (ScalarType::Int32, 2))),
+            TextureSampleType::Sint
+        );
+        assert_eq!(
+            sample_type(texture(shape, (ScalarType::UInt8, 1))),
+            TextureSampleType::Uint
+        );
+        assert_eq!(
+            sample_type(texture(
+                shape | ffi::SLANG_TEXTURE_SHADOW_FLAG,
+                (ScalarType::Float32, 1)
+            )),
+            TextureSampleType::Depth
+        );
+    }
+
+    #[test]
+    fn textures() {
+        let b = texture(
+            ffi::SLANG_TEXTURE_2D
+                | ffi::SLANG_TEXTURE_ARRAY_FLAG
+                | ffi::SLANG_TEXTURE_MULTISAMPLE_FLAG,
+            (ScalarType::Float32, 4),
+        );
+        assert_eq!(
+            entry(&b).unwrap().layout,
+            BindingLayout::Texture {
+                sample_type: TextureSampleType::Float,
+                view_dimension: TextureViewDimension::D2Array,
+                multisampled: true,
+            }
+        );
+
+        let b = texture(
+            ffi::SLANG_TEXTURE_1D | ffi::SLANG_TEXTURE_ARRAY_FLAG,
+            (ScalarType::Float32, 4),
+        );
+        assert_eq!(entry(&b), Err(error("unsupported texture shape")));
+    }
+
+    #[test]
+    fn storage_textures() {
+        let float4 = (ScalarType::Float32, 4);
+
+        // the declared format wins over the texel type
+        let b = storage_texture(
+            ffi::SLANG_RESOURCE_ACCESS_READ_WRITE,
+            ffi::SLANG_IMAGE_FORMAT_RGBA8,
+            float4,
+        );
+        assert_eq!(
+            entry(&b).unwrap(),
+            BindGroupLayoutEntry {
+                binding: 0,
+                visibility: ShaderStages::FRAGMENT | ShaderStages::COMPUTE,
+                layout: BindingLayout::StorageTexture {
+                    access: StorageTextureAccess::ReadWrite,
+                    format: TextureFormat::Rgba8Unorm,
+                    view_dimension: TextureViewDimension::D2,
+                },
+            }
+        );
+
+        // without a declared format, the texel type picks it
+        let b = storage_texture(
+            ffi::SLANG_RESOURCE_ACCESS_WRITE,
+            ffi::SLANG_IMAGE_FORMAT_UNKNOWN,
+            float4,
+        );
+        assert_eq!(
+            entry(&b).unwrap().layout,
+            BindingLayout::StorageTexture {
+                access: StorageTextureAccess::WriteOnly,
+                format: TextureFormat::Rgba32Float,
+                view_dimension: TextureViewDimension::D2,
+            }
+        );
+
+        let b = storage_texture(
+            ffi::SLANG_RESOURCE_ACCESS_READ,
+            ffi::SLANG_IMAGE_FORMAT_UNKNOWN,
+            (ScalarType::UInt32, 1),
+        );
+        assert_eq!(
+            entry(&b).unwrap().layout,
+            BindingLayout::StorageTexture {
+                access: StorageTextureAccess::ReadOnly,
+                format: TextureFormat::R32Uint,
+                view_dimension: TextureViewDimension::D2,
+            }
+        );
+
+        let b = storage_texture(
+            ffi::SLANG_RESOURCE_ACCESS_READ_WRITE,
+            ffi::SLANG_IMAGE_FORMAT_UNKNOWN,
+            (ScalarType::Float32, 3),
+        );
+        assert_eq!(
+            entry(&b),
+            Err(error("no WebGPU storage texture format for this texture"))
+        );
+    }
+
+    #[test]
+    fn unsupported_bindings() {
+        for count in [Some(4), None] {
+            let b = binding(0, 0, BindingType::Texture, count, None);
+            assert_eq!(
+                entry(&b),
+                Err(error("arrays of resources are not supported"))
+            );
+        }
+
+        let b = binding(0, 0, BindingType::CombinedTextureSampler, Some(1), None);
+        assert_eq!(
+            entry(&b),
+            Err(error("combined texture-samplers are not supported"))
+        );
+
+        let b = binding(2, 5, BindingType::InlineUniformData, Some(1), None);
+        assert_eq!(
+            entry(&b),
+            Err(WebGpuError {
+                group: 2,
+                binding: 5,
+                reason: "binding type has no WebGPU equivalent",
+            })
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn entry_json() {
+        let entries = [
+            (
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::VERTEX | ShaderStages::FRAGMENT,
+                    layout: BindingLayout::Texture {
+                        sample_type: TextureSampleType::Float,
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                },
+                r#"{"binding":0,"visibility":3,"texture":{"sampleType":"float","viewDimension":"2d","multisampled":false}}"#,
+            ),
+            (
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::ALL,
+                    layout: BindingLayout::Buffer {
+                        ty: BufferBindingType::ReadOnlyStorage,
+                    },
+                },
+                r#"{"binding":1,"visibility":7,"buffer":{"type":"read-only-storage"}}"#,
+            ),
+            (
+                BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: ShaderStages::FRAGMENT,
+                    layout: BindingLayout::Sampler {
+                        ty: SamplerBindingType::Comparison,
+                    },
+                },
+                r#"{"binding":2,"visibility":2,"sampler":{"type":"comparison"}}"#,
+            ),
+            (
+                BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: ShaderStages::COMPUTE,
+                    layout: BindingLayout::StorageTexture {
+                        access: StorageTextureAccess::WriteOnly,
+                        format: TextureFormat::Rgba16Float,
+                        view_dimension: TextureViewDimension::CubeArray,
+                    },
+                },
+                r#"{"binding":3,"visibility":4,"storageTexture":{"access":"write-only","format":"rgba16float","viewDimension":"cube-array"}}"#,
+            ),
+        ];
+
+        for (entry, json) in entries {
+            assert_eq!(serde_json::to_string(&entry).unwrap(), json);
+            assert_eq!(
+                serde_json::from_str::<BindGroupLayoutEntry>(json).unwrap(),
+                entry
+            );
+        }
+    }
+}
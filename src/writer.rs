@@ -0,0 +1,364 @@
+//! `ISlangWriter` implemented over Rust sinks, for capturing compiler output.
+
+use core::{
+    ffi::{c_char, c_void},
+    mem::MaybeUninit,
+};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use crate::{
+    IBlobPtr, IUnknownVTable, IWriter, IWriterPtr, IWriterVTable, Result, SlangError,
+    SlangInterfacePtr, SlangUUID, UNKNOWN_IID, WRITER_IID, WriterChannel, WriterMode,
+    blob_to_string,
+    com::ComObject,
+    ffi::{
+        SLANG_OK, SLANG_WRITER_CHANNEL_COUNT_OF, SLANG_WRITER_CHANNEL_DIAGNOSTIC, SlangBool,
+        SlangResult,
+    },
+};
+
+/// Destination of text written by Slang through [`IWriterPtr::from_provider`].
+pub trait WriterProvider: Send + 'static {
+    /// Receives a chunk of output. Chunks are not necessarily split at line or UTF-8 character boundaries.
+    fn write(&mut self, chars: &[u8]) -> Result<()>;
+
+    fn flush(&mut self) {}
+
+    fn is_console(&self) -> bool {
+        false
+    }
+
+    fn set_mode(&mut self, _mode: WriterMode) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// [`WriterProvider`] over a [`std::io::Write`].
+struct IoWriter<W>(W);
+impl<W: std::io::Write + Send + 'static> WriterProvider for IoWriter<W> {
+    #[inline]
+    fn write(&mut self, chars: &[u8]) -> Result<()> {
+        self.0.write_all(chars).map_err(|_| SlangError::FAIL)
+    }
+
+    #[inline]
+    fn flush(&mut self) {
+        let _ = self.0.flush();
+    }
+}
+
+/// [`WriterProvider`] passing each chunk to a callback.
+struct FnWriter<F>(F);
+impl<F: FnMut(&str) + Send + 'static> WriterProvider for FnWriter<F> {
+    #[inline]
+    fn write(&mut self, chars: &[u8]) -> Result<()> {
+        (self.0)(&String::from_utf8_lossy(chars));
+        Ok(())
+    }
+}
+
+/// [`WriterProvider`] passing each complete line (without the line terminator) to a callback.
+struct LineWriter<F: FnMut(&str)> {
+    callback: F,
+    pending: Vec<u8>,
+}
+impl<F: FnMut(&str)> LineWriter<F> {
+    fn emit_pending(&mut self) {
+        if self.pending.is_empty() {
+            return;
+        }
+
+        let line = core::mem::take(&mut self.pending);
+        let line = line.strip_suffix(b"\r").unwrap_or(&line);
+        (self.callback)(&String::from_utf8_lossy(line));
+    }
+}
+impl<F: FnMut(&str) + Send + 'static> WriterProvider for LineWriter<F> {
+    fn write(&mut self, chars: &[u8]) -> Result<()> {
+        let mut rest = chars;
+        while let Some(p) = rest.iter().position(|&c| c == b'\n') {
+            self.pending.extend_from_slice(&rest[..p]);
+            let line = core::mem::take(&mut self.pending);
+            let line = line.strip_suffix(b"\r").unwrap_or(&line);
+            (self.callback)(&String::from_utf8_lossy(line));
+            rest = &rest[p + 1..];
+        }
+        self.pending.extend_from_slice(rest);
+
+        Ok(())
+    }
+
+    #[inline]
+    fn flush(&mut self) {
+        self.emit_pending();
+    }
+}
+impl<F: FnMut(&str)> Drop for LineWriter<F> {
+    fn drop(&mut self) {
+        // dropped from `release`, called by Slang: a panicking callback must not unwind into it
+        let _ = std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| self.emit_pending()));
+    }
+}
+
+struct WriterState<P> {
+    provider: Mutex<P>,
+    /// Storage handed out by `beginAppendBuffer`, valid until the next call
+    append_buffer: Mutex<Vec<u8>>,
+}
+
+type WriterObject<P> = ComObject<WriterState<P>>;
+
+struct Adapter<P>(core::marker::PhantomData<P>);
+impl<P: WriterProvider> Adapter<P> {
+    const INTERFACES: &'static [SlangUUID] = &[UNKNOWN_IID, WRITER_IID];
+    const VTABLE: IWriterVTable = IWriterVTable {
+        base: IUnknownVTable {
+            query_interface: WriterObject::<P>::query_interface,
+            add_ref: WriterObject::<P>::add_ref,
+            release: WriterObject::<P>::release,
+        },
+        begin_append_buffer: Self::begin_append_buffer,
+        end_append_buffer: Self::end_append_buffer,
+        write: Self::write,
+        flush: Self::flush,
+        is_console: Self::is_console,
+        set_mode: Self::set_mode,
+    };
+
+    #[inline(always)]
+    unsafe fn provider<'a>(this: *mut c_void) -> MutexGuard<'a, P> {
+        unsafe { WriterObject::<P>::inner(this) }
+            .provider
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    #[inline(always)]
+    fn guarded<R>(f: impl FnOnce() -> R, default: R) -> R {
+        std::panic::catch_unwind(core::panic::AssertUnwindSafe(f)).unwrap_or(default)
+    }
+
+    unsafe extern "system" fn begin_append_buffer(
+        this: *mut c_void,
+        max_num_chars: usize,
+    ) -> *mut c_char {
+        let state = unsafe { WriterObject::<P>::inner(this) };
+        let mut buffer = state
+            .append_buffer
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        buffer.clear();
+        buffer.resize(max_num_chars.max(1), 0);
+
+        buffer.as_mut_ptr().cast()
+    }
+
+    unsafe extern "system" fn end_append_buffer(
+        this: *mut c_void,
+        buffer: *mut c_char,
+        num_chars: usize,
+    ) -> SlangResult {
+        let state = unsafe { WriterObject::<P>::inner(this) };
+        let append_buffer = state
+            .append_buffer
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if buffer.cast::<u8>().cast_const() != append_buffer.as_ptr()
+            || num_chars > append_buffer.len()
+        {
+            return SlangError::INVALID_ARG.code();
+        }
+
+        Self::guarded(
+            || {
+                let r = unsafe { Self::provider(this) }.write(&append_buffer[..num_chars]);
+
+                r.map_or_else(|e| e.code(), |_| SLANG_OK)
+            },
+            SlangError::INTERNAL_FAIL.code(),
+        )
+    }
+
+    unsafe extern "system" fn write(
+        this: *mut c_void,
+        chars: *const c_char,
+        num_chars: usize,
+    ) -> SlangResult {
+        let chars = match num_chars {
+            0 => &[][..],
+            _ => unsafe { core::slice::from_raw_parts(chars.cast::<u8>(), num_chars) },
+        };
+
+        Self::guarded(
+            || {
+                let r = unsafe { Self::provider(this) }.write(chars);
+
+                r.map_or_else(|e| e.code(), |_| SLANG_OK)
+            },
+            SlangError::INTERNAL_FAIL.code(),
+        )
+    }
+
+    unsafe extern "system" fn flush(this: *mut c_void) {
+        Self::guarded(|| unsafe { Self::provider(this) }.flush(), ());
+    }
+
+    unsafe extern "system" fn is_console(this: *mut c_void) -> SlangBool {
+        Self::guarded(|| unsafe { Self::provider(this) }.is_console(), false)
+    }
+
+    unsafe extern "system" fn set_mode(this: *mut c_void, mode: WriterMode) -> SlangResult {
+        Self::guarded(
+            || {
+                let r = unsafe { Self::provider(this) }.set_mode(mode);
+
+                r.map_or_else(|e| e.code(), |_| SLANG_OK)
+            },
+            SlangError::INTERNAL_FAIL.code(),
+        )
+    }
+}
+
+impl IWriterPtr {
+    /// Wraps a Rust writer so it can be installed in [`Writers`] or on a compile request
+    /// (see [`ICompileRequestObject::set_writer`](crate::ICompileRequestObject::set_writer)).
+    pub fn from_provider<P: WriterProvider>(provider: P) -> Self {
+        unsafe {
+            Self::from_ptr(WriterObject::<P>::allocate(
+                &Adapter::<P>::VTABLE,
+                Adapter::<P>::INTERFACES,
+                WriterState {
+                    provider: Mutex::new(provider),
+                    append_buffer: Mutex::new(Vec::new()),
+                },
+            ))
+        }
+    }
+
+    /// A writer forwarding everything to `w` (e.g. a file, `std::io::stderr()` or a `Vec<u8>`).
+    #[inline]
+    pub fn from_io(w: impl std::io::Write + Send + 'static) -> Self {
+        Self::from_provider(IoWriter(w))
+    }
+
+    /// A writer calling `f` with every chunk of output.
+    #[inline]
+    pub fn from_fn(f: impl FnMut(&str) + Send + 'static) -> Self {
+        Self::from_provider(FnWriter(f))
+    }
+
+    /// A writer calling `f` with every complete line of output (e.g. to turn each line into a log record).
+    ///
+    /// A trailing unterminated line is passed on when Slang flushes the writer, or when the writer is released.
+    #[inline]
+    pub fn from_lines(f: impl FnMut(&str) + Send + 'static) -> Self {
+        Self::from_provider(LineWriter {
+            callback: f,
+            pending: Vec::new(),
+        })
+    }
+}
+
+/// A writer for each [`WriterChannel`], for the session-based pipeline.
+///
+/// `ISession` and the component types have no writers of their own: their calls return the diagnostic output as a
+/// blob. The `*_with_writers` methods (e.g. [`ISession::load_module_with_writers`](crate::ISession::load_module_with_writers))
+/// pass that output on to the writer of `SLANG_WRITER_CHANNEL_DIAGNOSTIC`. The standard output and error channels are
+/// only written by compile requests, see [`install`](Self::install).
+#[derive(Debug, Clone, Default)]
+pub struct Writers([Option<IWriterPtr>; SLANG_WRITER_CHANNEL_COUNT_OF as usize]);
+impl Writers {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Routes the output of `channel` to `writer`, or drops it if `None`.
+    ///
+    /// # Panics
+    /// If `channel` is not a `SLANG_WRITER_CHANNEL_*` value.
+    #[inline]
+    pub fn set(&mut self, channel: WriterChannel, writer: Option<IWriterPtr>) {
+        self.0[channel as usize] = writer;
+    }
+
+    /// The writer for `channel`, if any.
+    #[inline]
+    pub fn get(&self, channel: WriterChannel) -> Option<&IWriterPtr> {
+        self.0.get(channel as usize)?.as_ref()
+    }
+
+    /// Runs `f` with a diagnostics out-parameter, and writes the diagnostic output it receives to the diagnostic
+    /// writer.
+    pub fn run<T>(&self, f: impl FnOnce(Option<&mut MaybeUninit<Option<IBlobPtr>>>) -> T) -> T {
+        let Some(writer) = self.get(SLANG_WRITER_CHANNEL_DIAGNOSTIC) else {
+            return f(None);
+        };
+
+        let mut d = MaybeUninit::new(None);
+        let r = f(Some(&mut d));
+        if let Some(blob) = unsafe { d.assume_init() } {
+            let text = blob_to_string(&blob);
+            let _ = unsafe {
+                (IWriter::vt(writer).write)(writer.thisptr(), text.as_ptr().cast(), text.len())
+            };
+            writer.flush();
+        }
+
+        r
+    }
+
+    /// Installs the writers on a compile request, for every channel.
+    #[allow(deprecated)]
+    pub fn install(&self, request: &crate::ICompileRequestObject) {
+        for (channel, writer) in self.0.iter().enumerate() {
+            request.set_writer(channel as _, writer.as_ref());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    #[test]
+    fn diagnostics_go_to_the_diagnostic_writer() {
+        let lines = Arc::new(Mutex::new(Vec::new()));
+        let mut writers = Writers::new();
+        writers.set(
+            SLANG_WRITER_CHANNEL_DIAGNOSTIC,
+            Some(IWriterPtr::from_lines({
+                let lines = lines.clone();
+                move |l| lines.lock().unwrap().push(l.to_owned())
+            })),
+        );
+
+        let r = writers.run(|d| {
+            d.unwrap().write(Some(IBlobPtr::from_string(
+                "a.slang(1): warning 1: x\r\nnote\n",
+            )));
+            7
+        });
+
+        assert_eq!(r, 7);
+        assert_eq!(*lines.lock().unwrap(), ["a.slang(1): warning 1: x", "note"]);
+    }
+
+    #[test]
+    fn no_diagnostic_writer() {
+        assert!(Writers::new().run(|d| d.is_none()));
+    }
+
+    #[test]
+    fn panicking_line_callback_on_release() {
+        let writer = IWriterPtr::from_lines(|_| panic!("callback"));
+        unsafe {
+            (IWriter::vt(&writer).write)(writer.thisptr(), c"unterminated".as_ptr(), 12);
+        }
+
+        // the pending line is passed on (and the panic caught) when the last reference is released
+        drop(writer);
+    }
+}